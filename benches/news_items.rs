@@ -0,0 +1,90 @@
+//! 複数年分のお知らせを読み込む際の、メモリ確保の回数と処理時間を計測します。
+//!
+//! `cargo bench --bench news_items`で実行できます。
+
+use chrono::{Duration, NaiveDate};
+use data_formats::structs::news::{borrowed::NewsItemsRef, NewsItems};
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    hint::black_box,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Instant,
+};
+
+/// メモリ確保の回数を数えるアロケーターです。
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// 計測の繰り返し回数です。
+const ITERATIONS: u32 = 20;
+/// 生成するお知らせの日数です。(約4年分)
+const DAYS: i64 = 365 * 4;
+/// 1日あたりのお知らせの件数です。
+const ITEMS_PER_DAY: i64 = 3;
+
+/// 複数年分のお知らせを、JSON形式の文字列として生成します。
+fn news_history() -> String {
+    let start = NaiveDate::from_ymd_opt(2020, 3, 25).unwrap();
+    let items = (0..DAYS)
+        .flat_map(|day| {
+            let date = start + Duration::days(day);
+            (0..ITEMS_PER_DAY).map(move |n| {
+                format!(
+                    r#"{{"date":"{}","text":"京都府内における新型コロナウイルス感染症の発生状況について（第{}報）","url":"https://www.pref.kyoto.jp/kentai/news/{}-{}.html"}}"#,
+                    date.format("%Y/%m/%d"),
+                    day * ITEMS_PER_DAY + n + 1,
+                    date.format("%Y%m%d"),
+                    n
+                )
+            })
+        })
+        .collect::<Vec<String>>()
+        .join(",");
+    format!(r#"{{"news_items":[{}]}}"#, items)
+}
+
+/// 処理を繰り返し実行し、1回あたりのメモリ確保の回数と処理時間を出力します。
+fn measure<F: FnMut()>(name: &str, mut f: F) {
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        f();
+    }
+    let elapsed = start.elapsed() / ITERATIONS;
+    let allocations = (ALLOCATIONS.load(Ordering::Relaxed) - allocations) / ITERATIONS as usize;
+    println!(
+        "{:<14} {:>10} allocations/iter {:>12?}/iter",
+        name, allocations, elapsed
+    );
+}
+
+fn main() {
+    let json = news_history();
+    println!(
+        "news_items: {} items, {} bytes",
+        DAYS * ITEMS_PER_DAY,
+        json.len()
+    );
+
+    measure("NewsItems", || {
+        black_box(serde_json::from_str::<NewsItems>(black_box(&json)).unwrap());
+    });
+    measure("NewsItemsRef", || {
+        black_box(serde_json::from_str::<NewsItemsRef>(black_box(&json)).unwrap());
+    });
+}
@@ -0,0 +1,8 @@
+#![no_main]
+
+use chrono::Local;
+use data_formats::structs::last_update::LastUpdate;
+use data_formats_fuzz::check_round_trip;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| check_round_trip::<LastUpdate<Local>>(data));
@@ -0,0 +1,7 @@
+#![no_main]
+
+use data_formats::structs::summary::Summary;
+use data_formats_fuzz::check_round_trip;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| check_round_trip::<Summary>(data));
@@ -0,0 +1,36 @@
+//! ファジングの対象となる処理を、各ターゲットで共通化します。
+
+use serde::{de::DeserializeOwned, Serialize};
+use std::fmt::Debug;
+
+/// 任意のバイト列をJSON形式としてデシリアライズし、パースできた場合は再シリアライズの一貫性を検証します。
+///
+/// デシリアライズの際に値は正規化される(例: 最終更新日時の秒は切り捨てられる)ため、
+/// 一度シリアライズした結果を基準として、再度パースした値と再度シリアライズした結果が一致することを確認します。
+pub fn check_round_trip<T>(data: &[u8])
+where
+    T: Serialize + DeserializeOwned + PartialEq + Debug,
+{
+    // パースできない入力は、エラーが返却されれば良い
+    let Ok(value) = serde_json::from_slice::<T>(data) else {
+        return;
+    };
+
+    let serialized = serde_json::to_vec(&value).expect("parsed value must be serializable");
+    let reparsed = serde_json::from_slice::<T>(&serialized).unwrap_or_else(|error| {
+        panic!(
+            "serialized value must be parsable: {}: {}",
+            error,
+            String::from_utf8_lossy(&serialized)
+        )
+    });
+    let reserialized = serde_json::to_vec(&reparsed).expect("reparsed value must be serializable");
+    assert_eq!(
+        String::from_utf8_lossy(&serialized),
+        String::from_utf8_lossy(&reserialized)
+    );
+    assert_eq!(
+        serde_json::from_slice::<T>(&reserialized).expect("reserialized value must be parsable"),
+        reparsed
+    );
+}
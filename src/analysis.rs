@@ -0,0 +1,76 @@
+//! 小計の一覧(`Summary`)を、日毎の値の系列として分析する機能を提供します。
+//!
+//! 各分析は、最初の小計の日付から最後の小計の日付までの全ての日付を対象とし、小計が存在しない日付の値は0として扱います。
+//! 同じ日付の小計が複数存在する場合は、その合計を使用します。
+
+use crate::structs::summary::Summary;
+use chrono::{Duration, NaiveDate};
+use std::{
+    collections::BTreeMap,
+    error::Error as StdError,
+    fmt::{Display, Error, Formatter},
+};
+
+pub mod anomaly;
+pub mod distribution;
+pub mod growth;
+pub mod rt;
+
+/// 分析の条件が不正な際のエラーです。
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct InvalidParameter {
+    /// 条件の名前です。
+    pub name: &'static str,
+    /// エラーの内容です。
+    pub message: String,
+}
+
+impl Display for InvalidParameter {
+    fn fmt(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        write!(formatter, "invalid `{}`: {}", self.name, self.message)
+    }
+}
+
+impl StdError for InvalidParameter {}
+
+/// 小計の一覧を、日付ごとの値に集計します。同じ日付の小計は合計します。
+///
+/// 合計が`u32`の範囲を超えても値が失われないよう、`u64`で集計します。
+pub fn daily_sums(summary: &Summary) -> BTreeMap<NaiveDate, u64> {
+    let mut daily = BTreeMap::<NaiveDate, u64>::new();
+    for content in &summary.data {
+        *daily.entry(content.date).or_default() += u64::from(content.sum);
+    }
+    daily
+}
+
+/// 小計の一覧を、欠けた日付の値を0とした、日付の昇順の日毎の値の系列に変換します。
+pub fn daily_values(summary: &Summary) -> Vec<(NaiveDate, u64)> {
+    let daily = daily_sums(summary);
+    let (Some(first), Some(last)) = (daily.keys().next(), daily.keys().next_back()) else {
+        return Vec::new();
+    };
+
+    let days = (*last - *first).num_days();
+    (0..=days)
+        .map(|offset| {
+            let date = *first + Duration::days(offset);
+            (date, daily.get(&date).copied().unwrap_or(0))
+        })
+        .collect()
+}
+
+/// 値の中央値を返却します。値が存在しない場合は`None`を返却します。
+pub(crate) fn median(values: &[f64]) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    let middle = sorted.len() / 2;
+    Some(if sorted.len().is_multiple_of(2) {
+        (sorted[middle - 1] + sorted[middle]) / 2.0
+    } else {
+        sorted[middle]
+    })
+}
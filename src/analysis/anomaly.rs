@@ -0,0 +1,214 @@
+//! 日毎の値の系列から、入力の誤り(桁の誤り等)が疑われる外れ値を検出します。
+//!
+//! 各日付の値を、直前の一定期間(窓)の値と比較し、中央値絶対偏差(MAD)による修正zスコアを求めます。
+//! 件数の値が小さい期間に僅かな変動を外れ値としないよう、標準偏差の推定値には、ポアソン分布の標準偏差(推定値の平方根)を下限として設けます。
+//! 曜日による変動を考慮する場合は、窓の中での曜日ごとの中央値と全体の中央値との差を、曜日の影響として取り除いてから比較します。
+
+use chrono::{DateTime, Datelike, Local, NaiveDate};
+use std::fmt::{Display, Error, Formatter};
+
+use super::{daily_values, median, InvalidParameter};
+use crate::{
+    structs::summary::Summary,
+    utils::formats::codec::{DATE, DATETIME},
+};
+
+/// 正規分布において、中央値絶対偏差から標準偏差を推定する際の除数です。(Iglewicz and Hoaglin, 1993)
+const MAD_SCALE: f64 = 0.6745;
+/// 正規分布において、平均絶対偏差を標準偏差に換算する係数です。
+const MEAN_AD_SCALE: f64 = 1.253314;
+
+/// 外れ値の検出の条件です。
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AnomalyOptions {
+    /// 比較に使用する、直前の日数です。
+    pub window: usize,
+    /// 比較に必要な、直前の最小の日数です。これより前の日付は評価しません。
+    pub min_history: usize,
+    /// 外れ値とする修正zスコアの絶対値の閾値です。
+    pub threshold: f64,
+    /// 曜日による変動を取り除いてから比較するかを指定します。
+    pub seasonality: bool,
+}
+
+impl Default for AnomalyOptions {
+    /// 直前の28日間と比較し、修正zスコアの絶対値が3.5以上の値を外れ値とします。
+    fn default() -> Self {
+        AnomalyOptions {
+            window: 28,
+            min_history: 14,
+            threshold: 3.5,
+            seasonality: true,
+        }
+    }
+}
+
+impl AnomalyOptions {
+    /// 条件が妥当であるか検証します。
+    pub fn validate(&self) -> Result<(), InvalidParameter> {
+        let invalid = |name, message: &str| {
+            Err(InvalidParameter {
+                name,
+                message: message.to_string(),
+            })
+        };
+        if self.window == 0 {
+            return invalid("window", "must be 1 or more");
+        }
+        if self.min_history == 0 || self.min_history > self.window {
+            return invalid("min_history", "must be between 1 and `window`");
+        }
+        if !(self.threshold.is_finite() && self.threshold > 0.0) {
+            return invalid("threshold", "must be a positive number");
+        }
+        Ok(())
+    }
+}
+
+/// 外れ値が疑われる日付です。
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Anomaly {
+    /// 日付です。
+    pub date: NaiveDate,
+    /// 小計の値です。
+    pub value: u64,
+    /// 直前の窓の値から推定した、その日付の典型的な値です。
+    pub expected: f64,
+    /// 修正zスコアです。正の値は推定より大きく、負の値は推定より小さいことを表します。
+    pub score: f64,
+}
+
+impl Display for Anomaly {
+    fn fmt(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        write!(
+            formatter,
+            "{}: {} (expected {:.1}, score {:+.2})",
+            DATE.format(&self.date),
+            self.value,
+            self.expected,
+            self.score
+        )
+    }
+}
+
+/// 外れ値の検出結果です。
+#[derive(Clone, Debug, PartialEq)]
+pub struct AnomalyReport {
+    /// 検出に使用した条件です。
+    pub options: AnomalyOptions,
+    /// 分析した小計の一覧の最終更新日時です。
+    pub last_update: DateTime<Local>,
+    /// 評価した日付の数です。
+    pub evaluated: usize,
+    /// 外れ値が疑われる日付を、日付の昇順に格納します。
+    pub anomalies: Vec<Anomaly>,
+}
+
+impl AnomalyReport {
+    /// 外れ値が検出されなかったかを返却します。
+    pub fn is_empty(&self) -> bool {
+        self.anomalies.is_empty()
+    }
+}
+
+impl Display for AnomalyReport {
+    fn fmt(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        writeln!(
+            formatter,
+            "{}: {} anomaly(ies) in {} evaluated day(s) (window {}, threshold {})",
+            DATETIME.format(&self.last_update),
+            self.anomalies.len(),
+            self.evaluated,
+            self.options.window,
+            self.options.threshold
+        )?;
+        for anomaly in &self.anomalies {
+            writeln!(formatter, "  {}", anomaly)?;
+        }
+        Ok(())
+    }
+}
+
+impl Summary {
+    /// 小計の一覧から、外れ値が疑われる日付を検出します。
+    pub fn anomalies(&self, options: &AnomalyOptions) -> Result<AnomalyReport, InvalidParameter> {
+        options.validate()?;
+        let series = daily_values(self);
+
+        let mut evaluated = 0;
+        let mut anomalies = Vec::new();
+        for (index, (date, value)) in series.iter().enumerate() {
+            let window = &series[index.saturating_sub(options.window)..index];
+            if window.len() < options.min_history {
+                continue;
+            }
+            evaluated += 1;
+            let (expected, score) = score(window, *date, *value as f64, options.seasonality);
+            if score.abs() >= options.threshold {
+                anomalies.push(Anomaly {
+                    date: *date,
+                    value: *value,
+                    expected,
+                    score,
+                });
+            }
+        }
+
+        Ok(AnomalyReport {
+            options: *options,
+            last_update: self.last_update,
+            evaluated,
+            anomalies,
+        })
+    }
+}
+
+/// 直前の窓の値と比較し、推定される典型的な値と修正zスコアを返却します。
+fn score(
+    window: &[(NaiveDate, u64)],
+    date: NaiveDate,
+    value: f64,
+    seasonality: bool,
+) -> (f64, f64) {
+    let values = window.iter().map(|(_, v)| *v as f64).collect::<Vec<f64>>();
+    let overall = median(&values).unwrap_or(0.0);
+
+    // 曜日ごとの中央値と全体の中央値との差を、曜日の影響とする
+    let effect = |weekday: u32| -> f64 {
+        if !seasonality {
+            return 0.0;
+        }
+        let same = window
+            .iter()
+            .filter(|(d, _)| d.weekday().num_days_from_monday() == weekday)
+            .map(|(_, v)| *v as f64)
+            .collect::<Vec<f64>>();
+        median(&same).map_or(0.0, |m| m - overall)
+    };
+    let effects = (0..7).map(effect).collect::<Vec<f64>>();
+    let effect_of = |date: &NaiveDate| effects[date.weekday().num_days_from_monday() as usize];
+
+    let adjusted = window
+        .iter()
+        .map(|(d, v)| *v as f64 - effect_of(d))
+        .collect::<Vec<f64>>();
+    let center = median(&adjusted).unwrap_or(0.0);
+    let expected = center + effect_of(&date);
+    let deviation = value - expected;
+
+    let deviations = adjusted
+        .iter()
+        .map(|v| (v - center).abs())
+        .collect::<Vec<f64>>();
+    let mad = median(&deviations).unwrap_or(0.0);
+    let robust = if mad > 0.0 {
+        mad / MAD_SCALE
+    } else {
+        // 半数以上の値が同じ場合は、平均絶対偏差で代用する
+        MEAN_AD_SCALE * deviations.iter().sum::<f64>() / deviations.len() as f64
+    };
+    // 件数の値はポアソン分布に従う揺らぎを持つため、その標準偏差を下限とする
+    let scale = robust.max(expected.max(1.0).sqrt());
+    let score = deviation / scale;
+    (expected, score)
+}
@@ -0,0 +1,184 @@
+//! 分析に使用する確率分布の関数を実装しています。
+//!
+//! 乱数を使用せず、同じ入力に対して常に同じ結果を返却します。
+
+/// ガンマ関数の対数を返却します。(Lanczos近似、g = 7)
+pub fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 9] = [
+        0.999_999_999_999_809_9,
+        676.520_368_121_885_1,
+        -1_259.139_216_722_402_8,
+        771.323_428_777_653_1,
+        -176.615_029_162_140_6,
+        12.507_343_278_686_905,
+        -0.138_571_095_265_720_12,
+        9.984_369_578_019_572e-6,
+        1.505_632_735_149_311_6e-7,
+    ];
+    if x < 0.5 {
+        // 相反公式により、0.5未満の値を求める
+        let pi = std::f64::consts::PI;
+        return (pi / (pi * x).sin()).ln() - ln_gamma(1.0 - x);
+    }
+    let x = x - 1.0;
+    let t = x + 7.5;
+    let series = COEFFICIENTS[1..]
+        .iter()
+        .enumerate()
+        .fold(COEFFICIENTS[0], |sum, (i, c)| {
+            sum + c / (x + i as f64 + 1.0)
+        });
+    0.5 * (2.0 * std::f64::consts::PI).ln() + (x + 0.5) * t.ln() - t + series.ln()
+}
+
+/// 正則化された下側不完全ガンマ関数 P(a, x) を返却します。
+pub fn gamma_p(a: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x < a + 1.0 {
+        // 級数展開
+        let mut term = 1.0 / a;
+        let mut sum = term;
+        for n in 1..1000 {
+            term *= x / (a + n as f64);
+            sum += term;
+            if term.abs() < sum.abs() * 1e-15 {
+                break;
+            }
+        }
+        (sum.ln() - x + a * x.ln() - ln_gamma(a)).exp()
+    } else {
+        // 連分数展開(Lentz法)
+        let tiny = 1e-300;
+        let mut b = x + 1.0 - a;
+        let mut c = 1.0 / tiny;
+        let mut d = 1.0 / b;
+        let mut h = d;
+        for i in 1..1000 {
+            let an = -(i as f64) * (i as f64 - a);
+            b += 2.0;
+            d = an * d + b;
+            if d.abs() < tiny {
+                d = tiny;
+            }
+            c = b + an / c;
+            if c.abs() < tiny {
+                c = tiny;
+            }
+            d = 1.0 / d;
+            let delta = d * c;
+            h *= delta;
+            if (delta - 1.0).abs() < 1e-15 {
+                break;
+            }
+        }
+        1.0 - (-x + a * x.ln() - ln_gamma(a)).exp() * h
+    }
+}
+
+/// 形状母数`shape`・尺度母数`scale`のガンマ分布の累積分布関数を返却します。
+pub fn gamma_cdf(x: f64, shape: f64, scale: f64) -> f64 {
+    gamma_p(shape, x / scale)
+}
+
+/// 形状母数`shape`・尺度母数`scale`のガンマ分布の分位点を返却します。
+pub fn gamma_quantile(p: f64, shape: f64, scale: f64) -> f64 {
+    // 平均を基準に上限を広げ、二分法で求める
+    let mut upper = shape * scale;
+    while gamma_cdf(upper, shape, scale) < p {
+        upper *= 2.0;
+    }
+    bisect(0.0, upper, |x| gamma_cdf(x, shape, scale) - p)
+}
+
+/// 正則化された不完全ベータ関数 I_x(a, b) を返却します。
+pub fn beta_i(a: f64, b: f64, x: f64) -> f64 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+    if x >= 1.0 {
+        return 1.0;
+    }
+    let front =
+        (ln_gamma(a + b) - ln_gamma(a) - ln_gamma(b) + a * x.ln() + b * (1.0 - x).ln()).exp();
+    // 連分数が速く収束する側で求める
+    if x < (a + 1.0) / (a + b + 2.0) {
+        front * beta_continued_fraction(a, b, x) / a
+    } else {
+        1.0 - front * beta_continued_fraction(b, a, 1.0 - x) / b
+    }
+}
+
+/// 不完全ベータ関数の連分数展開(Lentz法)を返却します。
+fn beta_continued_fraction(a: f64, b: f64, x: f64) -> f64 {
+    let tiny = 1e-300;
+    let mut c = 1.0;
+    let mut d = 1.0 - (a + b) * x / (a + 1.0);
+    if d.abs() < tiny {
+        d = tiny;
+    }
+    d = 1.0 / d;
+    let mut h = d;
+    for m in 1..1000 {
+        let m = m as f64;
+        // 偶数番目と奇数番目の項を、順に適用する
+        for an in [
+            m * (b - m) * x / ((a + 2.0 * m - 1.0) * (a + 2.0 * m)),
+            -(a + m) * (a + b + m) * x / ((a + 2.0 * m) * (a + 2.0 * m + 1.0)),
+        ] {
+            d = 1.0 + an * d;
+            if d.abs() < tiny {
+                d = tiny;
+            }
+            c = 1.0 + an / c;
+            if c.abs() < tiny {
+                c = tiny;
+            }
+            d = 1.0 / d;
+            h *= d * c;
+        }
+        if (d * c - 1.0).abs() < 1e-15 {
+            break;
+        }
+    }
+    h
+}
+
+/// 自由度`df`のt分布の累積分布関数を返却します。
+pub fn student_t_cdf(t: f64, df: f64) -> f64 {
+    let tail = 0.5 * beta_i(df / 2.0, 0.5, df / (df + t * t));
+    if t > 0.0 {
+        1.0 - tail
+    } else {
+        tail
+    }
+}
+
+/// 自由度`df`のt分布の分位点を返却します。
+pub fn student_t_quantile(p: f64, df: f64) -> f64 {
+    if p < 0.5 {
+        return -student_t_quantile(1.0 - p, df);
+    }
+    let mut upper = 1.0;
+    while student_t_cdf(upper, df) < p {
+        upper *= 2.0;
+    }
+    bisect(0.0, upper, |t| student_t_cdf(t, df) - p)
+}
+
+/// 単調増加な関数`f`が0となる値を、区間`[lower, upper]`から二分法で求めます。
+pub(crate) fn bisect(mut lower: f64, mut upper: f64, f: impl Fn(f64) -> f64) -> f64 {
+    for _ in 0..200 {
+        let middle = (lower + upper) / 2.0;
+        if f(middle) < 0.0 {
+            lower = middle;
+        } else {
+            upper = middle;
+        }
+        if upper - lower <= f64::EPSILON * upper.abs().max(1.0) {
+            break;
+        }
+    }
+    (lower + upper) / 2.0
+}
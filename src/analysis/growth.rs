@@ -0,0 +1,196 @@
+//! 日毎の値の系列から、指数的な増加・減少の速さ(増加率・倍加時間・半減時間)を求めます。
+//!
+//! 一定の期間(窓)の値の対数を日付に対して最小二乗法で回帰し、その傾きを1日あたりの増加率とします。
+//! 信頼区間は、傾きの標準誤差とt分布から求めます。乱数を使用しないため、同じ入力に対して常に同じ結果を返却します。
+
+use chrono::{DateTime, Local, NaiveDate};
+use serde::{ser::SerializeStruct, Serialize, Serializer};
+use std::f64::consts::LN_2;
+
+use super::{daily_values, distribution::student_t_quantile, InvalidParameter};
+use crate::{
+    structs::summary::Summary,
+    utils::formats::codec::{DATE, DATETIME},
+};
+
+/// 増加率の推定の条件です。
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GrowthOptions {
+    /// 回帰に使用する期間の日数です。信頼区間を求めるため、3日以上である必要があります。
+    pub window: usize,
+    /// 信頼区間の確率です。
+    pub confidence: f64,
+}
+
+impl Default for GrowthOptions {
+    /// 7日間の値から、95%信頼区間を求めます。
+    fn default() -> Self {
+        GrowthOptions {
+            window: 7,
+            confidence: 0.95,
+        }
+    }
+}
+
+impl GrowthOptions {
+    /// 条件が妥当であるか検証します。
+    pub fn validate(&self) -> Result<(), InvalidParameter> {
+        let invalid = |name, message: &str| {
+            Err(InvalidParameter {
+                name,
+                message: message.to_string(),
+            })
+        };
+        if self.window < 3 {
+            return invalid("window", "must be 3 or more");
+        }
+        if !(self.confidence > 0.0 && self.confidence < 1.0) {
+            return invalid("confidence", "must be between 0 and 1");
+        }
+        Ok(())
+    }
+}
+
+/// ある期間の増加率の推定値です。
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GrowthEstimate {
+    /// 期間の初日です。
+    pub start: NaiveDate,
+    /// 期間の最終日です。推定値は、この日付の値として表示することを想定しています。
+    pub end: NaiveDate,
+    /// 1日あたりの増加率(対数の傾き)です。負の値は減少を表します。
+    pub rate: f64,
+    /// 増加率の信頼区間の下限です。
+    pub lower: f64,
+    /// 増加率の信頼区間の上限です。
+    pub upper: f64,
+}
+
+impl GrowthEstimate {
+    /// 値が2倍になるまでの日数を返却します。増加していない場合は`None`です。
+    pub fn doubling_time(&self) -> Option<f64> {
+        (self.rate > 0.0).then(|| LN_2 / self.rate)
+    }
+
+    /// 倍加時間の信頼区間を、(下限, 上限)の順に返却します。増加率の信頼区間が0以下の値を含む場合は`None`です。
+    pub fn doubling_time_interval(&self) -> Option<(f64, f64)> {
+        (self.lower > 0.0).then(|| (LN_2 / self.upper, LN_2 / self.lower))
+    }
+
+    /// 値が半分になるまでの日数を返却します。減少していない場合は`None`です。
+    pub fn halving_time(&self) -> Option<f64> {
+        (self.rate < 0.0).then(|| LN_2 / -self.rate)
+    }
+
+    /// 半減時間の信頼区間を、(下限, 上限)の順に返却します。増加率の信頼区間が0以上の値を含む場合は`None`です。
+    pub fn halving_time_interval(&self) -> Option<(f64, f64)> {
+        (self.upper < 0.0).then(|| (LN_2 / -self.lower, LN_2 / -self.upper))
+    }
+}
+
+/// 増加率の推定値の系列です。
+#[derive(Clone, Debug, PartialEq)]
+pub struct GrowthSeries {
+    /// 推定に使用した小計の一覧の最終更新日時です。
+    pub last_update: DateTime<Local>,
+    /// 信頼区間の確率です。
+    pub confidence: f64,
+    /// 推定値を、期間の最終日の昇順に格納します。
+    pub data: Vec<GrowthEstimate>,
+}
+
+/// GrowthEstimateのシリアライズ処理の実装です。
+impl Serialize for GrowthEstimate {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let doubling = self.doubling_time_interval();
+        let halving = self.halving_time_interval();
+        let mut state = serializer.serialize_struct("GrowthEstimate", 11)?;
+        // 日付は、小計と同じ書式で出力する
+        state.serialize_field("start", &DATE.format(&self.start))?;
+        state.serialize_field("end", &DATE.format(&self.end))?;
+        state.serialize_field("rate", &self.rate)?;
+        state.serialize_field("lower", &self.lower)?;
+        state.serialize_field("upper", &self.upper)?;
+        // 求められない倍加時間・半減時間は、nullとして出力する
+        state.serialize_field("doubling_time", &self.doubling_time())?;
+        state.serialize_field("doubling_time_lower", &doubling.map(|(lower, _)| lower))?;
+        state.serialize_field("doubling_time_upper", &doubling.map(|(_, upper)| upper))?;
+        state.serialize_field("halving_time", &self.halving_time())?;
+        state.serialize_field("halving_time_lower", &halving.map(|(lower, _)| lower))?;
+        state.serialize_field("halving_time_upper", &halving.map(|(_, upper)| upper))?;
+        state.end()
+    }
+}
+
+/// GrowthSeriesのシリアライズ処理の実装です。
+impl Serialize for GrowthSeries {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("GrowthSeries", 3)?;
+        state.serialize_field("data", &self.data)?;
+        state.serialize_field("confidence", &self.confidence)?;
+        state.serialize_field("last_update", &DATETIME.format(&self.last_update))?;
+        state.end()
+    }
+}
+
+impl Summary {
+    /// 小計の一覧から、期間ごとの増加率と倍加時間・半減時間を推定します。
+    ///
+    /// 対数を取れない0の値を含む期間は、推定しません。
+    pub fn growth_rates(&self, options: &GrowthOptions) -> Result<GrowthSeries, InvalidParameter> {
+        options.validate()?;
+        let series = daily_values(self);
+        let n = options.window as f64;
+        let t = student_t_quantile(1.0 - (1.0 - options.confidence) / 2.0, n - 2.0);
+
+        // 日付は期間内の0始まりの日数とするため、その平均と偏差平方和は期間によらず一定となる
+        let x_mean = (n - 1.0) / 2.0;
+        let sxx = (0..options.window)
+            .map(|x| (x as f64 - x_mean).powi(2))
+            .sum::<f64>();
+
+        let mut data = Vec::new();
+        for window in series.windows(options.window) {
+            if window.iter().any(|(_, v)| *v == 0) {
+                continue;
+            }
+            let y = window
+                .iter()
+                .map(|(_, v)| (*v as f64).ln())
+                .collect::<Vec<f64>>();
+            let y_mean = y.iter().sum::<f64>() / n;
+            let sxy = y
+                .iter()
+                .enumerate()
+                .map(|(x, y)| (x as f64 - x_mean) * (y - y_mean))
+                .sum::<f64>();
+            let rate = sxy / sxx;
+            let sse = y
+                .iter()
+                .enumerate()
+                .map(|(x, y)| (y - y_mean - rate * (x as f64 - x_mean)).powi(2))
+                .sum::<f64>();
+            let error = (sse / (n - 2.0) / sxx).sqrt();
+
+            data.push(GrowthEstimate {
+                start: window[0].0,
+                end: window[options.window - 1].0,
+                rate,
+                lower: rate - t * error,
+                upper: rate + t * error,
+            });
+        }
+
+        Ok(GrowthSeries {
+            last_update: self.last_update,
+            confidence: options.confidence,
+            data,
+        })
+    }
+}
@@ -0,0 +1,270 @@
+//! 日毎の新規感染者数から、実効再生産数(Rt)を推定します。
+//!
+//! Cori et al. (2013) "A New Framework and Software to Estimate Time-Varying Reproduction Numbers During Epidemics"
+//! の方法により、一定の期間(窓)の間Rtが一定であると仮定し、ガンマ分布の事前分布に対する事後分布を解析的に求めます。
+//! 乱数を使用しないため、同じ入力に対して常に同じ結果を返却します。
+
+use chrono::{DateTime, Local, NaiveDate};
+use serde::{ser::SerializeStruct, Serialize, Serializer};
+
+use super::{
+    daily_values,
+    distribution::{gamma_cdf, gamma_quantile},
+    InvalidParameter,
+};
+use crate::{
+    structs::summary::Summary,
+    utils::formats::codec::{DATE, DATETIME},
+};
+
+/// 離散化した発症間隔の分布の、無視する裾の確率です。
+const TAIL: f64 = 1e-6;
+/// 離散化した発症間隔の分布の、最大の日数です。
+const MAX_DAYS: usize = 365;
+
+/// 発症間隔(serial interval)の分布です。
+#[derive(Clone, Debug, PartialEq)]
+pub enum SerialInterval {
+    /// 平均と標準偏差(日数)を指定したガンマ分布を、Cori et al. (2013)の方法で1日単位に離散化した分布です。
+    ///
+    /// 1日分ずらしたガンマ分布を離散化するため、平均は1より大きい必要があります。
+    Gamma { mean: f64, sd: f64 },
+    /// 1日後からの各日の確率を、順に指定した分布です。合計が1となるよう正規化して使用します。
+    Discrete(Vec<f64>),
+}
+
+impl SerialInterval {
+    /// 0日後からの各日の確率を返却します。0日後の確率は常に0です。
+    pub fn weights(&self) -> Result<Vec<f64>, InvalidParameter> {
+        let invalid = |message: &str| InvalidParameter {
+            name: "serial_interval",
+            message: message.to_string(),
+        };
+        let mut weights = match self {
+            SerialInterval::Gamma { mean, sd } => {
+                if !(mean.is_finite() && *mean > 1.0 && sd.is_finite() && *sd > 0.0) {
+                    return Err(invalid(
+                        "mean must be greater than 1 and sd must be positive",
+                    ));
+                }
+                discretize_gamma(*mean, *sd)
+            }
+            SerialInterval::Discrete(weights) => {
+                if weights.iter().any(|w| !w.is_finite() || *w < 0.0) {
+                    return Err(invalid("weights must be non-negative numbers"));
+                }
+                std::iter::once(0.0)
+                    .chain(weights.iter().copied())
+                    .collect()
+            }
+        };
+        let total = weights.iter().sum::<f64>();
+        if total <= 0.0 {
+            return Err(invalid("weights must have a positive sum"));
+        }
+        weights.iter_mut().for_each(|w| *w /= total);
+        Ok(weights)
+    }
+}
+
+/// 1日分ずらしたガンマ分布を、1日単位に離散化します。(Cori et al. (2013) Web Appendix 11)
+fn discretize_gamma(mean: f64, sd: f64) -> Vec<f64> {
+    let shape = ((mean - 1.0) / sd).powi(2);
+    let scale = sd * sd / (mean - 1.0);
+    let cdf = |k: f64, shape: f64| {
+        if k <= 0.0 {
+            0.0
+        } else {
+            gamma_cdf(k, shape, scale)
+        }
+    };
+
+    let mut weights = vec![0.0];
+    for k in 1..=MAX_DAYS {
+        let k = k as f64;
+        let weight = k * cdf(k, shape) + (k - 2.0) * cdf(k - 2.0, shape)
+            - 2.0 * (k - 1.0) * cdf(k - 1.0, shape)
+            + shape
+                * scale
+                * (2.0 * cdf(k - 1.0, shape + 1.0)
+                    - cdf(k - 2.0, shape + 1.0)
+                    - cdf(k, shape + 1.0));
+        weights.push(weight.max(0.0));
+        if cdf(k - 1.0, shape) > 1.0 - TAIL {
+            break;
+        }
+    }
+    weights
+}
+
+/// 実効再生産数の推定の条件です。
+#[derive(Clone, Debug, PartialEq)]
+pub struct RtOptions {
+    /// 発症間隔の分布です。
+    pub serial_interval: SerialInterval,
+    /// Rtが一定であると仮定する期間の日数です。
+    pub window: usize,
+    /// Rtの事前分布(ガンマ分布)の平均です。
+    pub prior_mean: f64,
+    /// Rtの事前分布(ガンマ分布)の標準偏差です。
+    pub prior_sd: f64,
+    /// 信用区間の確率です。
+    pub credibility: f64,
+}
+
+impl RtOptions {
+    /// 発症間隔の分布を指定し、その他の条件はCori et al. (2013)の既定値(窓は7日間、事前分布は平均5・標準偏差5、95%信用区間)とします。
+    pub fn new(serial_interval: SerialInterval) -> RtOptions {
+        RtOptions {
+            serial_interval,
+            window: 7,
+            prior_mean: 5.0,
+            prior_sd: 5.0,
+            credibility: 0.95,
+        }
+    }
+
+    /// 条件が妥当であるか検証します。
+    pub fn validate(&self) -> Result<(), InvalidParameter> {
+        let invalid = |name, message: &str| {
+            Err(InvalidParameter {
+                name,
+                message: message.to_string(),
+            })
+        };
+        if self.window == 0 {
+            return invalid("window", "must be 1 or more");
+        }
+        if !(self.prior_mean.is_finite() && self.prior_mean > 0.0) {
+            return invalid("prior_mean", "must be a positive number");
+        }
+        if !(self.prior_sd.is_finite() && self.prior_sd > 0.0) {
+            return invalid("prior_sd", "must be a positive number");
+        }
+        if !(self.credibility > 0.0 && self.credibility < 1.0) {
+            return invalid("credibility", "must be between 0 and 1");
+        }
+        Ok(())
+    }
+}
+
+/// ある期間の実効再生産数の推定値です。
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RtEstimate {
+    /// 期間の初日です。
+    pub start: NaiveDate,
+    /// 期間の最終日です。推定値は、この日付の値として表示することを想定しています。
+    pub end: NaiveDate,
+    /// 期間内の新規感染者数の合計です。少ない場合は、推定値の信頼性が低くなります。
+    pub incidence: u64,
+    /// 事後分布の平均です。
+    pub mean: f64,
+    /// 事後分布の標準偏差です。
+    pub sd: f64,
+    /// 事後分布の中央値です。
+    pub median: f64,
+    /// 信用区間の下限です。
+    pub lower: f64,
+    /// 信用区間の上限です。
+    pub upper: f64,
+}
+
+/// 実効再生産数の推定値の系列です。
+#[derive(Clone, Debug, PartialEq)]
+pub struct RtSeries {
+    /// 推定に使用した小計の一覧の最終更新日時です。
+    pub last_update: DateTime<Local>,
+    /// 信用区間の確率です。
+    pub credibility: f64,
+    /// 推定値を、期間の最終日の昇順に格納します。
+    pub data: Vec<RtEstimate>,
+}
+
+/// RtEstimateのシリアライズ処理の実装です。
+impl Serialize for RtEstimate {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("RtEstimate", 8)?;
+        // 日付は、小計と同じ書式で出力する
+        state.serialize_field("start", &DATE.format(&self.start))?;
+        state.serialize_field("end", &DATE.format(&self.end))?;
+        state.serialize_field("incidence", &self.incidence)?;
+        state.serialize_field("mean", &self.mean)?;
+        state.serialize_field("sd", &self.sd)?;
+        state.serialize_field("median", &self.median)?;
+        state.serialize_field("lower", &self.lower)?;
+        state.serialize_field("upper", &self.upper)?;
+        state.end()
+    }
+}
+
+/// RtSeriesのシリアライズ処理の実装です。
+impl Serialize for RtSeries {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("RtSeries", 3)?;
+        state.serialize_field("data", &self.data)?;
+        state.serialize_field("credibility", &self.credibility)?;
+        state.serialize_field("last_update", &DATETIME.format(&self.last_update))?;
+        state.end()
+    }
+}
+
+impl Summary {
+    /// 新規感染者数の小計の一覧から、実効再生産数を推定します。
+    ///
+    /// 各期間の推定値は、期間の前日までに感染者が存在し、感染力(過去の感染者数を発症間隔で重み付けした合計)が正の場合のみ求めます。
+    pub fn reproduction_numbers(&self, options: &RtOptions) -> Result<RtSeries, InvalidParameter> {
+        options.validate()?;
+        let weights = options.serial_interval.weights()?;
+        let series = daily_values(self);
+        let incidence = series.iter().map(|(_, v)| *v as f64).collect::<Vec<f64>>();
+
+        // 各日の感染力を求める
+        let infectivity = (0..incidence.len())
+            .map(|t| {
+                (1..weights.len().min(t + 1))
+                    .map(|s| incidence[t - s] * weights[s])
+                    .sum::<f64>()
+            })
+            .collect::<Vec<f64>>();
+
+        // 事前分布の形状母数と尺度母数
+        let prior_shape = (options.prior_mean / options.prior_sd).powi(2);
+        let prior_scale = options.prior_sd.powi(2) / options.prior_mean;
+        let tail = (1.0 - options.credibility) / 2.0;
+
+        let mut data = Vec::new();
+        for end in options.window..incidence.len() {
+            let start = end + 1 - options.window;
+            let cases = incidence[start..=end].iter().sum::<f64>();
+            let force = infectivity[start..=end].iter().sum::<f64>();
+            if force <= 0.0 {
+                continue;
+            }
+
+            let shape = prior_shape + cases;
+            let scale = 1.0 / (1.0 / prior_scale + force);
+            data.push(RtEstimate {
+                start: series[start].0,
+                end: series[end].0,
+                incidence: cases as u64,
+                mean: shape * scale,
+                sd: shape.sqrt() * scale,
+                median: gamma_quantile(0.5, shape, scale),
+                lower: gamma_quantile(tail, shape, scale),
+                upper: gamma_quantile(1.0 - tail, shape, scale),
+            });
+        }
+
+        Ok(RtSeries {
+            last_update: self.last_update,
+            credibility: options.credibility,
+            data,
+        })
+    }
+}
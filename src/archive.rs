@@ -0,0 +1,297 @@
+//! 日々のデータファイル(`main_summary.json`・`patients_summary.json`)のスナップショットを、最終更新日時ごとに索引する機能を提供します。
+//!
+//! 索引したスナップショットから、指定された日付に対策サイトが表示していた内容や、属性ごとの値の推移を求められます。
+
+use crate::{
+    document::{Document, DocumentType},
+    migration::MigrationError,
+    structs::{
+        status::{Attributes, Status},
+        summary::{Summary, SummaryContent},
+    },
+    utils::formats::{codec::DateInput, jst},
+};
+use chrono::{DateTime, Local, NaiveDate};
+use std::{
+    collections::BTreeMap,
+    error::Error as StdError,
+    fmt::{Display, Error, Formatter},
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// COVID-19に関する情報(`Status`)のデータファイル名の接頭辞です。
+pub const STATUS_FILE_PREFIX: &str = "main_summary";
+/// 小計の一覧(`Summary`)のデータファイル名の接頭辞です。
+pub const SUMMARY_FILE_PREFIX: &str = "patients_summary";
+
+/// スナップショットの読み込みに失敗した際のエラーです。
+#[derive(Debug)]
+pub enum ArchiveError {
+    /// ファイルの読み込みに失敗しました。
+    Io { path: PathBuf, error: io::Error },
+    /// データファイルの形式が不正です。
+    Document {
+        path: PathBuf,
+        error: MigrationError,
+    },
+    /// 最終更新日時(`last_update`)を持たないStatusは、索引できません。
+    ///
+    /// ファイルから読み込んだ場合は、そのパスを保持します。
+    MissingLastUpdate { path: Option<PathBuf> },
+    /// 同じ最終更新日時を持つ、内容の異なるスナップショットが存在します。
+    ///
+    /// ファイルから読み込んだ場合は、そのパスを保持します。
+    Conflict {
+        path: Option<PathBuf>,
+        last_update: DateTime<Local>,
+    },
+    /// 日付として解釈できない引数です。
+    InvalidDate(String),
+}
+
+impl Display for ArchiveError {
+    fn fmt(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        match self {
+            ArchiveError::Io { path, error } => write!(formatter, "{}: {}", path.display(), error),
+            ArchiveError::Document { path, error } => {
+                write!(formatter, "{}: {}", path.display(), error)
+            }
+            ArchiveError::MissingLastUpdate { path } => {
+                write_path(formatter, path)?;
+                write!(formatter, "status has no last_update")
+            }
+            ArchiveError::Conflict { path, last_update } => {
+                write_path(formatter, path)?;
+                write!(
+                    formatter,
+                    "another snapshot with last_update {} has different contents",
+                    last_update
+                )
+            }
+            ArchiveError::InvalidDate(message) => write!(formatter, "{}", message),
+        }
+    }
+}
+
+/// ファイルのパスが存在する場合は、エラーの前に出力します。
+fn write_path(formatter: &mut Formatter, path: &Option<PathBuf>) -> Result<(), Error> {
+    match path {
+        Some(path) => write!(formatter, "{}: ", path.display()),
+        None => Ok(()),
+    }
+}
+
+impl StdError for ArchiveError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            ArchiveError::Io { error, .. } => Some(error),
+            ArchiveError::Document { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl ArchiveError {
+    /// ファイルのパスを持たないエラーに、読み込んだファイルのパスを設定します。
+    fn with_path(self, file: &Path) -> ArchiveError {
+        match self {
+            ArchiveError::MissingLastUpdate { .. } => ArchiveError::MissingLastUpdate {
+                path: Some(file.to_path_buf()),
+            },
+            ArchiveError::Conflict { last_update, .. } => ArchiveError::Conflict {
+                path: Some(file.to_path_buf()),
+                last_update,
+            },
+            error => error,
+        }
+    }
+}
+
+/// ある日付に対策サイトが表示していた、データファイルの内容です。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SiteView<'a> {
+    /// その日付の終わりの時点で最新であった、COVID-19に関する情報です。
+    pub status: Option<&'a Status>,
+    /// その日付の終わりの時点で最新であった、小計の一覧です。
+    pub summary: Option<&'a Summary>,
+}
+
+/// スナップショットを、最終更新日時ごとに索引したものです。
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Archive {
+    statuses: BTreeMap<DateTime<Local>, Status>,
+    summaries: BTreeMap<DateTime<Local>, Summary>,
+}
+
+impl Archive {
+    /// 空の索引を生成します。
+    pub fn new() -> Archive {
+        Archive::default()
+    }
+
+    /// ディレクトリ(サブディレクトリを含む)に格納されたスナップショットを読み込みます。
+    ///
+    /// ファイル名が`main_summary`で始まるJSONファイルをStatus、`patients_summary`で始まるJSONファイルをSummaryとして読み込み、
+    /// その他のファイルは無視します。以前の版のデータファイルは、現在の版に変換した上で読み込みます。
+    pub fn load_dir(path: impl AsRef<Path>) -> Result<Archive, ArchiveError> {
+        let mut archive = Archive::new();
+        for file in json_files(path.as_ref())? {
+            let document_type = match file.file_name().and_then(|v| v.to_str()) {
+                Some(name) if name.starts_with(STATUS_FILE_PREFIX) => DocumentType::Status,
+                Some(name) if name.starts_with(SUMMARY_FILE_PREFIX) => DocumentType::Summary,
+                _ => continue,
+            };
+            let json = fs::read_to_string(&file).map_err(|error| ArchiveError::Io {
+                path: file.clone(),
+                error,
+            })?;
+            let document =
+                Document::from_versioned_json(document_type, &json).map_err(|error| {
+                    ArchiveError::Document {
+                        path: file.clone(),
+                        error,
+                    }
+                })?;
+            match document {
+                Document::Status(status) => archive.insert_status(status),
+                Document::Summary(summary) => archive.insert_summary(summary),
+                _ => Ok(()),
+            }
+            .map_err(|error| error.with_path(&file))?;
+        }
+        Ok(archive)
+    }
+
+    /// COVID-19に関する情報を、最終更新日時のスナップショットとして追加します。
+    ///
+    /// 同じ最終更新日時を持つ、内容の異なるスナップショットが既に存在する場合は、エラーを返却します。
+    pub fn insert_status(&mut self, status: Status) -> Result<(), ArchiveError> {
+        let last_update = status
+            .last_update
+            .ok_or(ArchiveError::MissingLastUpdate { path: None })?;
+        insert(&mut self.statuses, last_update, status)
+    }
+
+    /// 小計の一覧を、最終更新日時のスナップショットとして追加します。
+    ///
+    /// 同じ最終更新日時を持つ、内容の異なるスナップショットが既に存在する場合は、エラーを返却します。
+    pub fn insert_summary(&mut self, summary: Summary) -> Result<(), ArchiveError> {
+        insert(&mut self.summaries, summary.last_update, summary)
+    }
+
+    /// 索引されたCOVID-19に関する情報を、最終更新日時の古い順に返却します。
+    pub fn statuses(&self) -> impl Iterator<Item = (&DateTime<Local>, &Status)> {
+        self.statuses.iter()
+    }
+
+    /// 索引された小計の一覧を、最終更新日時の古い順に返却します。
+    pub fn summaries(&self) -> impl Iterator<Item = (&DateTime<Local>, &Summary)> {
+        self.summaries.iter()
+    }
+
+    /// 指定された日付(日本標準時)の終わりの時点で、最新であったCOVID-19に関する情報を返却します。
+    pub fn status_on(
+        &self,
+        date: impl DateInput<NaiveDate>,
+    ) -> Result<Option<&Status>, ArchiveError> {
+        let date = date.into_date().map_err(ArchiveError::InvalidDate)?;
+        Ok(latest_on(&self.statuses, date))
+    }
+
+    /// 指定された日付(日本標準時)の終わりの時点で、最新であった小計の一覧を返却します。
+    pub fn summary_on(
+        &self,
+        date: impl DateInput<NaiveDate>,
+    ) -> Result<Option<&Summary>, ArchiveError> {
+        let date = date.into_date().map_err(ArchiveError::InvalidDate)?;
+        Ok(latest_on(&self.summaries, date))
+    }
+
+    /// 指定された日付(日本標準時)に、対策サイトが表示していた内容を返却します。
+    pub fn site_on(&self, date: impl DateInput<NaiveDate>) -> Result<SiteView<'_>, ArchiveError> {
+        let date = date.into_date().map_err(ArchiveError::InvalidDate)?;
+        Ok(SiteView {
+            status: latest_on(&self.statuses, date),
+            summary: latest_on(&self.summaries, date),
+        })
+    }
+
+    /// 指定された属性の値の推移を、最終更新日(日本標準時)ごとの小計の一覧として返却します。
+    ///
+    /// 同じ日に複数のスナップショットが存在する場合は、最も新しいスナップショットの値を使用します。
+    /// 属性を含むスナップショットが存在しない場合は、`None`を返却します。
+    pub fn history(&self, attr: &Attributes) -> Option<Summary> {
+        let mut values = BTreeMap::<NaiveDate, u32>::new();
+        let mut last_update = None;
+        for (datetime, status) in &self.statuses {
+            if let Some(found) = status.find(attr) {
+                values.insert(jst_date(datetime), found.value);
+                last_update = Some(*datetime);
+            }
+        }
+
+        Some(Summary {
+            data: values
+                .into_iter()
+                .map(|(date, sum)| SummaryContent { date, sum })
+                .collect(),
+            last_update: last_update?,
+        })
+    }
+}
+
+/// スナップショットを追加します。同じ最終更新日時の、内容の異なるスナップショットが存在する場合はエラーとします。
+fn insert<T: PartialEq>(
+    snapshots: &mut BTreeMap<DateTime<Local>, T>,
+    last_update: DateTime<Local>,
+    snapshot: T,
+) -> Result<(), ArchiveError> {
+    match snapshots.get(&last_update) {
+        Some(existing) if *existing != snapshot => Err(ArchiveError::Conflict {
+            path: None,
+            last_update,
+        }),
+        Some(_) => Ok(()),
+        None => {
+            snapshots.insert(last_update, snapshot);
+            Ok(())
+        }
+    }
+}
+
+/// 日時を、日本標準時の日付に変換します。
+fn jst_date(datetime: &DateTime<Local>) -> NaiveDate {
+    datetime.with_timezone(&jst()).date_naive()
+}
+
+/// 最終更新日(日本標準時)が指定された日付以前のスナップショットのうち、最も新しいものを返却します。
+fn latest_on<T>(snapshots: &BTreeMap<DateTime<Local>, T>, date: NaiveDate) -> Option<&T> {
+    snapshots
+        .iter()
+        .rev()
+        .find(|(datetime, _)| jst_date(datetime) <= date)
+        .map(|(_, snapshot)| snapshot)
+}
+
+/// ディレクトリ(サブディレクトリを含む)に格納されたJSONファイルを、パスの順に列挙します。
+fn json_files(directory: &Path) -> Result<Vec<PathBuf>, ArchiveError> {
+    let io_error = |error| ArchiveError::Io {
+        path: directory.to_path_buf(),
+        error,
+    };
+    let mut files = Vec::new();
+    for entry in fs::read_dir(directory).map_err(io_error)? {
+        let path = entry.map_err(io_error)?.path();
+        if path.is_dir() {
+            files.extend(json_files(&path)?);
+        } else if path
+            .extension()
+            .is_some_and(|extension| extension == "json")
+        {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
@@ -0,0 +1,469 @@
+//! データファイルの検証・整形・要約を行うコマンドラインツールです。
+
+use clap::{
+    builder::{PossibleValuesParser, TypedValueParser},
+    error::ErrorKind,
+    CommandFactory, Parser, Subcommand,
+};
+use data_formats::{
+    analysis::{
+        anomaly::AnomalyOptions,
+        growth::GrowthOptions,
+        rt::{RtOptions, SerialInterval},
+    },
+    convert::{self, Format},
+    document::{Document, DocumentType},
+    migration::{upgrade_json, MigrationError},
+    structs::{
+        news::localized::{Fallback, Locale, LocalizedNewsItems},
+        summary::DateRepresentation,
+    },
+};
+use std::{
+    fs,
+    io::{self, Read, Write},
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+/// 京都府 新型コロナウイルス感染症 対策サイトのデータファイルを取り扱います。
+#[derive(Parser)]
+#[command(name = "covid19-kyoto", version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// データファイルの形式を検証します。
+    Validate {
+        /// データファイルのパスです。(`-`を指定した場合は標準入力から読み込みます)
+        file: PathBuf,
+        /// データファイルの種別です。
+        #[arg(long = "type", short = 't', value_parser = document_types())]
+        document_type: DocumentType,
+        /// お知らせに同じURLが含まれている場合も、不正なデータとして扱います。(`--type news`の場合のみ指定できます)
+        #[arg(long)]
+        strict: bool,
+    },
+    /// データファイルを、正規化された形式で出力します。
+    Fmt {
+        /// データファイルのパスです。(`-`を指定した場合は標準入力から読み込みます)
+        file: PathBuf,
+        /// データファイルの種別です。
+        #[arg(long = "type", short = 't', value_parser = document_types())]
+        document_type: DocumentType,
+        /// 標準出力ではなく、データファイルを上書きします。(標準入力から読み込む場合は指定できません)
+        #[arg(long, short = 'w')]
+        write: bool,
+        /// 小計の日付を、以前のRFC3339形式(例: `2020-03-24T15:00:00+00:00`)で出力します。
+        #[arg(long)]
+        legacy_dates: bool,
+    },
+    /// データファイルの内容を、表形式で要約します。
+    Inspect {
+        /// データファイルのパスです。(`-`を指定した場合は標準入力から読み込みます)
+        file: PathBuf,
+        /// データファイルの種別です。
+        #[arg(long = "type", short = 't', value_parser = document_types())]
+        document_type: DocumentType,
+    },
+    /// データファイルを、別のデータ形式に変換します。
+    Convert {
+        /// データファイルのパスです。(`-`を指定した場合は標準入力から読み込みます)
+        file: PathBuf,
+        /// データファイルの種別です。
+        #[arg(long = "type", short = 't', value_parser = document_types())]
+        document_type: DocumentType,
+        /// 変換元のデータ形式です。(json, csv, yaml, msgpack)
+        #[arg(long, default_value = "json")]
+        from: Format,
+        /// 変換先のデータ形式です。(json, csv, yaml, msgpack)
+        #[arg(long)]
+        to: Format,
+        /// 変換結果を書き出すファイルのパスです。(省略した場合は標準出力に書き出します)
+        #[arg(long, short = 'o')]
+        output: Option<PathBuf>,
+        /// 小計の日付を、以前のRFC3339形式(例: `2020-03-24T15:00:00+00:00`)で出力します。
+        #[arg(long)]
+        legacy_dates: bool,
+    },
+    /// 以前の版のデータファイルを、現在の版数(`version`)を含む形式に変換します。
+    Migrate {
+        /// データファイルのパスです。(`-`を指定した場合は標準入力から読み込みます)
+        file: PathBuf,
+        /// データファイルの種別です。
+        #[arg(long = "type", short = 't', value_parser = document_types())]
+        document_type: DocumentType,
+        /// 標準出力ではなく、データファイルを上書きします。(標準入力から読み込む場合は指定できません)
+        #[arg(long, short = 'w')]
+        write: bool,
+    },
+    /// 2つの版の小計の一覧を比較し、過去の日付の小計の訂正を表示します。
+    Revisions {
+        /// 以前の版の小計の一覧のパスです。
+        previous: PathBuf,
+        /// 新しい版の小計の一覧のパスです。(`-`を指定した場合は標準入力から読み込みます)
+        current: PathBuf,
+    },
+    /// 小計の一覧から、入力の誤りが疑われる外れ値を検出します。外れ値が存在する場合は、終了コード1で終了します。
+    Anomalies {
+        /// 小計の一覧のパスです。(`-`を指定した場合は標準入力から読み込みます)
+        file: PathBuf,
+        /// 比較に使用する、直前の日数です。
+        #[arg(long, default_value_t = AnomalyOptions::default().window)]
+        window: usize,
+        /// 比較に必要な、直前の最小の日数です。
+        #[arg(long, default_value_t = AnomalyOptions::default().min_history)]
+        min_history: usize,
+        /// 外れ値とする修正zスコアの絶対値の閾値です。
+        #[arg(long, default_value_t = AnomalyOptions::default().threshold)]
+        threshold: f64,
+        /// 曜日による変動を考慮せずに比較します。
+        #[arg(long)]
+        no_seasonality: bool,
+    },
+    /// 小計の一覧から、実効再生産数(Rt)の推定値と信用区間をJSON形式で出力します。
+    Rt {
+        /// 小計の一覧のパスです。(`-`を指定した場合は標準入力から読み込みます)
+        file: PathBuf,
+        /// 発症間隔の平均(日数)です。
+        #[arg(long)]
+        si_mean: f64,
+        /// 発症間隔の標準偏差(日数)です。
+        #[arg(long)]
+        si_sd: f64,
+        /// Rtが一定であると仮定する期間の日数です。
+        #[arg(long, default_value_t = 7)]
+        window: usize,
+    },
+    /// 小計の一覧から、増加率と倍加時間・半減時間の推定値と信頼区間をJSON形式で出力します。
+    Growth {
+        /// 小計の一覧のパスです。(`-`を指定した場合は標準入力から読み込みます)
+        file: PathBuf,
+        /// 回帰に使用する期間の日数です。
+        #[arg(long, default_value_t = GrowthOptions::default().window)]
+        window: usize,
+        /// 信頼区間の確率です。
+        #[arg(long, default_value_t = GrowthOptions::default().confidence)]
+        confidence: f64,
+    },
+    /// 複数の言語の本文を持つお知らせから、単一の言語のお知らせ(`news.json`)を出力します。
+    Localize {
+        /// 複数の言語の本文を持つお知らせのパスです。(`-`を指定した場合は標準入力から読み込みます)
+        file: PathBuf,
+        /// 出力する言語です。(ja, en, zh, ko)
+        #[arg(long, short = 'l')]
+        locale: Locale,
+        /// 翻訳されていないお知らせを、日本語で出力せずに除外します。
+        #[arg(long)]
+        skip_untranslated: bool,
+        /// 出力先のファイルのパスです。(省略した場合は標準出力に書き出します)
+        #[arg(long, short = 'o')]
+        output: Option<PathBuf>,
+    },
+}
+
+/// データが不正であった場合の終了コードです。
+const EXIT_INVALID: u8 = 1;
+/// 入出力に失敗した場合の終了コードです。
+const EXIT_IO_ERROR: u8 = 2;
+
+fn main() -> ExitCode {
+    match Cli::parse().command {
+        Command::Validate {
+            file,
+            document_type,
+            strict,
+        } => {
+            // URLの重複はお知らせのみで検証するため、他の種別での指定は誤りとして扱う
+            if strict && document_type != DocumentType::News {
+                Cli::command()
+                    .error(
+                        ErrorKind::ArgumentConflict,
+                        "`--strict` can only be used with `--type news`",
+                    )
+                    .exit();
+            }
+            load(&file, document_type).and_then(|document| {
+                if let (true, Document::News(news_items)) = (strict, &document) {
+                    let duplicates = news_items.duplicates();
+                    for duplicate in &duplicates {
+                        eprintln!("{}: {}", file.display(), duplicate);
+                    }
+                    if !duplicates.is_empty() {
+                        return Err(EXIT_INVALID);
+                    }
+                }
+                println!("{}: ok ({})", file.display(), document_type);
+                Ok(())
+            })
+        }
+        Command::Fmt {
+            file,
+            document_type,
+            write,
+            legacy_dates,
+        } => check_write(&file, write)
+            .and_then(|()| load(&file, document_type))
+            .and_then(|document| {
+                let json = document
+                    .to_json_pretty_with_dates(dates(legacy_dates))
+                    .map_err(|error| fail(&file, error, EXIT_INVALID))?
+                    + "\n";
+                if write {
+                    fs::write(&file, json).map_err(|error| fail(&file, error, EXIT_IO_ERROR))
+                } else {
+                    print!("{}", json);
+                    Ok(())
+                }
+            }),
+        Command::Inspect {
+            file,
+            document_type,
+        } => load(&file, document_type).map(|document| {
+            let rows = document.inspect();
+            // 項目名の最大幅に合わせて整列する
+            let width = rows.iter().map(|(key, _)| key.chars().count()).max();
+            for (key, value) in &rows {
+                println!("{:<width$}  {}", key, value, width = width.unwrap_or(0));
+            }
+        }),
+        Command::Convert {
+            file,
+            document_type,
+            from,
+            to,
+            output,
+            legacy_dates,
+        } => read(&file)
+            .map_err(|error| fail(&file, error, EXIT_IO_ERROR))
+            .and_then(|input| {
+                convert::read(from, document_type, &input)
+                    .and_then(|document| {
+                        convert::write_with_dates(&document, to, dates(legacy_dates))
+                    })
+                    .map_err(|error| {
+                        fail(
+                            &file,
+                            format!("cannot convert {} from {}: {}", document_type, from, error),
+                            EXIT_INVALID,
+                        )
+                    })
+            })
+            .and_then(|converted| match output {
+                Some(output) => fs::write(&output, converted)
+                    .map_err(|error| fail(&output, error, EXIT_IO_ERROR)),
+                None => io::stdout()
+                    .write_all(&converted)
+                    .map_err(|error| fail(Path::new("-"), error, EXIT_IO_ERROR)),
+            }),
+        Command::Migrate {
+            file,
+            document_type,
+            write,
+        } => check_write(&file, write)
+            .and_then(|()| read_text(&file))
+            .and_then(|json| {
+                Document::from_versioned_json(document_type, &json).map_err(|error| {
+                    fail(
+                        &file,
+                        format!("cannot migrate {}: {}", document_type, error),
+                        EXIT_INVALID,
+                    )
+                })
+            })
+            .and_then(|document| {
+                let json = document
+                    .to_versioned_json_pretty()
+                    .map_err(|error| fail(&file, error, EXIT_INVALID))?
+                    + "\n";
+                if write {
+                    fs::write(&file, json).map_err(|error| fail(&file, error, EXIT_IO_ERROR))
+                } else {
+                    print!("{}", json);
+                    Ok(())
+                }
+            }),
+        Command::Revisions { previous, current } => load(&previous, DocumentType::Summary)
+            .and_then(|previous| Ok((previous, load(&current, DocumentType::Summary)?)))
+            .map(|documents| {
+                if let (Document::Summary(previous), Document::Summary(current)) = documents {
+                    print!("{}", current.revisions_since(&previous));
+                }
+            }),
+        Command::Anomalies {
+            file,
+            window,
+            min_history,
+            threshold,
+            no_seasonality,
+        } => load(&file, DocumentType::Summary).and_then(|document| {
+            let Document::Summary(summary) = document else {
+                return Ok(());
+            };
+            let options = AnomalyOptions {
+                window,
+                min_history,
+                threshold,
+                seasonality: !no_seasonality,
+            };
+            let report = summary
+                .anomalies(&options)
+                .map_err(|error| fail(&file, error, EXIT_INVALID))?;
+            print!("{}", report);
+            if report.is_empty() {
+                Ok(())
+            } else {
+                Err(EXIT_INVALID)
+            }
+        }),
+        Command::Rt {
+            file,
+            si_mean,
+            si_sd,
+            window,
+        } => load(&file, DocumentType::Summary).and_then(|document| {
+            let Document::Summary(summary) = document else {
+                return Ok(());
+            };
+            let options = RtOptions {
+                window,
+                ..RtOptions::new(SerialInterval::Gamma {
+                    mean: si_mean,
+                    sd: si_sd,
+                })
+            };
+            let series = summary
+                .reproduction_numbers(&options)
+                .map_err(|error| fail(&file, error, EXIT_INVALID))?;
+            let json = serde_json::to_string_pretty(&series)
+                .map_err(|error| fail(&file, error, EXIT_INVALID))?;
+            println!("{}", json);
+            Ok(())
+        }),
+        Command::Growth {
+            file,
+            window,
+            confidence,
+        } => load(&file, DocumentType::Summary).and_then(|document| {
+            let Document::Summary(summary) = document else {
+                return Ok(());
+            };
+            let options = GrowthOptions { window, confidence };
+            let series = summary
+                .growth_rates(&options)
+                .map_err(|error| fail(&file, error, EXIT_INVALID))?;
+            let json = serde_json::to_string_pretty(&series)
+                .map_err(|error| fail(&file, error, EXIT_INVALID))?;
+            println!("{}", json);
+            Ok(())
+        }),
+        Command::Localize {
+            file,
+            locale,
+            skip_untranslated,
+            output,
+        } => read_text(&file)
+            .and_then(|json| {
+                // 版数(`version`)を含むお知らせの一覧は、現在の版に変換した上で読み込む
+                upgrade_json(DocumentType::News, &json)
+                    .and_then(|json| {
+                        serde_json::from_str::<LocalizedNewsItems>(&json)
+                            .map_err(MigrationError::Json)
+                    })
+                    .map_err(|error| fail(&file, format!("invalid news {}", error), EXIT_INVALID))
+            })
+            .and_then(|news_items| {
+                let fallback = if skip_untranslated {
+                    Fallback::Skip
+                } else {
+                    Fallback::Japanese
+                };
+                let json = serde_json::to_string_pretty(&news_items.select(locale, fallback))
+                    .map_err(|error| fail(&file, error, EXIT_INVALID))?
+                    + "\n";
+                match output {
+                    Some(output) => fs::write(&output, json)
+                        .map_err(|error| fail(&output, error, EXIT_IO_ERROR)),
+                    None => {
+                        print!("{}", json);
+                        Ok(())
+                    }
+                }
+            }),
+    }
+    .map_or_else(ExitCode::from, |_| ExitCode::SUCCESS)
+}
+
+/// `--type`に指定できる種別を、`DocumentType::ALL`から求めます。
+fn document_types() -> impl TypedValueParser<Value = DocumentType> {
+    PossibleValuesParser::new(DocumentType::ALL.iter().map(DocumentType::as_str))
+        .try_map(|name| name.parse::<DocumentType>())
+}
+
+/// `--write`を指定できるか確認します。
+///
+/// 標準入力から読み込む場合は上書きするファイルが存在しないため、`-`という名前のファイルを作成せずに誤りとして扱います。
+fn check_write(file: &Path, write: bool) -> Result<(), u8> {
+    if write && file == Path::new("-") {
+        Cli::command()
+            .error(
+                ErrorKind::ArgumentConflict,
+                "`--write` cannot be used when reading from stdin (`-`)",
+            )
+            .exit();
+    }
+    Ok(())
+}
+
+/// データファイルを読み込み、指定された種別の構造体としてデシリアライズします。
+///
+/// 版数(`version`)を含むデータファイルは、現在の版に変換した上でデシリアライズします。
+fn load(file: &Path, document_type: DocumentType) -> Result<Document, u8> {
+    let json = read_text(file)?;
+    Document::from_versioned_json(document_type, &json).map_err(|error| {
+        fail(
+            file,
+            format!("invalid {} {}", document_type, error),
+            EXIT_INVALID,
+        )
+    })
+}
+
+/// データファイルを、UTF-8の文字列として読み込みます。
+fn read_text(file: &Path) -> Result<String, u8> {
+    read(file)
+        .and_then(|input| {
+            String::from_utf8(input)
+                .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+        })
+        .map_err(|error| fail(file, error, EXIT_IO_ERROR))
+}
+
+/// 小計の日付の表現形式を、コマンドライン引数から決定します。
+fn dates(legacy_dates: bool) -> DateRepresentation {
+    if legacy_dates {
+        DateRepresentation::Legacy
+    } else {
+        DateRepresentation::CalendarDay
+    }
+}
+
+/// データファイルを読み込みます。
+fn read(file: &Path) -> io::Result<Vec<u8>> {
+    if file == Path::new("-") {
+        let mut input = Vec::new();
+        io::stdin().read_to_end(&mut input)?;
+        Ok(input)
+    } else {
+        fs::read(file)
+    }
+}
+
+/// エラーを標準エラー出力に書き出し、終了コードを返却します。
+fn fail(file: &Path, error: impl std::fmt::Display, code: u8) -> u8 {
+    eprintln!("{}: {}", file.display(), error);
+    code
+}
@@ -0,0 +1,26 @@
+//! 構造体に対応するTypeScriptの型定義を出力するプログラムです。
+//!
+//! 引数にファイルパスを指定した場合はそのファイルへ、指定しない場合は標準出力へ書き出します。
+
+use data_formats::codegen::typescript::declarations;
+use std::{env, fs, process::ExitCode};
+
+fn main() -> ExitCode {
+    let declarations = declarations();
+
+    match env::args().nth(1) {
+        // ファイルに書き出す
+        Some(path) => match fs::write(&path, declarations) {
+            Ok(()) => ExitCode::SUCCESS,
+            Err(error) => {
+                eprintln!("{}: {}", path, error);
+                ExitCode::FAILURE
+            }
+        },
+        // 標準出力に書き出す
+        None => {
+            print!("{}", declarations);
+            ExitCode::SUCCESS
+        }
+    }
+}
@@ -0,0 +1,559 @@
+//! 構造体のシリアライズ結果と一致する、TypeScriptの型定義(`.d.ts`)を生成します。
+//!
+//! フロントエンドとRustの双方で同じデータ形式を共有するため、型定義は本モジュールの出力を正とします。
+
+use crate::{
+    population::age_band::AgeBand,
+    structs::{
+        age_distribution::{
+            self, AgeDistribution, AgeDistributionContent, AgeGroup, DailyAgeDistribution,
+        },
+        last_update::{self, LastUpdate},
+        news::{
+            self, link,
+            localized::{Locale, LocalizedNewsItem, LocalizedNewsItems, LocalizedText},
+            NewsItem, NewsItems,
+        },
+        status::{self, Attributes, Status},
+        summary::{self, Summary, SummaryContent},
+        vaccination::{self, CoverageGroup, Vaccination, VaccinationContent, VaccinationCoverage},
+    },
+    utils::formats::{DATETIME_FORMAT, DATE_FORMAT},
+};
+use chrono::Local;
+
+/// 生成されるファイルの先頭に付与されるヘッダーです。
+const HEADER: &str =
+    "// このファイルは data-formats クレートにより自動生成されています。直接編集しないでください。\n";
+
+/// 文字列として表現されるプロパティの書式です。
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum StringFormat {
+    /// chronoクレートの書式指定子で表現される書式です。
+    Chrono(&'static str),
+    /// `http`または`https`の絶対URLです。
+    Url,
+}
+
+impl StringFormat {
+    /// 書式を、人間が読みやすい形式で返却します。
+    pub fn describe(&self) -> String {
+        match self {
+            StringFormat::Chrono(format) => format
+                .replace("%Y", "YYYY")
+                .replace("%m", "MM")
+                .replace("%d", "DD")
+                .replace("%R", "HH:mm"),
+            StringFormat::Url => "絶対URL".to_string(),
+        }
+    }
+}
+
+/// TypeScriptのインターフェースが持つプロパティを表現します。
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Property {
+    /// シリアライズ時のフィールド名です。
+    pub name: &'static str,
+    /// TypeScriptでの型です。
+    pub ty: &'static str,
+    /// 省略可能なプロパティであるかを示します。
+    pub optional: bool,
+    /// プロパティの説明です。
+    pub description: &'static str,
+    /// 文字列で表現される場合の書式です。
+    pub format: Option<StringFormat>,
+}
+
+/// TypeScriptの型定義を生成できる型が実装するトレイトです。
+pub trait TypeScriptDefinition {
+    /// TypeScriptでの型名です。
+    const NAME: &'static str;
+
+    /// インターフェースとして宣言される場合のプロパティを返却します。
+    fn properties() -> Vec<Property> {
+        Vec::new()
+    }
+
+    /// 型定義を出力します。
+    fn declaration() -> String;
+}
+
+impl TypeScriptDefinition for Attributes {
+    const NAME: &'static str = "Attributes";
+
+    fn declaration() -> String {
+        // serdeによるシリアライズ結果を、そのまま文字列リテラル型として扱う
+        let literals = Attributes::ALL
+            .iter()
+            .map(|attr| format!("  | {}", serde_json::to_string(attr).unwrap()))
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        format!(
+            "/**\n * COVID-19に関連する情報の属性です。\n */\nexport type {} =\n{};\n",
+            Self::NAME,
+            literals
+        )
+    }
+}
+
+impl TypeScriptDefinition for Status {
+    const NAME: &'static str = "Status";
+
+    fn properties() -> Vec<Property> {
+        properties(
+            status::STATUS_FIELDS,
+            [
+                Spec::new(Attributes::NAME, "情報の属性です。"),
+                Spec::new("number", "人数や件数です。"),
+                Spec::new("Status[]", "子属性の情報です。").optional(),
+                Spec::new("string", "最終更新日時です。")
+                    .optional()
+                    .format(StringFormat::Chrono(DATETIME_FORMAT)),
+            ],
+        )
+    }
+
+    fn declaration() -> String {
+        interface(
+            Self::NAME,
+            "COVID-19に関する情報です。",
+            &Self::properties(),
+        )
+    }
+}
+
+impl TypeScriptDefinition for SummaryContent {
+    const NAME: &'static str = "SummaryContent";
+
+    fn properties() -> Vec<Property> {
+        properties(
+            summary::SUMMARYCONTENT_FIELDS,
+            [
+                Spec::new("string", "小計の対象日(日本標準時)です。")
+                    .format(StringFormat::Chrono(DATE_FORMAT)),
+                Spec::new("number", "小計です。"),
+            ],
+        )
+    }
+
+    fn declaration() -> String {
+        interface(Self::NAME, "日毎の小計です。", &Self::properties())
+    }
+}
+
+impl TypeScriptDefinition for Summary {
+    const NAME: &'static str = "Summary";
+
+    fn properties() -> Vec<Property> {
+        properties(
+            summary::SUMMARY_FIELDS,
+            [
+                Spec::new("SummaryContent[]", "日毎の小計の一覧です。"),
+                Spec::new("string", "最終更新日時です。")
+                    .format(StringFormat::Chrono(DATETIME_FORMAT)),
+            ],
+        )
+    }
+
+    fn declaration() -> String {
+        interface(Self::NAME, "小計の一覧です。", &Self::properties())
+    }
+}
+
+impl TypeScriptDefinition for NewsItem {
+    const NAME: &'static str = "NewsItem";
+
+    fn properties() -> Vec<Property> {
+        properties(
+            news::NEWS_ITEM_FIELDS,
+            [
+                Spec::new("string", "お知らせの日付です。")
+                    .format(StringFormat::Chrono(DATE_FORMAT)),
+                Spec::new("string", "お知らせの本文です。"),
+                Spec::new("string", "お知らせのリンク先です。").format(StringFormat::Url),
+            ],
+        )
+    }
+
+    fn declaration() -> String {
+        interface(Self::NAME, "最新のお知らせです。", &Self::properties())
+    }
+}
+
+impl TypeScriptDefinition for NewsItems {
+    const NAME: &'static str = "NewsItems";
+
+    fn properties() -> Vec<Property> {
+        properties(
+            link::NEWS_ITEMS_FIELDS,
+            [Spec::new("NewsItem[]", "お知らせの一覧です。")],
+        )
+    }
+
+    fn declaration() -> String {
+        interface(
+            Self::NAME,
+            "最新のお知らせの一覧です。",
+            &Self::properties(),
+        )
+    }
+}
+
+impl TypeScriptDefinition for LocalizedText {
+    const NAME: &'static str = "LocalizedText";
+
+    fn properties() -> Vec<Property> {
+        Locale::ALL
+            .iter()
+            .map(|locale| Property {
+                name: locale.as_str(),
+                ty: "string",
+                // 日本語の本文のみ必須とする
+                optional: *locale != Locale::Ja,
+                description: match locale {
+                    Locale::Ja => "日本語の本文です。",
+                    Locale::En => "英語の本文です。",
+                    Locale::Zh => "中国語の本文です。",
+                    Locale::Ko => "韓国語の本文です。",
+                },
+                format: None,
+            })
+            .collect()
+    }
+
+    fn declaration() -> String {
+        interface(
+            Self::NAME,
+            "言語ごとのお知らせの本文です。",
+            &Self::properties(),
+        )
+    }
+}
+
+impl TypeScriptDefinition for LocalizedNewsItem {
+    const NAME: &'static str = "LocalizedNewsItem";
+
+    fn properties() -> Vec<Property> {
+        let mut properties = NewsItem::properties();
+        properties[1].ty = LocalizedText::NAME;
+        properties[1].description = "言語ごとのお知らせの本文です。";
+        properties
+    }
+
+    fn declaration() -> String {
+        interface(
+            Self::NAME,
+            "複数の言語の本文を持つ、最新のお知らせです。",
+            &Self::properties(),
+        )
+    }
+}
+
+impl TypeScriptDefinition for LocalizedNewsItems {
+    const NAME: &'static str = "LocalizedNewsItems";
+
+    fn properties() -> Vec<Property> {
+        properties(
+            link::NEWS_ITEMS_FIELDS,
+            [Spec::new("LocalizedNewsItem[]", "お知らせの一覧です。")],
+        )
+    }
+
+    fn declaration() -> String {
+        interface(
+            Self::NAME,
+            "複数の言語の本文を持つ、最新のお知らせの一覧です。",
+            &Self::properties(),
+        )
+    }
+}
+
+impl TypeScriptDefinition for LastUpdate<Local> {
+    const NAME: &'static str = "LastUpdate";
+
+    fn properties() -> Vec<Property> {
+        properties(
+            last_update::FIELDS,
+            [Spec::new("string", "最終更新日時です。")
+                .format(StringFormat::Chrono(DATETIME_FORMAT))],
+        )
+    }
+
+    fn declaration() -> String {
+        interface(Self::NAME, "データの最終更新日です。", &Self::properties())
+    }
+}
+
+impl TypeScriptDefinition for AgeGroup {
+    const NAME: &'static str = "AgeGroup";
+
+    fn declaration() -> String {
+        let literals = AgeGroup::ALL
+            .iter()
+            .map(|group| format!("  | {}", serde_json::to_string(group).unwrap()))
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        format!(
+            "/**\n * 陽性者の年代の区分です。\n */\nexport type {} =\n{};\n",
+            Self::NAME,
+            literals
+        )
+    }
+}
+
+impl TypeScriptDefinition for AgeDistributionContent {
+    const NAME: &'static str = "AgeDistributionContent";
+
+    fn properties() -> Vec<Property> {
+        properties(
+            age_distribution::AGE_DISTRIBUTION_CONTENT_FIELDS,
+            [
+                Spec::new(AgeGroup::NAME, "年代の区分です。"),
+                Spec::new("number", "陽性者数です。"),
+            ],
+        )
+    }
+
+    fn declaration() -> String {
+        interface(Self::NAME, "年代ごとの陽性者数です。", &Self::properties())
+    }
+}
+
+impl TypeScriptDefinition for DailyAgeDistribution {
+    const NAME: &'static str = "DailyAgeDistribution";
+
+    fn properties() -> Vec<Property> {
+        properties(
+            age_distribution::DAILY_AGE_DISTRIBUTION_FIELDS,
+            [
+                Spec::new("string", "陽性が判明した日付(日本標準時)です。")
+                    .format(StringFormat::Chrono(DATE_FORMAT)),
+                Spec::new("AgeDistributionContent[]", "年代ごとの陽性者数です。"),
+            ],
+        )
+    }
+
+    fn declaration() -> String {
+        interface(
+            Self::NAME,
+            "ある日付の、年代ごとの陽性者数です。",
+            &Self::properties(),
+        )
+    }
+}
+
+impl TypeScriptDefinition for AgeDistribution {
+    const NAME: &'static str = "AgeDistribution";
+
+    fn properties() -> Vec<Property> {
+        properties(
+            age_distribution::AGE_DISTRIBUTION_FIELDS,
+            [
+                Spec::new("AgeDistributionContent[]", "年代ごとの陽性者数の累計です。"),
+                Spec::new("DailyAgeDistribution[]", "日毎の内訳です。").optional(),
+                Spec::new("string", "最終更新日時です。")
+                    .format(StringFormat::Chrono(DATETIME_FORMAT)),
+            ],
+        )
+    }
+
+    fn declaration() -> String {
+        interface(Self::NAME, "年代別陽性者数です。", &Self::properties())
+    }
+}
+
+impl TypeScriptDefinition for CoverageGroup {
+    const NAME: &'static str = "CoverageGroup";
+
+    fn declaration() -> String {
+        let literals = std::iter::once(CoverageGroup::All)
+            .chain(AgeBand::ALL.into_iter().map(CoverageGroup::Band))
+            .map(|group| format!("  | {}", serde_json::to_string(&group).unwrap()))
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        format!(
+            "/**\n * 接種率を集計する年代の区分です。\n */\nexport type {} =\n{};\n",
+            Self::NAME,
+            literals
+        )
+    }
+}
+
+impl TypeScriptDefinition for VaccinationContent {
+    const NAME: &'static str = "VaccinationContent";
+
+    fn properties() -> Vec<Property> {
+        properties(
+            vaccination::VACCINATION_CONTENT_FIELDS,
+            [
+                Spec::new("string", "接種日(日本標準時)です。")
+                    .format(StringFormat::Chrono(DATE_FORMAT)),
+                Spec::new("number", "1回目の接種回数です。"),
+                Spec::new("number", "2回目の接種回数です。"),
+                Spec::new("number", "3回目の接種回数です。"),
+                Spec::new("number", "4回目以降の追加接種の接種回数です。"),
+            ],
+        )
+    }
+
+    fn declaration() -> String {
+        interface(
+            Self::NAME,
+            "ある日付の、回次ごとの接種回数です。",
+            &Self::properties(),
+        )
+    }
+}
+
+impl TypeScriptDefinition for VaccinationCoverage {
+    const NAME: &'static str = "VaccinationCoverage";
+
+    fn properties() -> Vec<Property> {
+        properties(
+            vaccination::VACCINATION_COVERAGE_FIELDS,
+            [
+                Spec::new(CoverageGroup::NAME, "年代の区分です。"),
+                Spec::new("number", "1回目の接種率(%)です。"),
+                Spec::new("number", "2回目の接種率(%)です。"),
+                Spec::new("number", "3回目の接種率(%)です。"),
+                Spec::new("number", "4回目以降の追加接種の接種率(%)です。"),
+            ],
+        )
+    }
+
+    fn declaration() -> String {
+        interface(
+            Self::NAME,
+            "ある年代の、回次ごとの累計の接種率です。",
+            &Self::properties(),
+        )
+    }
+}
+
+impl TypeScriptDefinition for Vaccination {
+    const NAME: &'static str = "Vaccination";
+
+    fn properties() -> Vec<Property> {
+        properties(
+            vaccination::VACCINATION_FIELDS,
+            [
+                Spec::new("VaccinationContent[]", "日毎の接種回数の一覧です。"),
+                Spec::new("VaccinationCoverage[]", "年代ごとの累計の接種率です。"),
+                Spec::new("string", "最終更新日時です。")
+                    .format(StringFormat::Chrono(DATETIME_FORMAT)),
+            ],
+        )
+    }
+
+    fn declaration() -> String {
+        interface(Self::NAME, "ワクチン接種の実績です。", &Self::properties())
+    }
+}
+
+/// フィールド名を除いた、プロパティの定義です。
+struct Spec {
+    ty: &'static str,
+    optional: bool,
+    description: &'static str,
+    format: Option<StringFormat>,
+}
+
+impl Spec {
+    /// 省略できず、書式を持たないプロパティの定義を作成します。
+    fn new(ty: &'static str, description: &'static str) -> Spec {
+        Spec {
+            ty,
+            optional: false,
+            description,
+            format: None,
+        }
+    }
+
+    /// 省略可能なプロパティとします。
+    fn optional(mut self) -> Spec {
+        self.optional = true;
+        self
+    }
+
+    /// 文字列で表現される場合の書式を設定します。
+    fn format(mut self, format: StringFormat) -> Spec {
+        self.format = Some(format);
+        self
+    }
+}
+
+/// デシリアライズに用いるフィールド名の一覧に、同じ順序でプロパティの定義を対応付けます。
+///
+/// 両者の長さが異なる場合はコンパイルに失敗するため、構造体にフィールドを追加した場合は型定義も更新する必要があります。
+fn properties<const N: usize>(fields: &[&'static str; N], specs: [Spec; N]) -> Vec<Property> {
+    fields
+        .iter()
+        .zip(specs)
+        .map(|(name, spec)| Property {
+            name,
+            ty: spec.ty,
+            optional: spec.optional,
+            description: spec.description,
+            format: spec.format,
+        })
+        .collect()
+}
+
+/// インターフェースの型定義を出力します。
+fn interface(name: &str, description: &str, properties: &[Property]) -> String {
+    let mut declaration = format!(
+        "/**\n * {}\n */\nexport interface {} {{\n",
+        description, name
+    );
+
+    for property in properties {
+        // 説明と書式をドキュメンテーションコメントとして出力する
+        match &property.format {
+            Some(format) => declaration.push_str(&format!(
+                "  /** {} (形式: {}) */\n",
+                property.description,
+                format.describe()
+            )),
+            None => declaration.push_str(&format!("  /** {} */\n", property.description)),
+        }
+        // プロパティを出力する
+        declaration.push_str(&format!(
+            "  {}{}: {};\n",
+            property.name,
+            if property.optional { "?" } else { "" },
+            property.ty
+        ));
+    }
+
+    declaration.push_str("}\n");
+    declaration
+}
+
+/// クレートが公開する全ての構造体について、TypeScriptの型定義を出力します。
+pub fn declarations() -> String {
+    [
+        HEADER.to_string(),
+        Attributes::declaration(),
+        Status::declaration(),
+        SummaryContent::declaration(),
+        Summary::declaration(),
+        NewsItem::declaration(),
+        NewsItems::declaration(),
+        LocalizedText::declaration(),
+        LocalizedNewsItem::declaration(),
+        LocalizedNewsItems::declaration(),
+        LastUpdate::<Local>::declaration(),
+        AgeGroup::declaration(),
+        AgeDistributionContent::declaration(),
+        DailyAgeDistribution::declaration(),
+        AgeDistribution::declaration(),
+        CoverageGroup::declaration(),
+        VaccinationContent::declaration(),
+        VaccinationCoverage::declaration(),
+        Vaccination::declaration(),
+    ]
+    .join("\n")
+}
@@ -0,0 +1,557 @@
+//! データファイルを、JSON・CSV・YAML・MessagePackの各形式の間で変換する機能を提供します。
+//!
+//! 変換は必ず構造体を経由するため、変換元のデータは変換の過程で検証されます。
+//! YAML形式は`yaml`フィーチャー、MessagePack形式は`msgpack`フィーチャーを有効にした場合のみ使用できます。
+
+use crate::{
+    document::{Document, DocumentType},
+    migration::MigrationError,
+    structs::{
+        last_update::LastUpdate,
+        news::{NewsItem, NewsItems},
+        status::{Attributes, Status},
+        summary::{DateRepresentation, Summary, SummaryContent},
+    },
+    utils::formats::codec::{DATE, DATETIME},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    error::Error as StdError,
+    fmt::{Display, Error, Formatter},
+    str::FromStr,
+};
+
+/// 変換に対応しているデータ形式を列挙しています。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Format {
+    /// JSON形式です。
+    Json,
+    /// CSV形式です。
+    Csv,
+    /// YAML形式です。(`yaml`フィーチャーが必要です)
+    Yaml,
+    /// MessagePack形式です。(`msgpack`フィーチャーが必要です)
+    MessagePack,
+}
+
+impl Format {
+    /// 全てのデータ形式を列挙した配列です。
+    pub const ALL: &'static [Format] =
+        &[Format::Json, Format::Csv, Format::Yaml, Format::MessagePack];
+
+    /// データ形式の名前を返却します。
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Format::Json => "json",
+            Format::Csv => "csv",
+            Format::Yaml => "yaml",
+            Format::MessagePack => "msgpack",
+        }
+    }
+
+    /// 有効なフィーチャーにより、このデータ形式が使用できるかを返却します。
+    pub fn is_supported(&self) -> bool {
+        match self {
+            Format::Json | Format::Csv => true,
+            Format::Yaml => cfg!(feature = "yaml"),
+            Format::MessagePack => cfg!(feature = "msgpack"),
+        }
+    }
+}
+
+impl Display for Format {
+    fn fmt(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        write!(formatter, "{}", self.as_str())
+    }
+}
+
+/// 未知のデータ形式が指定された際のエラーです。
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct UnknownFormat(pub String);
+
+impl Display for UnknownFormat {
+    fn fmt(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        write!(
+            formatter,
+            "unknown format `{}` (expected one of json, csv, yaml, msgpack)",
+            self.0
+        )
+    }
+}
+
+impl StdError for UnknownFormat {}
+
+impl FromStr for Format {
+    type Err = UnknownFormat;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            "yaml" | "yml" => Ok(Format::Yaml),
+            "msgpack" | "messagepack" => Ok(Format::MessagePack),
+            _ => Err(UnknownFormat(value.to_string())),
+        }
+    }
+}
+
+/// データ形式の変換に失敗した際のエラーです。
+#[derive(Debug)]
+pub enum ConvertError {
+    /// 有効になっていないフィーチャーのデータ形式が指定されました。
+    Unsupported(Format),
+    /// データ形式が、指定された種別の構造体を表現できません。
+    UnsupportedDocument {
+        /// 指定されたデータ形式です。
+        format: Format,
+        /// 指定された種別です。
+        document_type: DocumentType,
+    },
+    /// 入力がUTF-8の文字列ではありません。
+    Utf8(std::str::Utf8Error),
+    /// JSON形式のデータが不正であるか、以前の版のデータファイルを変換できません。
+    Json(MigrationError),
+    /// JSON形式への変換に失敗しました。
+    JsonEncode(serde_json::Error),
+    /// CSV形式の読み書きに失敗しました。
+    Csv(csv::Error),
+    /// CSV形式のレコードの内容が不正です。
+    InvalidRecord {
+        /// レコードの行番号です。
+        line: u64,
+        /// エラーの内容です。
+        message: String,
+    },
+    /// YAML形式の読み書きに失敗しました。
+    #[cfg(feature = "yaml")]
+    Yaml(serde_yaml::Error),
+    /// MessagePack形式の読み込みに失敗しました。
+    #[cfg(feature = "msgpack")]
+    MessagePackDecode(rmp_serde::decode::Error),
+    /// MessagePack形式への変換に失敗しました。
+    #[cfg(feature = "msgpack")]
+    MessagePackEncode(rmp_serde::encode::Error),
+}
+
+impl Display for ConvertError {
+    fn fmt(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        match self {
+            ConvertError::Unsupported(format) => write!(
+                formatter,
+                "format `{}` is not enabled (rebuild with `--features {}`)",
+                format, format
+            ),
+            ConvertError::UnsupportedDocument {
+                format,
+                document_type,
+            } => write!(
+                formatter,
+                "format `{}` does not support `{}`",
+                format, document_type
+            ),
+            ConvertError::Utf8(error) => write!(formatter, "input is not UTF-8: {}", error),
+            ConvertError::Json(error) => write!(formatter, "{}", error),
+            ConvertError::JsonEncode(error) => write!(formatter, "{}", error),
+            ConvertError::Csv(error) => write!(formatter, "{}", error),
+            ConvertError::InvalidRecord { line, message } => {
+                write!(formatter, "at line {}: {}", line, message)
+            }
+            #[cfg(feature = "yaml")]
+            ConvertError::Yaml(error) => write!(formatter, "{}", error),
+            #[cfg(feature = "msgpack")]
+            ConvertError::MessagePackDecode(error) => write!(formatter, "{}", error),
+            #[cfg(feature = "msgpack")]
+            ConvertError::MessagePackEncode(error) => write!(formatter, "{}", error),
+        }
+    }
+}
+
+impl StdError for ConvertError {}
+
+impl From<csv::Error> for ConvertError {
+    fn from(error: csv::Error) -> Self {
+        ConvertError::Csv(error)
+    }
+}
+
+/// 指定されたデータ形式の入力を、指定された種別の構造体として読み込みます。
+pub fn read(
+    format: Format,
+    document_type: DocumentType,
+    input: &[u8],
+) -> Result<Document, ConvertError> {
+    match format {
+        Format::Json => {
+            Document::from_versioned_json(document_type, utf8(input)?).map_err(ConvertError::Json)
+        }
+        Format::Csv => read_csv(document_type, input),
+        #[cfg(feature = "yaml")]
+        Format::Yaml => {
+            let input = utf8(input)?;
+            match document_type {
+                DocumentType::Summary => serde_yaml::from_str(input).map(Document::Summary),
+                DocumentType::Status => serde_yaml::from_str(input).map(Document::Status),
+                DocumentType::News => serde_yaml::from_str(input).map(Document::News),
+                DocumentType::LastUpdate => serde_yaml::from_str(input).map(Document::LastUpdate),
+                DocumentType::AgeDistribution => {
+                    serde_yaml::from_str(input).map(Document::AgeDistribution)
+                }
+                DocumentType::Vaccination => serde_yaml::from_str(input).map(Document::Vaccination),
+            }
+            .map_err(ConvertError::Yaml)
+        }
+        #[cfg(feature = "msgpack")]
+        Format::MessagePack => match document_type {
+            DocumentType::Summary => rmp_serde::from_slice(input).map(Document::Summary),
+            DocumentType::Status => rmp_serde::from_slice(input).map(Document::Status),
+            DocumentType::News => rmp_serde::from_slice(input).map(Document::News),
+            DocumentType::LastUpdate => rmp_serde::from_slice(input).map(Document::LastUpdate),
+            DocumentType::AgeDistribution => {
+                rmp_serde::from_slice(input).map(Document::AgeDistribution)
+            }
+            DocumentType::Vaccination => rmp_serde::from_slice(input).map(Document::Vaccination),
+        }
+        .map_err(ConvertError::MessagePackDecode),
+        #[allow(unreachable_patterns)]
+        _ => Err(ConvertError::Unsupported(format)),
+    }
+}
+
+/// 構造体を、指定されたデータ形式で書き出します。
+pub fn write(document: &Document, format: Format) -> Result<Vec<u8>, ConvertError> {
+    write_with_dates(document, format, DateRepresentation::default())
+}
+
+/// 小計の日付の表現形式を指定して、構造体を指定されたデータ形式で書き出します。
+pub fn write_with_dates(
+    document: &Document,
+    format: Format,
+    dates: DateRepresentation,
+) -> Result<Vec<u8>, ConvertError> {
+    match format {
+        Format::Json => document
+            .to_json_pretty_with_dates(dates)
+            .map(|json| (json + "\n").into_bytes())
+            .map_err(ConvertError::JsonEncode),
+        Format::Csv => write_csv(document, dates),
+        #[cfg(feature = "yaml")]
+        Format::Yaml => match document {
+            Document::Summary(v) => serde_yaml::to_string(&v.with_dates(dates)),
+            Document::Status(v) => serde_yaml::to_string(v),
+            Document::News(v) => serde_yaml::to_string(v),
+            Document::LastUpdate(v) => serde_yaml::to_string(v),
+            Document::AgeDistribution(v) => serde_yaml::to_string(v),
+            Document::Vaccination(v) => serde_yaml::to_string(v),
+        }
+        .map(String::into_bytes)
+        .map_err(ConvertError::Yaml),
+        // 構造体のフィールド名を保持するため、連想配列として書き出す
+        #[cfg(feature = "msgpack")]
+        Format::MessagePack => match document {
+            Document::Summary(v) => rmp_serde::to_vec_named(&v.with_dates(dates)),
+            Document::Status(v) => rmp_serde::to_vec_named(v),
+            Document::News(v) => rmp_serde::to_vec_named(v),
+            Document::LastUpdate(v) => rmp_serde::to_vec_named(v),
+            Document::AgeDistribution(v) => rmp_serde::to_vec_named(v),
+            Document::Vaccination(v) => rmp_serde::to_vec_named(v),
+        }
+        .map_err(ConvertError::MessagePackEncode),
+        #[allow(unreachable_patterns)]
+        _ => Err(ConvertError::Unsupported(format)),
+    }
+}
+
+/// 指定されたデータ形式の入力を、別のデータ形式に変換します。
+pub fn convert(
+    document_type: DocumentType,
+    from: Format,
+    to: Format,
+    input: &[u8],
+) -> Result<Vec<u8>, ConvertError> {
+    write(&read(from, document_type, input)?, to)
+}
+
+/// 入力をUTF-8の文字列として解釈します。
+fn utf8(input: &[u8]) -> Result<&str, ConvertError> {
+    std::str::from_utf8(input).map_err(ConvertError::Utf8)
+}
+
+/// Summary構造体をCSV形式で表現する際のレコードです。
+///
+/// 最終更新日時は、全てのレコードに同じ値を記録します。
+/// 小計が存在しない場合は、`date`と`sum`が空の、最終更新日時のみのレコードを1件記録します。
+#[derive(Deserialize, Serialize)]
+struct SummaryRecord {
+    date: Option<String>,
+    sum: Option<u32>,
+    last_update: String,
+}
+
+/// Status構造体をCSV形式で表現する際のレコードです。
+///
+/// 階層構造は、行きがけ順に並べたレコードと、その深さで表現します。
+/// 子属性を持たないStatusと、空の子属性を持つStatusは、`has_children`で区別します。
+#[derive(Deserialize, Serialize)]
+struct StatusRecord {
+    depth: usize,
+    attr: String,
+    value: u32,
+    has_children: bool,
+    last_update: Option<String>,
+}
+
+/// NewsItem構造体をCSV形式で表現する際のレコードです。
+#[derive(Deserialize, Serialize)]
+struct NewsItemRecord {
+    date: String,
+    text: String,
+    url: String,
+}
+
+/// LastUpdate構造体をCSV形式で表現する際のレコードです。
+#[derive(Deserialize, Serialize)]
+struct LastUpdateRecord {
+    last_update: String,
+}
+
+/// CSV形式の入力を、レコードの一覧として読み込みます。
+///
+/// 各レコードは、行番号と組にして返却します。
+fn records<T>(input: &[u8]) -> Result<Vec<(u64, T)>, ConvertError>
+where
+    T: for<'de> Deserialize<'de>,
+{
+    let mut reader = csv::Reader::from_reader(input);
+    let headers = reader.headers()?.clone();
+
+    reader
+        .records()
+        .map(|record| {
+            let record = record?;
+            let line = record.position().map_or(0, csv::Position::line);
+            Ok((line, record.deserialize(Some(&headers))?))
+        })
+        .collect()
+}
+
+/// レコードの内容に関するエラーを生成します。
+fn invalid(line: u64, message: impl Display) -> ConvertError {
+    ConvertError::InvalidRecord {
+        line,
+        message: message.to_string(),
+    }
+}
+
+/// CSV形式で表現できない種別が指定された際のエラーを生成します。
+///
+/// 年代別陽性者数とワクチン接種の実績は、形式の異なる2種類の一覧を持つため、1種類のレコードの並びとして表現しません。
+fn unsupported_csv(document_type: DocumentType) -> ConvertError {
+    ConvertError::UnsupportedDocument {
+        format: Format::Csv,
+        document_type,
+    }
+}
+
+/// CSV形式の入力を、指定された種別の構造体として読み込みます。
+fn read_csv(document_type: DocumentType, input: &[u8]) -> Result<Document, ConvertError> {
+    match document_type {
+        DocumentType::Summary => {
+            let records = records::<SummaryRecord>(input)?;
+            let mut data = Vec::new();
+            let mut last_update = None;
+            for (line, record) in &records {
+                let line = *line;
+                // 最終更新日時は、全てのレコードで一致している必要がある
+                let datetime = DATETIME
+                    .parse(&record.last_update)
+                    .map_err(|e| invalid(line, e))?;
+                match last_update {
+                    Some(previous) if previous != datetime => {
+                        return Err(invalid(
+                            line,
+                            format!(
+                                "last_update `{}` differs from `{}` in the previous records",
+                                record.last_update,
+                                DATETIME.format(&previous)
+                            ),
+                        ));
+                    }
+                    _ => last_update = Some(datetime),
+                }
+                // 最終更新日時のみのレコードは、小計が存在しない場合にのみ許容する
+                match (&record.date, record.sum) {
+                    (None, None) if records.len() == 1 => {}
+                    (Some(date), Some(sum)) => data.push(SummaryContent {
+                        date: DATE.parse(date).map_err(|e| invalid(line, e))?,
+                        sum,
+                    }),
+                    _ => return Err(invalid(line, "both date and sum are required")),
+                }
+            }
+            Ok(Document::Summary(Summary {
+                data,
+                last_update: last_update.ok_or_else(|| invalid(1, "no records found"))?,
+            }))
+        }
+        DocumentType::Status => {
+            // 祖先のStatusを、深さの順に積み上げる
+            let mut ancestors: Vec<Status> = Vec::new();
+            for (line, record) in records::<StatusRecord>(input)? {
+                // 最上位のStatusは1つのみとし、深さは1段階ずつ増加させる
+                if record.depth > ancestors.len() || (record.depth == 0 && !ancestors.is_empty()) {
+                    return Err(invalid(line, format!("unexpected depth {}", record.depth)));
+                }
+                // 兄弟またはそれより深いStatusを、親の子属性として確定させる
+                while ancestors.len() > record.depth {
+                    attach(&mut ancestors);
+                }
+                // 子属性を持たないStatusの下には、Statusを配置できない
+                if ancestors
+                    .last()
+                    .is_some_and(|parent| parent.children.is_none())
+                {
+                    return Err(invalid(
+                        line,
+                        "parent has no children (has_children is false)",
+                    ));
+                }
+                ancestors.push(Status {
+                    attr: Attributes::from_str(&record.attr).map_err(|e| invalid(line, e))?,
+                    value: record.value,
+                    children: record.has_children.then(Vec::new),
+                    last_update: record
+                        .last_update
+                        .map(|v| DATETIME.parse(&v))
+                        .transpose()
+                        .map_err(|e| invalid(line, e))?,
+                });
+            }
+            let mut root = None;
+            while !ancestors.is_empty() {
+                root = attach(&mut ancestors);
+            }
+            Ok(Document::Status(
+                root.ok_or_else(|| invalid(1, "no records found"))?,
+            ))
+        }
+        DocumentType::News => {
+            let mut news_items = Vec::new();
+            for (line, record) in records::<NewsItemRecord>(input)? {
+                news_items.push(NewsItem {
+                    date: DATE.parse(&record.date).map_err(|e| invalid(line, e))?,
+                    text: record.text,
+                    url: record.url.parse().map_err(|e| invalid(line, e))?,
+                });
+            }
+            Ok(Document::News(NewsItems { news_items }))
+        }
+        DocumentType::LastUpdate => {
+            let mut records = records::<LastUpdateRecord>(input)?.into_iter();
+            let (line, record) = records
+                .next()
+                .ok_or_else(|| invalid(1, "no records found"))?;
+            // 最終更新日時は1件のみとし、2件目以降のレコードは不正とする
+            if let Some((line, _)) = records.next() {
+                return Err(invalid(line, "only one record is allowed"));
+            }
+            Ok(Document::LastUpdate(LastUpdate {
+                datetime: DATETIME
+                    .parse(&record.last_update)
+                    .map_err(|e| invalid(line, e))?,
+            }))
+        }
+        DocumentType::AgeDistribution | DocumentType::Vaccination => {
+            Err(unsupported_csv(document_type))
+        }
+    }
+}
+
+/// 末尾のStatusを取り出し、親の子属性に追加します。
+///
+/// 親が存在しない場合は、取り出したStatusを最上位のStatusとして返却します。
+fn attach(ancestors: &mut Vec<Status>) -> Option<Status> {
+    let status = ancestors.pop()?;
+    match ancestors.last_mut() {
+        Some(parent) => {
+            // 子属性を持たない親の下に積み上げることはないため、常に子属性が存在する
+            parent.children.get_or_insert_with(Vec::new).push(status);
+            None
+        }
+        None => Some(status),
+    }
+}
+
+/// Statusの階層を、行きがけ順のレコードとして追加します。
+fn status_records(status: &Status, depth: usize, records: &mut Vec<StatusRecord>) {
+    records.push(StatusRecord {
+        depth,
+        attr: status.attr.as_str().to_string(),
+        value: status.value,
+        has_children: status.children.is_some(),
+        last_update: status.last_update.map(|v| DATETIME.format(&v)),
+    });
+    for child in status.children.iter().flatten() {
+        status_records(child, depth + 1, records);
+    }
+}
+
+/// 構造体を、CSV形式で書き出します。
+fn write_csv(document: &Document, dates: DateRepresentation) -> Result<Vec<u8>, ConvertError> {
+    // レコードが存在しない場合もヘッダーを出力するため、ヘッダーは明示的に書き出す
+    let mut writer = csv::WriterBuilder::new()
+        .has_headers(false)
+        .from_writer(Vec::new());
+
+    match document {
+        Document::Summary(summary) => {
+            writer.write_record(["date", "sum", "last_update"])?;
+            let last_update = DATETIME.format(&summary.last_update);
+            // 小計が存在しない場合も最終更新日時を失わないよう、最終更新日時のみのレコードを書き出す
+            if summary.data.is_empty() {
+                writer.serialize(SummaryRecord {
+                    date: None,
+                    sum: None,
+                    last_update: last_update.clone(),
+                })?;
+            }
+            for content in &summary.data {
+                writer.serialize(SummaryRecord {
+                    date: Some(dates.codec().format(&content.date)),
+                    sum: Some(content.sum),
+                    last_update: last_update.clone(),
+                })?;
+            }
+        }
+        Document::Status(status) => {
+            writer.write_record(["depth", "attr", "value", "has_children", "last_update"])?;
+            let mut records = Vec::new();
+            status_records(status, 0, &mut records);
+            for record in records {
+                writer.serialize(record)?;
+            }
+        }
+        Document::News(news) => {
+            writer.write_record(["date", "text", "url"])?;
+            for item in &news.news_items {
+                writer.serialize(NewsItemRecord {
+                    date: DATE.format(&item.date),
+                    text: item.text.clone(),
+                    url: item.url.to_string(),
+                })?;
+            }
+        }
+        Document::LastUpdate(last_update) => {
+            writer.write_record(["last_update"])?;
+            writer.serialize(LastUpdateRecord {
+                last_update: DATETIME.format(&last_update.datetime),
+            })?;
+        }
+        Document::AgeDistribution(_) | Document::Vaccination(_) => {
+            return Err(unsupported_csv(document.document_type()))
+        }
+    }
+
+    writer
+        .into_inner()
+        .map_err(|error| ConvertError::Csv(error.into_error().into()))
+}
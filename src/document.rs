@@ -0,0 +1,342 @@
+//! データファイルの種別を指定し、対応する構造体として読み込む機能を提供します。
+
+use crate::{
+    structs::{
+        age_distribution::AgeDistribution,
+        last_update::LastUpdate,
+        news::NewsItems,
+        status::Status,
+        summary::{DateRepresentation, Summary},
+        vaccination::{Dose, Vaccination},
+    },
+    utils::formats::{
+        codec::{DATE, DATETIME},
+        era::{format_era_datetime, Digits},
+    },
+};
+use chrono::Local;
+use std::{
+    error::Error as StdError,
+    fmt::{Display, Error, Formatter},
+    str::FromStr,
+};
+
+/// データファイルの種別を列挙しています。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DocumentType {
+    /// 小計の一覧です。(`Summary`構造体)
+    Summary,
+    /// COVID-19に関する情報です。(`Status`構造体)
+    Status,
+    /// 最新のお知らせの一覧です。(`NewsItems`構造体)
+    News,
+    /// データの最終更新日です。(`LastUpdate`構造体)
+    LastUpdate,
+    /// 年代別陽性者数です。(`AgeDistribution`構造体)
+    AgeDistribution,
+    /// ワクチン接種の実績です。(`Vaccination`構造体)
+    Vaccination,
+}
+
+impl DocumentType {
+    /// 全ての種別を列挙した配列です。
+    pub const ALL: &'static [DocumentType] = &[
+        DocumentType::Summary,
+        DocumentType::Status,
+        DocumentType::News,
+        DocumentType::LastUpdate,
+        DocumentType::AgeDistribution,
+        DocumentType::Vaccination,
+    ];
+
+    /// 種別の名前を返却します。
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DocumentType::Summary => "summary",
+            DocumentType::Status => "status",
+            DocumentType::News => "news",
+            DocumentType::LastUpdate => "last_update",
+            DocumentType::AgeDistribution => "age_distribution",
+            DocumentType::Vaccination => "vaccination",
+        }
+    }
+}
+
+impl Display for DocumentType {
+    fn fmt(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        write!(formatter, "{}", self.as_str())
+    }
+}
+
+/// 未知のデータファイルの種別が指定された際のエラーです。
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct UnknownDocumentType(pub String);
+
+impl Display for UnknownDocumentType {
+    fn fmt(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        let names = DocumentType::ALL
+            .iter()
+            .map(DocumentType::as_str)
+            .collect::<Vec<&str>>()
+            .join(", ");
+        write!(
+            formatter,
+            "unknown document type `{}` (expected one of {})",
+            self.0, names
+        )
+    }
+}
+
+impl StdError for UnknownDocumentType {}
+
+impl FromStr for DocumentType {
+    type Err = UnknownDocumentType;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        DocumentType::ALL
+            .iter()
+            .find(|document_type| document_type.as_str() == value)
+            .copied()
+            .ok_or_else(|| UnknownDocumentType(value.to_string()))
+    }
+}
+
+/// データファイルのデシリアライズに失敗した際のエラーです。
+///
+/// エラーが発生した位置を、JSONのパス(例: `data[3].date`)として保持します。
+#[derive(Debug)]
+pub struct DocumentError {
+    path: String,
+    error: serde_json::Error,
+}
+
+impl DocumentError {
+    /// エラーが発生した位置を、JSONのパスとして返却します。
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// エラーが発生した行番号を返却します。
+    pub fn line(&self) -> usize {
+        self.error.line()
+    }
+
+    /// エラーが発生した列番号を返却します。
+    pub fn column(&self) -> usize {
+        self.error.column()
+    }
+}
+
+impl Display for DocumentError {
+    fn fmt(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        write!(formatter, "at `{}`: {}", self.path, self.error)
+    }
+}
+
+impl StdError for DocumentError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// 種別ごとの構造体を格納します。
+///
+/// ワクチン接種の実績は接種率を浮動小数点数で保持するため、`Eq`と`Hash`は実装しません。
+#[derive(Clone, Debug, PartialEq)]
+pub enum Document {
+    Summary(Summary),
+    Status(Status),
+    News(NewsItems),
+    LastUpdate(LastUpdate<Local>),
+    AgeDistribution(AgeDistribution),
+    Vaccination(Vaccination),
+}
+
+impl Document {
+    /// JSON形式の文字列を、指定された種別の構造体としてデシリアライズします。
+    pub fn from_json(document_type: DocumentType, json: &str) -> Result<Document, DocumentError> {
+        match document_type {
+            DocumentType::Summary => deserialize(json).map(Document::Summary),
+            DocumentType::Status => deserialize(json).map(Document::Status),
+            DocumentType::News => deserialize(json).map(Document::News),
+            DocumentType::LastUpdate => deserialize(json).map(Document::LastUpdate),
+            DocumentType::AgeDistribution => deserialize(json).map(Document::AgeDistribution),
+            DocumentType::Vaccination => deserialize(json).map(Document::Vaccination),
+        }
+    }
+
+    /// 構造体の種別を返却します。
+    pub fn document_type(&self) -> DocumentType {
+        match self {
+            Document::Summary(_) => DocumentType::Summary,
+            Document::Status(_) => DocumentType::Status,
+            Document::News(_) => DocumentType::News,
+            Document::LastUpdate(_) => DocumentType::LastUpdate,
+            Document::AgeDistribution(_) => DocumentType::AgeDistribution,
+            Document::Vaccination(_) => DocumentType::Vaccination,
+        }
+    }
+
+    /// 構造体を、正規化されたJSON形式の文字列としてシリアライズします。
+    pub fn to_json_pretty(&self) -> Result<String, serde_json::Error> {
+        self.to_json_pretty_with_dates(DateRepresentation::default())
+    }
+
+    /// 小計の日付の表現形式を指定して、構造体をJSON形式の文字列としてシリアライズします。
+    pub fn to_json_pretty_with_dates(
+        &self,
+        dates: DateRepresentation,
+    ) -> Result<String, serde_json::Error> {
+        match self {
+            Document::Summary(v) => serde_json::to_string_pretty(&v.with_dates(dates)),
+            Document::Status(v) => serde_json::to_string_pretty(v),
+            Document::News(v) => serde_json::to_string_pretty(v),
+            Document::LastUpdate(v) => serde_json::to_string_pretty(v),
+            Document::AgeDistribution(v) => serde_json::to_string_pretty(v),
+            Document::Vaccination(v) => serde_json::to_string_pretty(v),
+        }
+    }
+
+    /// 構造体の内容を、項目名と値の組の一覧として要約します。
+    pub fn inspect(&self) -> Vec<(String, String)> {
+        let mut rows = vec![("type".to_string(), self.document_type().to_string())];
+
+        match self {
+            Document::Summary(summary) => {
+                rows.push((
+                    "last_update".to_string(),
+                    with_era(
+                        DATETIME.format(&summary.last_update),
+                        summary.era_last_update(Digits::HalfWidth),
+                    ),
+                ));
+                rows.push(("entries".to_string(), summary.data.len().to_string()));
+                if let (Some(first), Some(last)) = (summary.data.first(), summary.data.last()) {
+                    rows.push((
+                        "period".to_string(),
+                        format!("{} - {}", DATE.format(&first.date), DATE.format(&last.date)),
+                    ));
+                }
+                let total = summary.data.iter().map(|v| u64::from(v.sum)).sum::<u64>();
+                rows.push(("total".to_string(), total.to_string()));
+                if let Some(max) = summary.data.iter().max_by_key(|v| v.sum) {
+                    rows.push((
+                        "max".to_string(),
+                        format!("{} ({})", max.sum, DATE.format(&max.date)),
+                    ));
+                }
+            }
+            Document::Status(status) => {
+                if let Some(last_update) = status.last_update {
+                    rows.push((
+                        "last_update".to_string(),
+                        with_era(
+                            DATETIME.format(&last_update),
+                            format_era_datetime(last_update.naive_local(), Digits::HalfWidth),
+                        ),
+                    ));
+                }
+                inspect_status(status, 0, &mut rows);
+            }
+            Document::News(news) => {
+                rows.push(("entries".to_string(), news.news_items.len().to_string()));
+                for item in &news.news_items {
+                    rows.push((DATE.format(&item.date), item.text.clone()));
+                }
+            }
+            Document::LastUpdate(last_update) => {
+                rows.push((
+                    "last_update".to_string(),
+                    with_era(
+                        DATETIME.format(&last_update.datetime),
+                        last_update.era_datetime(Digits::HalfWidth),
+                    ),
+                ));
+            }
+            Document::AgeDistribution(distribution) => {
+                rows.push((
+                    "last_update".to_string(),
+                    with_era(
+                        DATETIME.format(&distribution.last_update),
+                        format_era_datetime(
+                            distribution.last_update.naive_local(),
+                            Digits::HalfWidth,
+                        ),
+                    ),
+                ));
+                rows.push(("total".to_string(), distribution.total().to_string()));
+                for content in &distribution.data {
+                    rows.push((content.age_group.to_string(), content.count.to_string()));
+                }
+                if let Some(daily) = &distribution.daily {
+                    rows.push(("days".to_string(), daily.len().to_string()));
+                }
+            }
+            Document::Vaccination(vaccination) => {
+                rows.push((
+                    "last_update".to_string(),
+                    with_era(
+                        DATETIME.format(&vaccination.last_update),
+                        format_era_datetime(
+                            vaccination.last_update.naive_local(),
+                            Digits::HalfWidth,
+                        ),
+                    ),
+                ));
+                rows.push(("entries".to_string(), vaccination.data.len().to_string()));
+                let cumulative = vaccination.cumulative();
+                for dose in Dose::ALL {
+                    rows.push((dose.to_string(), cumulative.get(dose).to_string()));
+                }
+                for coverage in &vaccination.coverage {
+                    let rates = Dose::ALL
+                        .iter()
+                        .map(|dose| format!("{}%", coverage.rates.get(*dose)))
+                        .collect::<Vec<String>>()
+                        .join(" / ");
+                    rows.push((coverage.age_group.to_string(), rates));
+                }
+            }
+        }
+
+        rows
+    }
+}
+
+/// 日付・時刻の文字列に、和暦で表現した日付・時刻を併記します。
+fn with_era(formatted: String, era: Option<String>) -> String {
+    match era {
+        Some(era) => format!("{} ({})", formatted, era),
+        None => formatted,
+    }
+}
+
+/// Statusの階層を、字下げされた属性名と値の組として追加します。
+fn inspect_status(status: &Status, depth: usize, rows: &mut Vec<(String, String)>) {
+    rows.push((
+        format!("{}{}", "  ".repeat(depth), status.attr.as_str()),
+        status.value.to_string(),
+    ));
+    for child in status.children.iter().flatten() {
+        inspect_status(child, depth + 1, rows);
+    }
+}
+
+/// JSON形式の文字列をデシリアライズし、失敗した場合はエラーが発生した位置を記録します。
+fn deserialize<'de, T>(json: &'de str) -> Result<T, DocumentError>
+where
+    T: serde::Deserialize<'de>,
+{
+    let mut deserializer = serde_json::Deserializer::from_str(json);
+    let value =
+        serde_path_to_error::deserialize(&mut deserializer).map_err(|error| DocumentError {
+            path: error.path().to_string(),
+            error: error.into_inner(),
+        })?;
+    // 末尾に余分な内容が含まれていないか確認する
+    deserializer.end().map_err(|error| DocumentError {
+        path: ".".to_string(),
+        error,
+    })?;
+    Ok(value)
+}
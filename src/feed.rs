@@ -0,0 +1,319 @@
+//! 最新のお知らせ(`NewsItems`)を、RSS 2.0形式とAtom 1.0形式のフィードとして読み書きする機能を提供します。
+//!
+//! お知らせの日付は、日本標準時(JST)の0時0分として扱います。
+
+use crate::{
+    structs::news::{link::UrlError, NewsItem},
+    utils::formats::jst,
+};
+use chrono::{DateTime, FixedOffset};
+use quick_xml::{
+    events::{BytesStart, Event},
+    name::QName,
+    Reader,
+};
+use std::{
+    error::Error as StdError,
+    fmt::{Display, Error, Formatter},
+};
+
+pub mod atom;
+pub mod rss;
+
+/// フィードのチャンネル情報です。
+///
+/// お知らせ以外の、フィード全体に関する情報を指定します。
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FeedChannel {
+    /// フィードのタイトルです。Atom形式では著者名としても使用します。
+    pub title: String,
+    /// フィードに対応するWebサイトのURLです。
+    pub link: String,
+    /// フィードの説明です。
+    pub description: String,
+    /// フィードの言語です。(例: `ja`)
+    pub language: Option<String>,
+    /// フィード自身のURLです。Atom形式ではフィードのIDとしても使用します。
+    pub feed_url: Option<String>,
+    /// フィードの発行者が所有するドメイン名です。(例: `kyoto.stopcovid19.jp`)
+    ///
+    /// 各項目の識別子(tag URI)の発行者として使用するため、お知らせのリンク先のドメインとは限りません。
+    pub domain: String,
+}
+
+/// フィードの読み込みに失敗した際のエラーです。
+#[derive(Debug)]
+pub enum FeedError {
+    /// XMLとして不正な入力です。
+    Xml(quick_xml::Error),
+    /// 項目に必要な要素が含まれていません。
+    MissingElement {
+        /// 項目の番号(0始まり)です。
+        index: usize,
+        /// 不足している要素の名前です。
+        element: String,
+    },
+    /// リンクが不正なURLです。
+    InvalidUrl {
+        /// 項目の番号(0始まり)です。
+        index: usize,
+        /// URLの検証に失敗した理由です。
+        error: UrlError,
+    },
+    /// 日付をパースできませんでした。
+    InvalidDate {
+        /// 項目の番号(0始まり)です。
+        index: usize,
+        /// パースできなかった文字列です。
+        value: String,
+    },
+}
+
+impl Display for FeedError {
+    fn fmt(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        match self {
+            FeedError::Xml(error) => write!(formatter, "{}", error),
+            FeedError::MissingElement { index, element } => {
+                write!(formatter, "item {}: missing `{}` element", index, element)
+            }
+            FeedError::InvalidUrl { index, error } => {
+                write!(formatter, "item {}: {}", index, error)
+            }
+            FeedError::InvalidDate { index, value } => {
+                write!(formatter, "item {}: invalid date `{}`", index, value)
+            }
+        }
+    }
+}
+
+impl StdError for FeedError {}
+
+impl From<quick_xml::Error> for FeedError {
+    fn from(error: quick_xml::Error) -> Self {
+        FeedError::Xml(error)
+    }
+}
+
+impl From<quick_xml::events::attributes::AttrError> for FeedError {
+    fn from(error: quick_xml::events::attributes::AttrError) -> Self {
+        FeedError::Xml(error.into())
+    }
+}
+
+/// お知らせの一意な識別子を、RFC 4151のtag URI(`tag:<ドメイン名>,<日付>:<URL>`)として返却します。
+///
+/// 発行者にはチャンネル情報の`domain`を使用し、お知らせのリンク先のホスト名は使用しません。
+/// URLと日付から生成するため、フィードを再生成しても値は変化しません。
+/// URLの`%`と`#`はパーセントエンコードするため、フラグメントを含むURLでも、日付やURLの異なるお知らせと識別子が重複しません。
+pub fn guid(channel: &FeedChannel, item: &NewsItem) -> String {
+    format!(
+        "tag:{},{}:{}",
+        channel.domain,
+        item.date.format("%Y-%m-%d"),
+        item.url
+            .as_url()
+            .as_str()
+            .replace('%', "%25")
+            .replace('#', "%23")
+    )
+}
+
+/// 要素名から、名前空間の接頭辞を除いた名前を返却します。
+fn local_name(name: QName<'_>) -> &[u8] {
+    name.local_name().into_inner()
+}
+
+/// 要素の属性の値を返却します。
+fn attribute(element: &BytesStart, name: &[u8]) -> Result<Option<String>, FeedError> {
+    for attribute in element.attributes() {
+        let attribute = attribute?;
+        if attribute.key.local_name().into_inner() == name {
+            return Ok(Some(attribute.unescape_value()?.into_owned()));
+        }
+    }
+    Ok(None)
+}
+
+/// 読み込み中の項目で、テキストを格納する対象の要素です。
+enum ItemField {
+    Title,
+    Link,
+    Date,
+}
+
+/// 読み込み中の項目の内容です。
+#[derive(Default)]
+struct PartialItem {
+    title: Option<String>,
+    link: Option<String>,
+    /// 日付と、その日付を含んでいた要素の優先順位です。
+    date: Option<(usize, String)>,
+}
+
+/// フィードの項目の要素名を指定します。
+pub(crate) struct ItemElements {
+    /// 項目を表す要素の名前です。
+    pub(crate) item: &'static [u8],
+    /// 日付を含む要素の名前です。先頭の要素ほど優先して使用します。
+    pub(crate) dates: &'static [&'static [u8]],
+}
+
+/// フィードを読み込み、項目ごとにお知らせへ変換します。
+///
+/// リンクは、要素のテキストと`href`属性(`rel`属性が`alternate`または省略されたもの)の両方に対応します。
+/// 日付は指定された関数でパースし、日本標準時の日付に変換します。
+pub(crate) fn read_items<F>(
+    xml: &str,
+    elements: &ItemElements,
+    parse_date: F,
+) -> Result<Vec<NewsItem>, FeedError>
+where
+    F: Fn(&str) -> Option<DateTime<FixedOffset>>,
+{
+    let mut reader = Reader::from_str(xml);
+    let mut items = Vec::new();
+    let mut item: Option<PartialItem> = None;
+    // 項目内で開かれている要素の深さです。項目の直下の要素は、深さ0で開かれます。
+    let mut depth = 0;
+    // テキストを格納する対象と、その要素が開かれた深さです。
+    let mut field: Option<(ItemField, usize)> = None;
+
+    loop {
+        match reader.read_event()? {
+            Event::Start(element) => {
+                let name = local_name(element.name());
+                if item.is_none() && name == elements.item {
+                    // 項目の読み込みを開始する
+                    item = Some(PartialItem::default());
+                    depth = 0;
+                } else if let Some(item) = item.as_mut() {
+                    // 項目の直下の要素のみを対象とし、<source>内の<title>などは無視する
+                    if depth == 0 {
+                        field = item_field(item, &element, elements)?.map(|field| (field, depth));
+                    }
+                    depth += 1;
+                }
+            }
+            Event::Empty(element) => {
+                if let Some(item) = item.as_mut().filter(|_| depth == 0) {
+                    item_field(item, &element, elements)?;
+                }
+            }
+            Event::Text(text) => {
+                if let (Some(item), Some((field, _))) = (item.as_mut(), field.as_ref()) {
+                    push_text(item, field, &text.unescape()?);
+                }
+            }
+            Event::CData(text) => {
+                if let (Some(item), Some((field, _))) = (item.as_mut(), field.as_ref()) {
+                    push_text(item, field, &String::from_utf8_lossy(&text));
+                }
+            }
+            Event::End(element) => {
+                if item.is_some() && depth > 0 {
+                    depth -= 1;
+                    // 対象の要素が閉じられた場合のみ、格納を終了する
+                    if field.as_ref().is_some_and(|(_, opened)| *opened == depth) {
+                        field = None;
+                    }
+                } else if local_name(element.name()) == elements.item {
+                    // 項目の読み込みを終了し、お知らせに変換する
+                    if let Some(item) = item.take() {
+                        let index = items.len();
+                        items.push(into_news_item(item, index, elements, &parse_date)?);
+                    }
+                }
+            }
+            Event::Eof => break,
+            _ => (),
+        }
+    }
+
+    Ok(items)
+}
+
+/// 項目内の要素について、テキストを格納する対象を返却します。
+///
+/// `href`属性を持つリンクの場合は、この時点でリンクを格納します。
+fn item_field(
+    item: &mut PartialItem,
+    element: &BytesStart,
+    elements: &ItemElements,
+) -> Result<Option<ItemField>, FeedError> {
+    let name = local_name(element.name());
+
+    if name == b"title" {
+        item.title = Some(String::new());
+        return Ok(Some(ItemField::Title));
+    }
+    if name == b"link" {
+        if let Some(href) = attribute(element, b"href")? {
+            let rel = attribute(element, b"rel")?;
+            if item.link.is_none() && rel.as_deref().unwrap_or("alternate") == "alternate" {
+                item.link = Some(href);
+            }
+            return Ok(None);
+        }
+        item.link = Some(String::new());
+        return Ok(Some(ItemField::Link));
+    }
+    if let Some(priority) = elements.dates.iter().position(|date| *date == name) {
+        // 優先順位の高い日付が格納されていない場合のみ、格納の対象とする
+        if item
+            .date
+            .as_ref()
+            .is_none_or(|(current, _)| priority <= *current)
+        {
+            item.date = Some((priority, String::new()));
+            return Ok(Some(ItemField::Date));
+        }
+    }
+    Ok(None)
+}
+
+/// 項目内の要素のテキストを格納します。
+fn push_text(item: &mut PartialItem, field: &ItemField, text: &str) {
+    let target = match field {
+        ItemField::Title => item.title.as_mut(),
+        ItemField::Link => item.link.as_mut(),
+        ItemField::Date => item.date.as_mut().map(|(_, date)| date),
+    };
+    if let Some(target) = target {
+        target.push_str(text);
+    }
+}
+
+/// 項目の内容を、お知らせに変換します。
+fn into_news_item<F>(
+    item: PartialItem,
+    index: usize,
+    elements: &ItemElements,
+    parse_date: F,
+) -> Result<NewsItem, FeedError>
+where
+    F: Fn(&str) -> Option<DateTime<FixedOffset>>,
+{
+    let missing = |element: &[u8]| FeedError::MissingElement {
+        index,
+        element: String::from_utf8_lossy(element).into_owned(),
+    };
+
+    let (_, date) = item.date.ok_or_else(|| missing(elements.dates[0]))?;
+    Ok(NewsItem {
+        date: parse_date(date.trim())
+            .ok_or(FeedError::InvalidDate { index, value: date })?
+            .with_timezone(&jst())
+            .date_naive(),
+        text: item
+            .title
+            .ok_or_else(|| missing(b"title"))?
+            .trim()
+            .to_string(),
+        url: item
+            .link
+            .ok_or_else(|| missing(b"link"))?
+            .trim()
+            .parse()
+            .map_err(|error| FeedError::InvalidUrl { index, error })?,
+    })
+}
@@ -0,0 +1,89 @@
+use super::{guid, read_items, FeedChannel, FeedError, ItemElements};
+use crate::{structs::news::NewsItems, utils::formats::jst_midnight};
+use chrono::{DateTime, SecondsFormat};
+use quick_xml::escape::escape;
+
+/// Atom 1.0形式の項目の要素名です。
+const ATOM_ELEMENTS: ItemElements = ItemElements {
+    item: b"entry",
+    dates: &[b"published", b"updated"],
+};
+
+impl NewsItems {
+    /// お知らせの一覧を、Atom 1.0形式のフィードとして出力します。
+    ///
+    /// フィードのIDには`feed_url`(省略された場合は`link`)を、
+    /// 各項目のIDにはチャンネル情報のドメイン名とURLと日付から生成した一意な識別子を出力します。
+    pub fn to_atom(&self, channel: &FeedChannel) -> String {
+        let id = channel.feed_url.as_ref().unwrap_or(&channel.link);
+        // 最も新しいお知らせの日付を、フィードの更新日時とする
+        let updated = self
+            .news_items
+            .iter()
+            .map(|item| item.date)
+            .max()
+            .map(|date| jst_midnight(date).to_rfc3339_opts(SecondsFormat::Secs, false));
+
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        match &channel.language {
+            Some(language) => xml.push_str(&format!(
+                "<feed xmlns=\"http://www.w3.org/2005/Atom\" xml:lang=\"{}\">\n",
+                escape(language)
+            )),
+            None => xml.push_str("<feed xmlns=\"http://www.w3.org/2005/Atom\">\n"),
+        }
+        xml.push_str(&format!("  <id>{}</id>\n", escape(id)));
+        xml.push_str(&format!("  <title>{}</title>\n", escape(&channel.title)));
+        xml.push_str(&format!(
+            "  <subtitle>{}</subtitle>\n",
+            escape(&channel.description)
+        ));
+        xml.push_str(&format!(
+            "  <link href=\"{}\" rel=\"alternate\"/>\n",
+            escape(&channel.link)
+        ));
+        if let Some(feed_url) = &channel.feed_url {
+            xml.push_str(&format!(
+                "  <link href=\"{}\" rel=\"self\"/>\n",
+                escape(feed_url)
+            ));
+        }
+        // お知らせが存在しない場合は、Unix時間の起点を更新日時とする
+        xml.push_str(&format!(
+            "  <updated>{}</updated>\n",
+            updated.as_deref().unwrap_or("1970-01-01T00:00:00Z")
+        ));
+        xml.push_str(&format!(
+            "  <author>\n    <name>{}</name>\n  </author>\n",
+            escape(&channel.title)
+        ));
+
+        for item in &self.news_items {
+            let date = jst_midnight(item.date).to_rfc3339_opts(SecondsFormat::Secs, false);
+            xml.push_str("  <entry>\n");
+            xml.push_str(&format!("    <id>{}</id>\n", escape(guid(channel, item))));
+            xml.push_str(&format!("    <title>{}</title>\n", escape(&item.text)));
+            xml.push_str(&format!(
+                "    <link href=\"{}\" rel=\"alternate\"/>\n",
+                escape(item.url.as_str())
+            ));
+            xml.push_str(&format!("    <published>{}</published>\n", date));
+            xml.push_str(&format!("    <updated>{}</updated>\n", date));
+            xml.push_str("  </entry>\n");
+        }
+
+        xml.push_str("</feed>\n");
+        xml
+    }
+
+    /// Atom 1.0形式のフィードを、お知らせの一覧として読み込みます。
+    ///
+    /// 日付は`published`(省略された場合は`updated`)を使用し、日本標準時の日付に変換します。
+    pub fn from_atom(xml: &str) -> Result<NewsItems, FeedError> {
+        Ok(NewsItems {
+            news_items: read_items(xml, &ATOM_ELEMENTS, |value| {
+                DateTime::parse_from_rfc3339(value).ok()
+            })?,
+        })
+    }
+}
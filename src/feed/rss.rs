@@ -0,0 +1,76 @@
+use super::{guid, read_items, FeedChannel, FeedError, ItemElements};
+use crate::{structs::news::NewsItems, utils::formats::jst_midnight};
+use chrono::DateTime;
+use quick_xml::escape::escape;
+
+/// RSS 2.0形式の項目の要素名です。
+const RSS_ELEMENTS: ItemElements = ItemElements {
+    item: b"item",
+    dates: &[b"pubDate"],
+};
+
+impl NewsItems {
+    /// お知らせの一覧を、RSS 2.0形式のフィードとして出力します。
+    ///
+    /// 各項目の`guid`には、チャンネル情報のドメイン名とURLと日付から生成した一意な識別子を出力します。
+    pub fn to_rss(&self, channel: &FeedChannel) -> String {
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str("<rss version=\"2.0\" xmlns:atom=\"http://www.w3.org/2005/Atom\">\n");
+        xml.push_str("  <channel>\n");
+        xml.push_str(&format!("    <title>{}</title>\n", escape(&channel.title)));
+        xml.push_str(&format!("    <link>{}</link>\n", escape(&channel.link)));
+        xml.push_str(&format!(
+            "    <description>{}</description>\n",
+            escape(&channel.description)
+        ));
+        if let Some(language) = &channel.language {
+            xml.push_str(&format!("    <language>{}</language>\n", escape(language)));
+        }
+        if let Some(feed_url) = &channel.feed_url {
+            xml.push_str(&format!(
+                "    <atom:link href=\"{}\" rel=\"self\" type=\"application/rss+xml\"/>\n",
+                escape(feed_url)
+            ));
+        }
+        // 最も新しいお知らせの日付を、フィードの更新日時とする
+        if let Some(date) = self.news_items.iter().map(|item| item.date).max() {
+            xml.push_str(&format!(
+                "    <lastBuildDate>{}</lastBuildDate>\n",
+                jst_midnight(date).to_rfc2822()
+            ));
+        }
+
+        for item in &self.news_items {
+            xml.push_str("    <item>\n");
+            xml.push_str(&format!("      <title>{}</title>\n", escape(&item.text)));
+            xml.push_str(&format!(
+                "      <link>{}</link>\n",
+                escape(item.url.as_str())
+            ));
+            xml.push_str(&format!(
+                "      <guid isPermaLink=\"false\">{}</guid>\n",
+                escape(guid(channel, item))
+            ));
+            xml.push_str(&format!(
+                "      <pubDate>{}</pubDate>\n",
+                jst_midnight(item.date).to_rfc2822()
+            ));
+            xml.push_str("    </item>\n");
+        }
+
+        xml.push_str("  </channel>\n");
+        xml.push_str("</rss>\n");
+        xml
+    }
+
+    /// RSS 2.0形式のフィードを、お知らせの一覧として読み込みます。
+    ///
+    /// `pubDate`は、日本標準時の日付に変換します。
+    pub fn from_rss(xml: &str) -> Result<NewsItems, FeedError> {
+        Ok(NewsItems {
+            news_items: read_items(xml, &RSS_ELEMENTS, |value| {
+                DateTime::parse_from_rfc2822(value).ok()
+            })?,
+        })
+    }
+}
@@ -1,5 +1,16 @@
 //! 京都府 新型コロナウイルス感染症 対策サイトの開発・運用に使用するデータフォーマットを、Rust言語で実装したプログラムです。
 
+pub mod analysis;
+pub mod archive;
+pub mod codegen;
+pub mod convert;
+pub mod document;
+pub mod feed;
+pub mod migration;
+pub mod population;
+#[cfg(feature = "store")]
+pub mod store;
+pub mod stream;
 pub mod structs;
 pub mod utils;
 
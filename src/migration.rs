@@ -0,0 +1,252 @@
+//! データファイルの形式の版数を管理し、以前の版のデータファイルを現在の構造体に変換する機能を提供します。
+//!
+//! 版数を含むデータファイルは、`{"version": 3, "data": [...], ...}`のように、
+//! 構造体のフィールドと同じ階層に`version`フィールドを持ちます。
+//! 以前の版のデータファイルは、版ごとの変換処理(`MIGRATIONS`)を1段階ずつ適用して、現在の版に変換します。
+
+use crate::document::{Document, DocumentError, DocumentType};
+use serde::{de::IgnoredAny, Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::{
+    borrow::Cow,
+    error::Error as StdError,
+    fmt::{Display, Error, Formatter},
+};
+
+pub mod steps;
+
+/// 現在のデータファイルの形式の版数です。
+pub const CURRENT_VERSION: u32 = 3;
+
+/// `version`フィールドを持たないデータファイルの版数です。
+///
+/// 各版の変換処理は、既に変換済みの内容を変更しないため、全ての版のデータファイルをこの版として扱えます。
+pub const UNVERSIONED: u32 = 1;
+
+/// 版数を格納するフィールド名です。
+pub const VERSION_FIELD: &str = "version";
+
+/// ある版から次の版への変換処理です。
+#[derive(Clone, Copy, Debug)]
+pub struct Migration {
+    /// 変換元の版数です。変換後の版数は、この値に1を加えたものになります。
+    pub from: u32,
+    /// 変更内容の説明です。
+    pub description: &'static str,
+    /// データファイルの内容を、次の版の形式に書き換えます。
+    pub migrate: fn(DocumentType, &mut Map<String, Value>) -> Result<(), String>,
+}
+
+/// 版ごとの変換処理を、版数の順に列挙した配列です。
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        from: 1,
+        description: "Statusに子属性(`children`)を追加",
+        migrate: steps::v1_to_v2,
+    },
+    Migration {
+        from: 2,
+        description: "SummaryContentの日付を、RFC3339形式から日本標準時の日付(`2020/03/25`)に変更",
+        migrate: steps::v2_to_v3,
+    },
+];
+
+/// データファイルの変換に失敗した際のエラーです。
+#[derive(Debug)]
+pub enum MigrationError {
+    /// JSON形式として不正な入力です。
+    Json(serde_json::Error),
+    /// データファイルの最上位が、連想配列ではありません。
+    NotAnObject,
+    /// 版数が、0以上の整数ではありません。
+    InvalidVersion(Value),
+    /// 本クレートが対応していない版数です。
+    UnsupportedVersion(u32),
+    /// 版の変換処理に失敗しました。
+    Step {
+        /// 変換元の版数です。
+        from: u32,
+        /// エラーの内容です。
+        message: String,
+    },
+    /// 変換後の内容を、構造体として読み込めません。
+    Document(DocumentError),
+}
+
+impl Display for MigrationError {
+    fn fmt(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        match self {
+            MigrationError::Json(error) => write!(formatter, "{}", error),
+            MigrationError::NotAnObject => write!(formatter, "document is not a JSON object"),
+            MigrationError::InvalidVersion(value) => {
+                write!(formatter, "invalid version `{}`", value)
+            }
+            MigrationError::UnsupportedVersion(version) => write!(
+                formatter,
+                "unsupported version {} (expected {} to {})",
+                version, UNVERSIONED, CURRENT_VERSION
+            ),
+            MigrationError::Step { from, message } => write!(
+                formatter,
+                "cannot migrate from version {} to {}: {}",
+                from,
+                from + 1,
+                message
+            ),
+            MigrationError::Document(error) => write!(formatter, "{}", error),
+        }
+    }
+}
+
+impl StdError for MigrationError {}
+
+/// JSON形式のエラーを返却する読み込み処理で使用するため、変換のエラーをJSON形式のエラーとして扱います。
+impl From<MigrationError> for serde_json::Error {
+    fn from(error: MigrationError) -> Self {
+        match error {
+            MigrationError::Json(error) => error,
+            error => serde::de::Error::custom(error),
+        }
+    }
+}
+
+/// `version`フィールドの値を検証し、版数を返却します。
+pub fn check_version(value: Value) -> Result<u32, MigrationError> {
+    let version = value
+        .as_u64()
+        .and_then(|version| u32::try_from(version).ok())
+        .ok_or(MigrationError::InvalidVersion(value))?;
+    if !(UNVERSIONED..=CURRENT_VERSION).contains(&version) {
+        return Err(MigrationError::UnsupportedVersion(version));
+    }
+    Ok(version)
+}
+
+/// データファイルの内容から`version`フィールドを取り除き、版数を返却します。
+///
+/// `version`フィールドが存在しない場合は、`UNVERSIONED`を返却します。
+fn take_version(object: &mut Map<String, Value>) -> Result<u32, MigrationError> {
+    match object.remove(VERSION_FIELD) {
+        None => Ok(UNVERSIONED),
+        Some(value) => check_version(value),
+    }
+}
+
+/// 指定された版のデータファイルの内容に、現在の版までの変換処理を適用します。
+fn migrate(
+    document_type: DocumentType,
+    version: u32,
+    object: &mut Map<String, Value>,
+) -> Result<(), MigrationError> {
+    for migration in MIGRATIONS.iter().filter(|v| v.from >= version) {
+        (migration.migrate)(document_type, object).map_err(|message| MigrationError::Step {
+            from: migration.from,
+            message,
+        })?;
+    }
+    Ok(())
+}
+
+/// データファイルの内容を、現在の版の形式に変換します。
+///
+/// 返却される内容は`version`フィールドを含まず、構造体としてそのままデシリアライズできます。
+pub fn upgrade(document_type: DocumentType, value: Value) -> Result<Value, MigrationError> {
+    let Value::Object(mut object) = value else {
+        return Err(MigrationError::NotAnObject);
+    };
+    let version = take_version(&mut object)?;
+    migrate(document_type, version, &mut object)?;
+    Ok(Value::Object(object))
+}
+
+/// データファイルの一覧のフィールド(`field`)に含まれる1件の要素を、指定された版から現在の版の形式に変換します。
+///
+/// データファイル全体をメモリに読み込まずに、要素ごとに変換するために使用します。
+pub fn upgrade_element(
+    document_type: DocumentType,
+    version: u32,
+    field: &str,
+    element: Value,
+) -> Result<Value, MigrationError> {
+    let mut object = Map::new();
+    object.insert(field.to_string(), Value::Array(vec![element]));
+    migrate(document_type, version, &mut object)?;
+    Ok(object
+        .get_mut(field)
+        .and_then(|elements| elements.get_mut(0))
+        .map_or(Value::Null, Value::take))
+}
+
+/// いずれかの版のJSON形式の文字列を、現在の版の形式の文字列に変換します。
+///
+/// `version`フィールドを持たないデータファイルは、各版の変換処理を適用した場合と同じ内容となるため、
+/// エラーの行番号と列番号を保つよう、変換せずにそのまま返却します。
+pub fn upgrade_json(
+    document_type: DocumentType,
+    json: &str,
+) -> Result<Cow<'_, str>, MigrationError> {
+    // JSON形式として不正な入力は、構造体として読み込む際にエラーの位置を報告する
+    let versioned =
+        serde_json::from_str::<VersionProbe>(json).is_ok_and(|probe| probe.version.is_some());
+    if !versioned {
+        return Ok(Cow::Borrowed(json));
+    }
+
+    let value = serde_json::from_str(json).map_err(MigrationError::Json)?;
+    // エラーの位置をJSONのパスで報告するため、文字列を経由して読み込む
+    Ok(Cow::Owned(upgrade(document_type, value)?.to_string()))
+}
+
+/// 構造体を、版数を含むデータファイルとしてシリアライズする際の形式です。
+#[derive(Serialize)]
+struct Envelope<'a, T> {
+    version: u32,
+    #[serde(flatten)]
+    document: &'a T,
+}
+
+/// データファイルが`version`フィールドを持つかを判定するための構造体です。
+///
+/// その他のフィールドは読み飛ばします。
+#[derive(Deserialize)]
+struct VersionProbe {
+    version: Option<IgnoredAny>,
+}
+
+impl Document {
+    /// いずれかの版のJSON形式の文字列を、現在の版に変換した上で、指定された種別の構造体としてデシリアライズします。
+    ///
+    /// 変換は`upgrade_json`で行います。
+    pub fn from_versioned_json(
+        document_type: DocumentType,
+        json: &str,
+    ) -> Result<Document, MigrationError> {
+        let json = upgrade_json(document_type, json)?;
+        Document::from_json(document_type, &json).map_err(MigrationError::Document)
+    }
+
+    /// 構造体を、現在の版数を含むJSON形式の文字列としてシリアライズします。
+    pub fn to_versioned_json_pretty(&self) -> Result<String, serde_json::Error> {
+        let version = CURRENT_VERSION;
+        match self {
+            Document::Summary(document) => {
+                serde_json::to_string_pretty(&Envelope { version, document })
+            }
+            Document::Status(document) => {
+                serde_json::to_string_pretty(&Envelope { version, document })
+            }
+            Document::News(document) => {
+                serde_json::to_string_pretty(&Envelope { version, document })
+            }
+            Document::LastUpdate(document) => {
+                serde_json::to_string_pretty(&Envelope { version, document })
+            }
+            Document::AgeDistribution(document) => {
+                serde_json::to_string_pretty(&Envelope { version, document })
+            }
+            Document::Vaccination(document) => {
+                serde_json::to_string_pretty(&Envelope { version, document })
+            }
+        }
+    }
+}
@@ -0,0 +1,42 @@
+//! 版ごとの変換処理を実装しています。
+//!
+//! 各変換処理は、既に次の版の形式となっている内容を変更しません。
+
+use crate::{document::DocumentType, utils::formats::codec::DATE};
+use chrono::NaiveDate;
+use serde_json::{Map, Value};
+
+/// 第1版から第2版に変換します。
+///
+/// 第2版ではStatusに子属性(`children`)が追加されました。
+/// `children`は省略可能なフィールドであり、子属性を持たない第1版のStatusはそのまま第2版のStatusとして有効なため、
+/// 内容の書き換えは不要です。変換処理は、版数を1段階ずつ進めるためだけに定義しています。
+pub fn v1_to_v2(_: DocumentType, _: &mut Map<String, Value>) -> Result<(), String> {
+    Ok(())
+}
+
+/// 第2版から第3版に変換します。
+///
+/// 小計の日付を、RFC3339形式の日時から、日本標準時における日付(`2020/03/25`)に書き換えます。
+pub fn v2_to_v3(
+    document_type: DocumentType,
+    object: &mut Map<String, Value>,
+) -> Result<(), String> {
+    if document_type != DocumentType::Summary {
+        return Ok(());
+    }
+    let Some(Value::Array(data)) = object.get_mut("data") else {
+        return Ok(());
+    };
+
+    for (index, content) in data.iter_mut().enumerate() {
+        let Some(Value::String(date)) = content.get_mut("date") else {
+            continue;
+        };
+        let parsed = DATE
+            .parse::<NaiveDate>(date)
+            .map_err(|error| format!("at `data[{}].date`: {}", index, error))?;
+        *date = DATE.format(&parsed);
+    }
+    Ok(())
+}
@@ -0,0 +1,383 @@
+//! 人口のデータセットと、人口10万人あたりの値への換算を提供します。
+//!
+//! データセットは、国勢調査の年ごとに、京都府と府内の市町村の総人口と年代ごとの人口を保持します。
+//! 数値は検証可能な出典(国勢調査の公表値)に基づく必要があるため、CSV形式のファイルから読み込みます。
+//! `BUNDLED`に登録したデータセットは、クレートに同梱され、`Populations::bundled`から使用できます。
+//! 同梱しているのは京都府と京都市の総人口のみのため、その他の市町村や年代ごとの人口は、`Populations::insert`で追加する必要があります。
+
+use crate::structs::{
+    status::{Attributes, Status},
+    summary::Summary,
+};
+use crate::utils::formats::codec::{DATE, DATETIME};
+use chrono::{DateTime, Local, NaiveDate};
+use serde::{ser::SerializeStruct, Deserialize, Serialize, Serializer};
+use std::{
+    collections::BTreeMap,
+    error::Error as StdError,
+    fmt::{Display, Error, Formatter},
+};
+
+pub mod age_band;
+
+use age_band::AgeBand;
+
+/// 京都府全体を表す地域名です。
+pub const PREFECTURE: &str = "京都府";
+
+/// 換算の基準とする人口です。
+pub const PER: f64 = 100_000.0;
+
+/// クレートに同梱するデータセットの、(調査年, 出典, CSV形式のデータ)の一覧です。
+///
+/// 出典の公表値と照合できない数値は、同梱しません。
+/// 現在は、京都府と京都市の総人口のみを同梱しています。
+const BUNDLED: &[(u16, &str, &str)] = &[
+    (
+        2015,
+        "総務省統計局「平成27年国勢調査 人口等基本集計」",
+        include_str!("population/data/2015.csv"),
+    ),
+    (
+        2020,
+        "総務省統計局「令和2年国勢調査 人口等基本集計」",
+        include_str!("population/data/2020.csv"),
+    ),
+];
+
+/// 人口のデータセットの読み込みや、人口による換算に失敗した際のエラーです。
+#[derive(Debug)]
+pub enum PopulationError {
+    /// CSV形式の読み込みに失敗しました。
+    Csv(csv::Error),
+    /// CSV形式のレコードの内容が不正です。
+    InvalidRecord {
+        /// レコードの行番号です。
+        line: u64,
+        /// エラーの内容です。
+        message: String,
+    },
+    /// 指定された調査年のデータセットが存在しません。`None`は、データセットが1つも存在しないことを表します。
+    UnknownYear(Option<u16>),
+    /// 指定された地域の人口が、データセットに存在しません。
+    UnknownRegion(String),
+    /// 指定された地域の、指定された年代の人口が、データセットに存在しません。
+    MissingAgeBand { region: String, age_band: AgeBand },
+    /// 人口が0のため、換算できません。
+    ZeroPopulation {
+        region: String,
+        age_band: Option<AgeBand>,
+    },
+}
+
+impl Display for PopulationError {
+    fn fmt(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        match self {
+            PopulationError::Csv(error) => write!(formatter, "{}", error),
+            PopulationError::InvalidRecord { line, message } => {
+                write!(formatter, "line {}: {}", line, message)
+            }
+            PopulationError::UnknownYear(Some(year)) => {
+                write!(formatter, "no population dataset for {}", year)
+            }
+            PopulationError::UnknownYear(None) => write!(formatter, "no population dataset"),
+            PopulationError::UnknownRegion(region) => {
+                write!(formatter, "no population for `{}`", region)
+            }
+            PopulationError::MissingAgeBand { region, age_band } => {
+                write!(formatter, "no population for `{}` ({})", region, age_band)
+            }
+            PopulationError::ZeroPopulation { region, age_band } => match age_band {
+                Some(age_band) => {
+                    write!(formatter, "population of `{}` ({}) is 0", region, age_band)
+                }
+                None => write!(formatter, "population of `{}` is 0", region),
+            },
+        }
+    }
+}
+
+impl StdError for PopulationError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            PopulationError::Csv(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<csv::Error> for PopulationError {
+    fn from(error: csv::Error) -> Self {
+        PopulationError::Csv(error)
+    }
+}
+
+/// ある地域の人口です。
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RegionPopulation {
+    /// 総人口です。年齢不詳の人口を含むため、年代ごとの人口の合計と一致するとは限りません。
+    pub total: u64,
+    /// 年代ごとの人口です。
+    pub age_bands: BTreeMap<AgeBand, u64>,
+}
+
+/// ある調査年の人口のデータセットです。
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PopulationDataset {
+    /// 調査年です。
+    pub year: u16,
+    /// 出典です。
+    pub source: String,
+    /// 地域名ごとの人口です。
+    pub regions: BTreeMap<String, RegionPopulation>,
+}
+
+/// 人口のデータセットをCSV形式で表現する際のレコードです。
+///
+/// `age_band`が空のレコードは、その地域の総人口を表します。
+#[derive(Deserialize)]
+struct PopulationRecord {
+    region: String,
+    age_band: Option<String>,
+    population: u64,
+}
+
+impl PopulationDataset {
+    /// `region,age_band,population`の列を持つCSV形式の入力から、データセットを読み込みます。
+    ///
+    /// 各地域には、`age_band`が空の総人口のレコードが必要です。
+    pub fn from_csv(
+        year: u16,
+        source: impl Into<String>,
+        input: &[u8],
+    ) -> Result<PopulationDataset, PopulationError> {
+        let invalid = |line, message: String| PopulationError::InvalidRecord { line, message };
+        let mut reader = csv::Reader::from_reader(input);
+        let headers = reader.headers()?.clone();
+
+        let mut totals = BTreeMap::new();
+        let mut regions = BTreeMap::<String, RegionPopulation>::new();
+        for record in reader.records() {
+            let record = record?;
+            let line = record.position().map_or(0, csv::Position::line);
+            let record: PopulationRecord = record.deserialize(Some(&headers))?;
+            let region = regions.entry(record.region.clone()).or_default();
+            let duplicated = match record.age_band.as_deref().filter(|v| !v.is_empty()) {
+                None => {
+                    region.total = record.population;
+                    totals.insert(record.region.clone(), line).is_some()
+                }
+                Some(age_band) => {
+                    let age_band = age_band
+                        .parse::<AgeBand>()
+                        .map_err(|error| invalid(line, error.to_string()))?;
+                    region
+                        .age_bands
+                        .insert(age_band, record.population)
+                        .is_some()
+                }
+            };
+            if duplicated {
+                return Err(invalid(
+                    line,
+                    format!("duplicated population for `{}`", record.region),
+                ));
+            }
+        }
+
+        if let Some(region) = regions.keys().find(|region| !totals.contains_key(*region)) {
+            return Err(invalid(0, format!("no total population for `{}`", region)));
+        }
+        Ok(PopulationDataset {
+            year,
+            source: source.into(),
+            regions,
+        })
+    }
+
+    /// 地域の人口を返却します。`age_band`を指定した場合は、その年代の人口を返却します。
+    pub fn population(
+        &self,
+        region: &str,
+        age_band: Option<AgeBand>,
+    ) -> Result<u64, PopulationError> {
+        let population = self
+            .regions
+            .get(region)
+            .ok_or_else(|| PopulationError::UnknownRegion(region.to_string()))?;
+        match age_band {
+            None => Ok(population.total),
+            Some(age_band) => population.age_bands.get(&age_band).copied().ok_or_else(|| {
+                PopulationError::MissingAgeBand {
+                    region: region.to_string(),
+                    age_band,
+                }
+            }),
+        }
+    }
+
+    /// 値を、地域(と年代)の人口10万人あたりの値に換算します。
+    pub fn per_100k(
+        &self,
+        region: &str,
+        age_band: Option<AgeBand>,
+        value: f64,
+    ) -> Result<f64, PopulationError> {
+        match self.population(region, age_band)? {
+            0 => Err(PopulationError::ZeroPopulation {
+                region: region.to_string(),
+                age_band,
+            }),
+            population => Ok(value * PER / population as f64),
+        }
+    }
+}
+
+/// 調査年ごとの人口のデータセットの一覧です。
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Populations {
+    datasets: BTreeMap<u16, PopulationDataset>,
+}
+
+impl Populations {
+    /// 空の一覧を生成します。
+    pub fn new() -> Populations {
+        Populations::default()
+    }
+
+    /// クレートに同梱するデータセットの一覧を返却します。
+    ///
+    /// 各データセットは京都府と京都市の総人口のみを含むため、その他の市町村の人口は`UnknownRegion`、
+    /// 年代ごとの人口は`MissingAgeBand`のエラーとなります。
+    pub fn bundled() -> Populations {
+        let mut populations = Populations::new();
+        for (year, source, csv) in BUNDLED {
+            let dataset = PopulationDataset::from_csv(*year, *source, csv.as_bytes())
+                .expect("bundled population dataset must be valid");
+            populations.insert(dataset);
+        }
+        populations
+    }
+
+    /// データセットを追加します。同じ調査年のデータセットが存在する場合は、置き換えたデータセットを返却します。
+    pub fn insert(&mut self, dataset: PopulationDataset) -> Option<PopulationDataset> {
+        self.datasets.insert(dataset.year, dataset)
+    }
+
+    /// データセットの調査年を、昇順に返却します。
+    pub fn years(&self) -> impl Iterator<Item = u16> + '_ {
+        self.datasets.keys().copied()
+    }
+
+    /// 調査年を指定して、データセットを返却します。`None`を指定した場合は、最新の調査年のデータセットを返却します。
+    pub fn select(&self, year: Option<u16>) -> Result<&PopulationDataset, PopulationError> {
+        match year {
+            Some(year) => self.datasets.get(&year),
+            None => self.datasets.values().next_back(),
+        }
+        .ok_or(PopulationError::UnknownYear(year))
+    }
+}
+
+/// 人口10万人あたりに換算した、ある日付の値です。
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Rate {
+    /// 日付です。
+    pub date: NaiveDate,
+    /// 換算前の値です。
+    pub value: u32,
+    /// 人口10万人あたりの値です。
+    pub rate: f64,
+}
+
+/// 小計の一覧を、人口10万人あたりに換算した系列です。
+#[derive(Clone, Debug, PartialEq)]
+pub struct RateSeries {
+    /// 換算した小計の一覧の最終更新日時です。
+    pub last_update: DateTime<Local>,
+    /// 換算に使用したデータセットの調査年です。
+    pub year: u16,
+    /// 換算に使用した地域名です。
+    pub region: String,
+    /// 換算に使用した年代です。`None`は総人口を表します。
+    pub age_band: Option<AgeBand>,
+    /// 換算した値を、小計の一覧と同じ順に格納します。
+    pub data: Vec<Rate>,
+}
+
+/// Rateのシリアライズ処理の実装です。
+impl Serialize for Rate {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("Rate", 3)?;
+        // 日付は、小計と同じ書式で出力する
+        state.serialize_field("date", &DATE.format(&self.date))?;
+        state.serialize_field("value", &self.value)?;
+        state.serialize_field("rate", &self.rate)?;
+        state.end()
+    }
+}
+
+/// RateSeriesのシリアライズ処理の実装です。
+impl Serialize for RateSeries {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("RateSeries", 5)?;
+        state.serialize_field("data", &self.data)?;
+        state.serialize_field("year", &self.year)?;
+        state.serialize_field("region", &self.region)?;
+        state.serialize_field("age_band", &self.age_band)?;
+        state.serialize_field("last_update", &DATETIME.format(&self.last_update))?;
+        state.end()
+    }
+}
+
+impl Summary {
+    /// 小計の一覧の各値を、地域(と年代)の人口10万人あたりの値に換算します。
+    pub fn per_100k(
+        &self,
+        dataset: &PopulationDataset,
+        region: &str,
+        age_band: Option<AgeBand>,
+    ) -> Result<RateSeries, PopulationError> {
+        // 人口が存在しない場合に、空の系列を返却しないよう、先に換算の可否を確認する
+        dataset.per_100k(region, age_band, 0.0)?;
+        let data = self
+            .data
+            .iter()
+            .map(|content| {
+                Ok(Rate {
+                    date: content.date,
+                    value: content.sum,
+                    rate: dataset.per_100k(region, age_band, content.sum as f64)?,
+                })
+            })
+            .collect::<Result<Vec<Rate>, PopulationError>>()?;
+
+        Ok(RateSeries {
+            last_update: self.last_update,
+            year: dataset.year,
+            region: region.to_string(),
+            age_band,
+            data,
+        })
+    }
+}
+
+impl Status {
+    /// 指定された属性の値を、地域の人口10万人あたりの値に換算します。属性が存在しない場合は`None`を返却します。
+    pub fn per_100k(
+        &self,
+        attribute: &Attributes,
+        dataset: &PopulationDataset,
+        region: &str,
+    ) -> Result<Option<f64>, PopulationError> {
+        self.find(attribute)
+            .map(|status| dataset.per_100k(region, None, status.value as f64))
+            .transpose()
+    }
+}
@@ -0,0 +1,120 @@
+//! 人口や陽性者を区分する、年代を定義しています。
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::{
+    error::Error as StdError,
+    fmt::{Display, Error, Formatter},
+    str::FromStr,
+};
+
+/// 年代です。対策サイトの表記に合わせ、10歳未満・10歳ごと・90歳以上に区分します。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AgeBand {
+    /// 10歳未満
+    Under10,
+    /// 10代
+    Teens,
+    /// 20代
+    Twenties,
+    /// 30代
+    Thirties,
+    /// 40代
+    Forties,
+    /// 50代
+    Fifties,
+    /// 60代
+    Sixties,
+    /// 70代
+    Seventies,
+    /// 80代
+    Eighties,
+    /// 90歳以上
+    Over90,
+}
+
+impl AgeBand {
+    /// 全ての年代を、若い順に格納しています。
+    pub const ALL: [AgeBand; 10] = [
+        AgeBand::Under10,
+        AgeBand::Teens,
+        AgeBand::Twenties,
+        AgeBand::Thirties,
+        AgeBand::Forties,
+        AgeBand::Fifties,
+        AgeBand::Sixties,
+        AgeBand::Seventies,
+        AgeBand::Eighties,
+        AgeBand::Over90,
+    ];
+
+    /// 年齢が属する年代を返却します。
+    pub fn of(age: u32) -> AgeBand {
+        AgeBand::ALL[(age / 10).min(9) as usize]
+    }
+
+    /// 対策サイトで使用する、年代の表記を返却します。
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AgeBand::Under10 => "10歳未満",
+            AgeBand::Teens => "10代",
+            AgeBand::Twenties => "20代",
+            AgeBand::Thirties => "30代",
+            AgeBand::Forties => "40代",
+            AgeBand::Fifties => "50代",
+            AgeBand::Sixties => "60代",
+            AgeBand::Seventies => "70代",
+            AgeBand::Eighties => "80代",
+            AgeBand::Over90 => "90歳以上",
+        }
+    }
+}
+
+impl Display for AgeBand {
+    fn fmt(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        write!(formatter, "{}", self.as_str())
+    }
+}
+
+/// 年代として解釈できない文字列です。
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnknownAgeBand(pub String);
+
+impl Display for UnknownAgeBand {
+    fn fmt(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        write!(formatter, "unknown age band `{}`", self.0)
+    }
+}
+
+impl StdError for UnknownAgeBand {}
+
+impl FromStr for AgeBand {
+    type Err = UnknownAgeBand;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        AgeBand::ALL
+            .into_iter()
+            .find(|band| band.as_str() == value)
+            .ok_or_else(|| UnknownAgeBand(value.to_string()))
+    }
+}
+
+/// AgeBandのシリアライズ処理の実装です。
+impl Serialize for AgeBand {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+/// AgeBandのデシリアライズ処理の実装です。
+impl<'de> Deserialize<'de> for AgeBand {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+        value.parse().map_err(de::Error::custom)
+    }
+}
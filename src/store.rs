@@ -0,0 +1,442 @@
+//! 小計・COVID-19に関する情報・最新のお知らせのスナップショットを、SQLiteのデータベースに保存する機能を提供します。
+//!
+//! 各スナップショットは最終更新日時ごとに1件として記録し、同じ最終更新日時のスナップショットを保存した場合は置き換えます。
+//! `store`フィーチャーを有効にした場合のみ使用できます。SQLiteはクレートに同梱されたものを使用します。
+
+use crate::{
+    document::DocumentType,
+    structs::{
+        last_update::LastUpdate,
+        news::{link::UrlPolicy, NewsItem, NewsItems},
+        status::Status,
+        summary::{Summary, SummaryContent},
+    },
+    utils::formats::{codec::DateInput, jst_midnight},
+};
+use chrono::{DateTime, Local, NaiveDate, TimeZone};
+use rusqlite::{params, Connection, OptionalExtension};
+use std::{
+    error::Error as StdError,
+    fmt::{Display, Error, Formatter},
+    path::Path,
+};
+
+pub mod schema;
+pub mod status;
+
+/// データベースに記録する日付の書式です。文字列として比較した順序が、日付の順序と一致します。
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+/// データベースの操作に失敗した際のエラーです。
+#[derive(Debug)]
+pub enum StoreError {
+    /// SQLiteの操作に失敗しました。
+    Sqlite(rusqlite::Error),
+    /// 本クレートが対応していない、テーブルの定義の版数です。
+    UnsupportedSchema(i64),
+    /// 最終更新日時を持たないStatusは、スナップショットとして保存できません。
+    MissingLastUpdate,
+    /// スナップショットとして保存しない種別です。
+    UnsupportedDocument(DocumentType),
+    /// 日付として解釈できない引数です。
+    InvalidDate(String),
+    /// データベースに記録された値が不正です。
+    InvalidValue {
+        /// 値が記録されていたテーブルです。
+        table: &'static str,
+        /// エラーの内容です。
+        message: String,
+    },
+}
+
+impl Display for StoreError {
+    fn fmt(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        match self {
+            StoreError::Sqlite(error) => write!(formatter, "{}", error),
+            StoreError::UnsupportedSchema(version) => write!(
+                formatter,
+                "unsupported schema version {} (expected {})",
+                version,
+                schema::SCHEMA_VERSION
+            ),
+            StoreError::MissingLastUpdate => {
+                write!(formatter, "status without last_update cannot be stored")
+            }
+            StoreError::UnsupportedDocument(document_type) => {
+                write!(formatter, "snapshots of `{}` are not stored", document_type)
+            }
+            StoreError::InvalidDate(message) => write!(formatter, "{}", message),
+            StoreError::InvalidValue { table, message } => {
+                write!(formatter, "invalid value in `{}`: {}", table, message)
+            }
+        }
+    }
+}
+
+impl StdError for StoreError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            StoreError::Sqlite(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<rusqlite::Error> for StoreError {
+    fn from(error: rusqlite::Error) -> Self {
+        StoreError::Sqlite(error)
+    }
+}
+
+/// スナップショットを保存するデータベースです。
+#[derive(Debug)]
+pub struct Store {
+    connection: Connection,
+}
+
+impl Store {
+    /// 指定されたパスのデータベースを開きます。存在しない場合は作成します。
+    pub fn open(path: impl AsRef<Path>) -> Result<Store, StoreError> {
+        Store::with_connection(Connection::open(path)?)
+    }
+
+    /// メモリ上に、一時的なデータベースを作成します。
+    pub fn open_in_memory() -> Result<Store, StoreError> {
+        Store::with_connection(Connection::open_in_memory()?)
+    }
+
+    /// 接続済みのデータベースを使用します。テーブルが存在しない場合は作成します。
+    pub fn with_connection(connection: Connection) -> Result<Store, StoreError> {
+        schema::initialize(&connection)?;
+        Ok(Store { connection })
+    }
+
+    /// 小計の一覧を、最終更新日時のスナップショットとして保存します。
+    pub fn save_summary(&mut self, summary: &Summary) -> Result<(), StoreError> {
+        let transaction = self.connection.transaction()?;
+        let summary_id = replace_snapshot(&transaction, "summaries", &summary.last_update)?;
+        let mut statement = transaction.prepare(
+            "INSERT INTO summary_contents (summary_id, position, date, sum) VALUES (?1, ?2, ?3, ?4)",
+        )?;
+        for (position, content) in summary.data.iter().enumerate() {
+            statement.execute(params![
+                summary_id,
+                position as i64,
+                content.date.format(DATE_FORMAT).to_string(),
+                content.sum
+            ])?;
+        }
+        drop(statement);
+        transaction.commit()?;
+        Ok(())
+    }
+
+    /// COVID-19に関する情報を、最終更新日時のスナップショットとして保存します。
+    ///
+    /// 最終更新日時(`last_update`)を持たない場合は、エラーを返却します。
+    pub fn save_status(&mut self, status: &Status) -> Result<(), StoreError> {
+        let last_update = status.last_update.ok_or(StoreError::MissingLastUpdate)?;
+        let transaction = self.connection.transaction()?;
+        let status_id = replace_snapshot(&transaction, "statuses", &last_update)?;
+        status::insert(&transaction, status_id, None, 0, status)?;
+        transaction.commit()?;
+        Ok(())
+    }
+
+    /// 最新のお知らせの一覧を、指定された最終更新日時のスナップショットとして保存します。
+    ///
+    /// お知らせの一覧は最終更新日時を持たないため、同時に取得した`last_update.json`の値を指定します。
+    pub fn save_news_items(
+        &mut self,
+        news_items: &NewsItems,
+        last_update: &LastUpdate<Local>,
+    ) -> Result<(), StoreError> {
+        let transaction = self.connection.transaction()?;
+        let news_id = replace_snapshot(&transaction, "news", &last_update.datetime)?;
+        let mut statement = transaction.prepare(
+            "INSERT INTO news_items (news_id, position, date, text, url) VALUES (?1, ?2, ?3, ?4, ?5)",
+        )?;
+        for (position, item) in news_items.news_items.iter().enumerate() {
+            statement.execute(params![
+                news_id,
+                position as i64,
+                item.date.format(DATE_FORMAT).to_string(),
+                item.text,
+                item.url.as_str()
+            ])?;
+        }
+        drop(statement);
+        transaction.commit()?;
+        Ok(())
+    }
+
+    /// 指定された種別の、保存されているスナップショットの最終更新日時を、古い順に返却します。
+    ///
+    /// `DocumentType::LastUpdate`を指定した場合は、全ての種別の最終更新日時を重複を除いて返却します。
+    /// スナップショットとして保存しない種別を指定した場合は、エラーを返却します。
+    pub fn snapshots(
+        &self,
+        document_type: DocumentType,
+    ) -> Result<Vec<DateTime<Local>>, StoreError> {
+        let (table, sql) = match document_type {
+            // 最終更新日時は個別のテーブルを持たないため、全てのスナップショットのテーブルから求める
+            DocumentType::LastUpdate => (
+                "summaries, statuses, news",
+                "SELECT last_update FROM summaries \
+                 UNION SELECT last_update FROM statuses \
+                 UNION SELECT last_update FROM news ORDER BY last_update"
+                    .to_string(),
+            ),
+            _ => {
+                let table = snapshot_table(document_type)
+                    .ok_or(StoreError::UnsupportedDocument(document_type))?;
+                (
+                    table,
+                    format!("SELECT last_update FROM {} ORDER BY last_update", table),
+                )
+            }
+        };
+        let mut statement = self.connection.prepare(&sql)?;
+        let timestamps = statement
+            .query_map([], |row| row.get::<_, i64>(0))?
+            .collect::<Result<Vec<i64>, rusqlite::Error>>()?;
+        timestamps
+            .into_iter()
+            .map(|timestamp| from_timestamp(table, timestamp))
+            .collect()
+    }
+
+    /// 指定された最終更新日時の、小計の一覧を読み込みます。
+    pub fn load_summary(
+        &self,
+        last_update: impl DateInput<DateTime<Local>>,
+    ) -> Result<Option<Summary>, StoreError> {
+        let last_update = last_update.into_date().map_err(StoreError::InvalidDate)?;
+        let id = self.snapshot_id("summaries", "last_update = ?1", to_timestamp(&last_update))?;
+        id.map(|id| self.select_summary(id, None)).transpose()
+    }
+
+    /// 指定された日付(日本標準時)の終わりの時点で、最新であった小計の一覧を読み込みます。
+    pub fn summary_as_of(
+        &self,
+        date: impl DateInput<NaiveDate>,
+    ) -> Result<Option<Summary>, StoreError> {
+        let id = self.snapshot_id("summaries", "last_update < ?1", end_of(date)?)?;
+        id.map(|id| self.select_summary(id, None)).transpose()
+    }
+
+    /// 最新の小計の一覧から、指定された期間(両端を含む)の小計を読み込みます。
+    pub fn summary_between(
+        &self,
+        from: impl DateInput<NaiveDate>,
+        to: impl DateInput<NaiveDate>,
+    ) -> Result<Option<Summary>, StoreError> {
+        let from = from.into_date().map_err(StoreError::InvalidDate)?;
+        let to = to.into_date().map_err(StoreError::InvalidDate)?;
+        let id = self.snapshot_id("summaries", "last_update <= ?1", i64::MAX)?;
+        id.map(|id| self.select_summary(id, Some((from, to))))
+            .transpose()
+    }
+
+    /// 指定された最終更新日時の、COVID-19に関する情報を読み込みます。
+    pub fn load_status(
+        &self,
+        last_update: impl DateInput<DateTime<Local>>,
+    ) -> Result<Option<Status>, StoreError> {
+        let last_update = last_update.into_date().map_err(StoreError::InvalidDate)?;
+        let id = self.snapshot_id("statuses", "last_update = ?1", to_timestamp(&last_update))?;
+        id.map(|id| status::select(&self.connection, id))
+            .transpose()
+    }
+
+    /// 指定された日付(日本標準時)の終わりの時点で、最新であったCOVID-19に関する情報を読み込みます。
+    pub fn status_as_of(
+        &self,
+        date: impl DateInput<NaiveDate>,
+    ) -> Result<Option<Status>, StoreError> {
+        let id = self.snapshot_id("statuses", "last_update < ?1", end_of(date)?)?;
+        id.map(|id| status::select(&self.connection, id))
+            .transpose()
+    }
+
+    /// 指定された最終更新日時の、最新のお知らせの一覧を読み込みます。
+    pub fn load_news_items(
+        &self,
+        last_update: impl DateInput<DateTime<Local>>,
+    ) -> Result<Option<NewsItems>, StoreError> {
+        let last_update = last_update.into_date().map_err(StoreError::InvalidDate)?;
+        let id = self.snapshot_id("news", "last_update = ?1", to_timestamp(&last_update))?;
+        id.map(|id| self.select_news_items(id)).transpose()
+    }
+
+    /// 指定された日付(日本標準時)の終わりの時点で、最新であったお知らせの一覧を読み込みます。
+    pub fn news_items_as_of(
+        &self,
+        date: impl DateInput<NaiveDate>,
+    ) -> Result<Option<NewsItems>, StoreError> {
+        let id = self.snapshot_id("news", "last_update < ?1", end_of(date)?)?;
+        id.map(|id| self.select_news_items(id)).transpose()
+    }
+
+    /// 条件に一致するスナップショットのうち、最終更新日時が最も新しいものの識別子を返却します。
+    fn snapshot_id(
+        &self,
+        table: &'static str,
+        condition: &str,
+        parameter: i64,
+    ) -> Result<Option<i64>, StoreError> {
+        let sql = format!(
+            "SELECT id FROM {} WHERE {} ORDER BY last_update DESC LIMIT 1",
+            table, condition
+        );
+        Ok(self
+            .connection
+            .query_row(&sql, [parameter], |row| row.get(0))
+            .optional()?)
+    }
+
+    /// 小計の一覧を読み込みます。期間を指定した場合は、期間内の小計のみを読み込みます。
+    fn select_summary(
+        &self,
+        summary_id: i64,
+        period: Option<(NaiveDate, NaiveDate)>,
+    ) -> Result<Summary, StoreError> {
+        let timestamp: i64 = self.connection.query_row(
+            "SELECT last_update FROM summaries WHERE id = ?1",
+            [summary_id],
+            |row| row.get(0),
+        )?;
+        let (from, to) = match period {
+            Some((from, to)) => (
+                Some(from.format(DATE_FORMAT).to_string()),
+                Some(to.format(DATE_FORMAT).to_string()),
+            ),
+            None => (None, None),
+        };
+
+        let mut statement = self.connection.prepare(
+            "SELECT date, sum FROM summary_contents WHERE summary_id = ?1 \
+             AND (?2 IS NULL OR date >= ?2) AND (?3 IS NULL OR date <= ?3) ORDER BY position",
+        )?;
+        let rows = statement
+            .query_map(params![summary_id, from, to], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+            })?
+            .collect::<Result<Vec<(String, i64)>, rusqlite::Error>>()?;
+        let data = rows
+            .into_iter()
+            .map(|(date, sum)| {
+                Ok(SummaryContent {
+                    date: parse_date("summary_contents", &date)?,
+                    sum: u32::try_from(sum).map_err(|_| StoreError::InvalidValue {
+                        table: "summary_contents",
+                        message: format!("sum {} is out of range", sum),
+                    })?,
+                })
+            })
+            .collect::<Result<Vec<SummaryContent>, StoreError>>()?;
+
+        Ok(Summary {
+            data,
+            last_update: from_timestamp("summaries", timestamp)?,
+        })
+    }
+
+    /// 最新のお知らせの一覧を読み込みます。
+    fn select_news_items(&self, news_id: i64) -> Result<NewsItems, StoreError> {
+        let mut statement = self.connection.prepare(
+            "SELECT date, text, url FROM news_items WHERE news_id = ?1 ORDER BY position",
+        )?;
+        let rows = statement
+            .query_map([news_id], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                ))
+            })?
+            .collect::<Result<Vec<(String, String, String)>, rusqlite::Error>>()?;
+        let news_items = rows
+            .into_iter()
+            .map(|(date, text, url)| {
+                Ok(NewsItem {
+                    date: parse_date("news_items", &date)?,
+                    text,
+                    // 保存時に検証済みのURLであるため、許可リスト等の制限は適用しない
+                    url: UrlPolicy::LENIENT.parse(&url).map_err(|error| {
+                        StoreError::InvalidValue {
+                            table: "news_items",
+                            message: error.to_string(),
+                        }
+                    })?,
+                })
+            })
+            .collect::<Result<Vec<NewsItem>, StoreError>>()?;
+        Ok(NewsItems { news_items })
+    }
+}
+
+/// 同じ最終更新日時のスナップショットを削除した上で、新たなスナップショットを作成し、その識別子を返却します。
+fn replace_snapshot(
+    connection: &Connection,
+    table: &'static str,
+    last_update: &DateTime<Local>,
+) -> Result<i64, StoreError> {
+    let timestamp = to_timestamp(last_update);
+    connection.execute(
+        &format!("DELETE FROM {} WHERE last_update = ?1", table),
+        [timestamp],
+    )?;
+    connection.execute(
+        &format!("INSERT INTO {} (last_update) VALUES (?1)", table),
+        [timestamp],
+    )?;
+    Ok(connection.last_insert_rowid())
+}
+
+/// スナップショットの種別に対応するテーブル名を返却します。
+///
+/// 保存しない種別と、個別のテーブルを持たない最終更新日時の場合は`None`を返却します。
+fn snapshot_table(document_type: DocumentType) -> Option<&'static str> {
+    match document_type {
+        DocumentType::Summary => Some("summaries"),
+        DocumentType::Status => Some("statuses"),
+        DocumentType::News => Some("news"),
+        DocumentType::LastUpdate | DocumentType::AgeDistribution | DocumentType::Vaccination => {
+            None
+        }
+    }
+}
+
+/// 日時を、データベースに記録するUNIXエポックからの秒数に変換します。秒未満は切り捨てます。
+fn to_timestamp<Tz: TimeZone>(datetime: &DateTime<Tz>) -> i64 {
+    datetime.timestamp()
+}
+
+/// データベースに記録されたUNIXエポックからの秒数を、日時に変換します。
+fn from_timestamp(table: &'static str, timestamp: i64) -> Result<DateTime<Local>, StoreError> {
+    Local
+        .timestamp_opt(timestamp, 0)
+        .single()
+        .ok_or_else(|| StoreError::InvalidValue {
+            table,
+            message: format!("timestamp {} is out of range", timestamp),
+        })
+}
+
+/// データベースに記録された日付を読み込みます。
+fn parse_date(table: &'static str, value: &str) -> Result<NaiveDate, StoreError> {
+    NaiveDate::parse_from_str(value, DATE_FORMAT).map_err(|error| StoreError::InvalidValue {
+        table,
+        message: format!("invalid date `{}`: {}", value, error),
+    })
+}
+
+/// 指定された日付の翌日の、日本標準時の0時0分を、UNIXエポックからの秒数として返却します。
+fn end_of(date: impl DateInput<NaiveDate>) -> Result<i64, StoreError> {
+    let date = date.into_date().map_err(StoreError::InvalidDate)?;
+    Ok(date
+        .succ_opt()
+        .map(|next| jst_midnight(next).timestamp())
+        .unwrap_or(i64::MAX))
+}
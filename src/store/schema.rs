@@ -0,0 +1,69 @@
+//! データベースのテーブルの定義です。
+
+use super::StoreError;
+use rusqlite::Connection;
+
+/// テーブルの定義の版数です。`PRAGMA user_version`に記録します。
+pub const SCHEMA_VERSION: i64 = 1;
+
+/// テーブルを作成するSQL文です。
+///
+/// 日時は、UNIXエポックからの秒数として記録します。日付は、`2020-03-25`の形式の文字列として記録します。
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS summaries (
+    id          INTEGER PRIMARY KEY,
+    last_update INTEGER NOT NULL UNIQUE
+);
+CREATE TABLE IF NOT EXISTS summary_contents (
+    summary_id INTEGER NOT NULL REFERENCES summaries (id) ON DELETE CASCADE,
+    position   INTEGER NOT NULL,
+    date       TEXT    NOT NULL,
+    sum        INTEGER NOT NULL,
+    PRIMARY KEY (summary_id, position)
+);
+CREATE INDEX IF NOT EXISTS summary_contents_date ON summary_contents (summary_id, date);
+
+CREATE TABLE IF NOT EXISTS statuses (
+    id          INTEGER PRIMARY KEY,
+    last_update INTEGER NOT NULL UNIQUE
+);
+CREATE TABLE IF NOT EXISTS status_nodes (
+    id           INTEGER PRIMARY KEY,
+    status_id    INTEGER NOT NULL REFERENCES statuses (id) ON DELETE CASCADE,
+    parent_id    INTEGER REFERENCES status_nodes (id) ON DELETE CASCADE,
+    position     INTEGER NOT NULL,
+    attr         TEXT    NOT NULL,
+    value        INTEGER NOT NULL,
+    has_children INTEGER NOT NULL,
+    last_update  INTEGER
+);
+CREATE INDEX IF NOT EXISTS status_nodes_status ON status_nodes (status_id, parent_id, position);
+
+CREATE TABLE IF NOT EXISTS news (
+    id          INTEGER PRIMARY KEY,
+    last_update INTEGER NOT NULL UNIQUE
+);
+CREATE TABLE IF NOT EXISTS news_items (
+    news_id  INTEGER NOT NULL REFERENCES news (id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    date     TEXT    NOT NULL,
+    text     TEXT    NOT NULL,
+    url      TEXT    NOT NULL,
+    PRIMARY KEY (news_id, position)
+);
+";
+
+/// テーブルを作成し、テーブルの定義の版数が一致するか確認します。
+pub fn initialize(connection: &Connection) -> Result<(), StoreError> {
+    connection.pragma_update(None, "foreign_keys", true)?;
+    let version: i64 = connection.pragma_query_value(None, "user_version", |row| row.get(0))?;
+    match version {
+        0 => {
+            connection.execute_batch(SCHEMA)?;
+            connection.pragma_update(None, "user_version", SCHEMA_VERSION)?;
+            Ok(())
+        }
+        SCHEMA_VERSION => Ok(()),
+        version => Err(StoreError::UnsupportedSchema(version)),
+    }
+}
@@ -0,0 +1,120 @@
+//! COVID-19に関する情報(`Status`)の階層を、データベースの行として読み書きします。
+//!
+//! 各属性を1行として記録し、親の属性の識別子(`parent_id`)と兄弟の中での順序(`position`)により階層を表現します。
+
+use super::{from_timestamp, to_timestamp, StoreError};
+use crate::structs::status::{Attributes, Status};
+use rusqlite::{params, Connection};
+use std::{collections::HashMap, str::FromStr};
+
+/// 属性を記録するテーブル名です。
+const TABLE: &str = "status_nodes";
+
+/// データベースに記録された属性の1行です。
+struct Node {
+    id: i64,
+    attr: String,
+    value: i64,
+    has_children: bool,
+    last_update: Option<i64>,
+}
+
+/// 属性と、その子属性を再帰的に記録します。
+pub fn insert(
+    connection: &Connection,
+    status_id: i64,
+    parent_id: Option<i64>,
+    position: usize,
+    status: &Status,
+) -> Result<(), StoreError> {
+    connection.execute(
+        "INSERT INTO status_nodes \
+         (status_id, parent_id, position, attr, value, has_children, last_update) \
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![
+            status_id,
+            parent_id,
+            position as i64,
+            status.attr.as_str(),
+            status.value,
+            status.children.is_some(),
+            status.last_update.as_ref().map(to_timestamp)
+        ],
+    )?;
+    let id = connection.last_insert_rowid();
+    for (position, child) in status.children.iter().flatten().enumerate() {
+        insert(connection, status_id, Some(id), position, child)?;
+    }
+    Ok(())
+}
+
+/// スナップショットに記録された属性を読み込み、階層を復元します。
+pub fn select(connection: &Connection, status_id: i64) -> Result<Status, StoreError> {
+    let mut statement = connection.prepare(
+        "SELECT id, parent_id, attr, value, has_children, last_update FROM status_nodes \
+         WHERE status_id = ?1 ORDER BY parent_id, position",
+    )?;
+    let rows = statement
+        .query_map([status_id], |row| {
+            Ok((
+                row.get::<_, Option<i64>>(1)?,
+                Node {
+                    id: row.get(0)?,
+                    attr: row.get(2)?,
+                    value: row.get(3)?,
+                    has_children: row.get(4)?,
+                    last_update: row.get(5)?,
+                },
+            ))
+        })?
+        .collect::<Result<Vec<(Option<i64>, Node)>, rusqlite::Error>>()?;
+
+    // 親の属性ごとに、子属性を順序どおりに分類する
+    let mut children = HashMap::<Option<i64>, Vec<Node>>::new();
+    for (parent_id, node) in rows {
+        children.entry(parent_id).or_default().push(node);
+    }
+    let root = children
+        .remove(&None)
+        .and_then(|mut roots| roots.pop())
+        .ok_or_else(|| StoreError::InvalidValue {
+            table: TABLE,
+            message: format!("status {} has no root attribute", status_id),
+        })?;
+    build(root, &mut children)
+}
+
+/// 属性の行から、子属性を含むStatusを組み立てます。
+fn build(node: Node, children: &mut HashMap<Option<i64>, Vec<Node>>) -> Result<Status, StoreError> {
+    let attr = Attributes::from_str(&node.attr).map_err(|error| StoreError::InvalidValue {
+        table: TABLE,
+        message: error.to_string(),
+    })?;
+    let value = u32::try_from(node.value).map_err(|_| StoreError::InvalidValue {
+        table: TABLE,
+        message: format!("value {} is out of range", node.value),
+    })?;
+    let last_update = node
+        .last_update
+        .map(|timestamp| from_timestamp(TABLE, timestamp))
+        .transpose()?;
+
+    let nodes = children.remove(&Some(node.id)).unwrap_or_default();
+    let children = if node.has_children {
+        Some(
+            nodes
+                .into_iter()
+                .map(|child| build(child, children))
+                .collect::<Result<Vec<Status>, StoreError>>()?,
+        )
+    } else {
+        None
+    };
+
+    Ok(Status {
+        attr,
+        value,
+        children,
+        last_update,
+    })
+}
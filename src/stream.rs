@@ -0,0 +1,121 @@
+//! 巨大なデータファイルを、全体をメモリに読み込まずに処理するための機能を提供します。
+
+use serde::de::{
+    Deserialize, DeserializeSeed, Deserializer, Error as DeserializationError, SeqAccess, Visitor,
+};
+use std::{
+    error::Error as StdError,
+    fmt::{self, Display, Formatter},
+    marker::PhantomData,
+};
+
+pub mod patients;
+pub mod summary;
+
+/// 逐次的な読み込みに失敗した際のエラーです。
+#[derive(Debug)]
+pub enum StreamError<E> {
+    /// JSON形式の読み込みに失敗しました。
+    Json(serde_json::Error),
+    /// 要素ごとに呼び出した関数が、エラーを返却しました。
+    Callback(E),
+}
+
+impl<E: Display> Display for StreamError<E> {
+    fn fmt(&self, formatter: &mut Formatter) -> Result<(), fmt::Error> {
+        match self {
+            StreamError::Json(error) => write!(formatter, "{}", error),
+            StreamError::Callback(error) => write!(formatter, "{}", error),
+        }
+    }
+}
+
+impl<E: StdError + 'static> StdError for StreamError<E> {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            StreamError::Json(error) => Some(error),
+            StreamError::Callback(error) => Some(error),
+        }
+    }
+}
+
+impl<E> From<serde_json::Error> for StreamError<E> {
+    fn from(error: serde_json::Error) -> Self {
+        StreamError::Json(error)
+    }
+}
+
+/// 読み込みの結果と、関数が返却したエラーから、逐次的な読み込みの結果を求めます。
+///
+/// 関数がエラーを返却した場合は読み込みを中断しているため、読み込みのエラーよりも優先します。
+fn finish<T, E>(
+    result: Result<T, serde_json::Error>,
+    callback: Option<E>,
+) -> Result<T, StreamError<E>> {
+    match callback {
+        Some(error) => Err(StreamError::Callback(error)),
+        None => result.map_err(StreamError::Json),
+    }
+}
+
+/// 配列を読み込み、要素ごとに関数を呼び出すDeserializeSeedです。
+///
+/// 関数がエラーを返却した場合は、そのエラーを`error`に格納し、以降の要素を読み込まずに中断します。
+///
+/// ※この構造体は、Visitorトレイトを実装することを意図しています。
+struct ElementsSeed<'f, T, F, E> {
+    f: &'f mut F,
+    error: &'f mut Option<E>,
+    element: PhantomData<T>,
+}
+
+impl<'f, T, F, E> ElementsSeed<'f, T, F, E> {
+    fn new(f: &'f mut F, error: &'f mut Option<E>) -> Self {
+        ElementsSeed {
+            f,
+            error,
+            element: PhantomData,
+        }
+    }
+}
+
+impl<'de, T, F, E> DeserializeSeed<'de> for ElementsSeed<'_, T, F, E>
+where
+    T: Deserialize<'de>,
+    F: FnMut(T) -> Result<(), E>,
+{
+    type Value = ();
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, T, F, E> Visitor<'de> for ElementsSeed<'_, T, F, E>
+where
+    T: Deserialize<'de>,
+    F: FnMut(T) -> Result<(), E>,
+{
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut Formatter) -> Result<(), fmt::Error> {
+        write!(formatter, "an array")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        // 要素を1件読み込むごとに関数へ渡し、保持しない
+        while let Some(element) = seq.next_element::<T>()? {
+            if let Err(error) = (self.f)(element) {
+                *self.error = Some(error);
+                return Err(DeserializationError::custom("aborted by the callback"));
+            }
+        }
+        Ok(())
+    }
+}
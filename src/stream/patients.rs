@@ -0,0 +1,39 @@
+//! 陽性者の個票(`PatientRecord`)の配列を、個票単位で逐次的に読み込みます。
+//!
+//! 全期間の陽性者の個票のような巨大な配列でも、個票の一覧(`Vec<PatientRecord>`)を生成しないため、
+//! 一定のメモリ使用量で年代別陽性者数等を集計できます。
+
+use super::{finish, ElementsSeed, StreamError};
+use crate::structs::age_distribution::PatientRecord;
+use serde::de::DeserializeSeed;
+use std::io::{BufReader, Read};
+
+/// 陽性者の個票の配列を、個票ごとに読み込みます。
+pub struct PatientReader<R: Read> {
+    reader: BufReader<R>,
+}
+
+impl<R: Read> PatientReader<R> {
+    /// 読み込み元を指定して生成します。
+    pub fn new(reader: R) -> Self {
+        PatientReader {
+            reader: BufReader::new(reader),
+        }
+    }
+
+    /// 個票を読み込むごとに、指定された関数を呼び出します。
+    ///
+    /// 関数がエラーを返却した場合は、以降の個票を読み込まずに、そのエラーを返却します。
+    pub fn for_each<F, E>(self, mut f: F) -> Result<(), StreamError<E>>
+    where
+        F: FnMut(PatientRecord) -> Result<(), E>,
+    {
+        let mut error = None;
+        let mut deserializer = serde_json::Deserializer::from_reader(self.reader);
+        let result = ElementsSeed::new(&mut f, &mut error)
+            .deserialize(&mut deserializer)
+            // 末尾に余分な内容が含まれていないか確認する
+            .and_then(|()| deserializer.end());
+        finish(result, error)
+    }
+}
@@ -0,0 +1,241 @@
+//! Summary構造体を、日毎の小計(`SummaryContent`)単位で逐次的に読み書きします。
+//!
+//! 複数年分の`patients_summary.json`のような巨大なファイルでも、
+//! 小計の一覧(`Vec<SummaryContent>`)を生成しないため、一定のメモリ使用量で処理できます。
+//! 版数(`version`)を含むデータファイルは、小計ごとに現在の版に変換した上で読み込みます。
+
+use super::{finish, ElementsSeed, StreamError};
+use crate::{
+    document::DocumentType,
+    migration::{self, MigrationError, CURRENT_VERSION, VERSION_FIELD},
+    structs::summary::{DateRepresentation, SummaryContent, SUMMARY_FIELDS},
+    utils::formats::codec::DATETIME,
+};
+use chrono::{DateTime, Local};
+use serde::de::{
+    Deserialize, DeserializeSeed, Deserializer, Error as DeserializationError, MapAccess, Visitor,
+};
+use serde_json::Value;
+use std::{
+    fmt::{Error, Formatter},
+    io::{BufReader, Read, Write},
+};
+
+/// Summary構造体を、日毎の小計ごとに読み込みます。
+pub struct SummaryReader<R: Read> {
+    reader: BufReader<R>,
+}
+
+impl<R: Read> SummaryReader<R> {
+    /// 読み込み元を指定して生成します。
+    pub fn new(reader: R) -> Self {
+        SummaryReader {
+            reader: BufReader::new(reader),
+        }
+    }
+
+    /// 日毎の小計を読み込むごとに、指定された関数を呼び出します。
+    ///
+    /// 全ての小計を読み込んだ後、最終更新日時を返却します。
+    /// 関数がエラーを返却した場合は、以降の小計を読み込まずに、そのエラーを返却します。
+    pub fn for_each<F, E>(self, mut f: F) -> Result<DateTime<Local>, StreamError<E>>
+    where
+        F: FnMut(SummaryContent) -> Result<(), E>,
+    {
+        let mut error = None;
+        let mut deserializer = serde_json::Deserializer::from_reader(self.reader);
+        let result = SummarySeed {
+            f: &mut f,
+            error: &mut error,
+        }
+        .deserialize(&mut deserializer)
+        // 末尾に余分な内容が含まれていないか確認する
+        .and_then(|last_update| deserializer.end().map(|()| last_update));
+        finish(result, error)
+    }
+}
+
+/// 版数を含むSummaryのフィールド名です。
+const VERSIONED_SUMMARY_FIELDS: &[&str; 3] = &["data", "last_update", VERSION_FIELD];
+
+/// 以前の版の小計を読み込む際の、小計ごとのエラーです。
+enum ElementError<E> {
+    /// 小計を現在の版に変換できません。
+    Migration(MigrationError),
+    /// 関数がエラーを返却しました。
+    Callback(E),
+}
+
+/// Summaryを読み込み、小計ごとに関数を呼び出すDeserializeSeedです。
+///
+/// ※この構造体は、Visitorトレイトを実装することを意図しています。
+struct SummarySeed<'f, F, E> {
+    f: &'f mut F,
+    error: &'f mut Option<E>,
+}
+
+impl<'de, F, E> DeserializeSeed<'de> for SummarySeed<'_, F, E>
+where
+    F: FnMut(SummaryContent) -> Result<(), E>,
+{
+    type Value = DateTime<Local>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct("Summary", SUMMARY_FIELDS, self)
+    }
+}
+
+impl<'de, F, E> Visitor<'de> for SummarySeed<'_, F, E>
+where
+    F: FnMut(SummaryContent) -> Result<(), E>,
+{
+    // 小計は関数に渡し、最終更新日時のみを返却する
+    type Value = DateTime<Local>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        write!(formatter, "format is not correct.")
+    }
+
+    fn visit_map<M>(self, mut map: M) -> Result<Self::Value, <M as MapAccess<'de>>::Error>
+    where
+        M: MapAccess<'de>,
+    {
+        let mut data = false;
+        let mut last_update = None;
+        let mut version = None;
+
+        // 連想配列のキーを取得する
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                // SummaryContentを1件ずつ取り出す
+                "data" => {
+                    // 既にdataを読み込んでいないか判定
+                    if data {
+                        return Err(DeserializationError::duplicate_field(SUMMARY_FIELDS[0]));
+                    }
+                    match version.filter(|version| *version < CURRENT_VERSION) {
+                        // 以前の版の小計は、1件ずつ現在の版に変換してから関数に渡す
+                        Some(version) => {
+                            let f = &mut *self.f;
+                            let mut migrate = |element: Value| {
+                                let content = migration::upgrade_element(
+                                    DocumentType::Summary,
+                                    version,
+                                    SUMMARY_FIELDS[0],
+                                    element,
+                                )
+                                .and_then(|value| {
+                                    SummaryContent::deserialize(value).map_err(MigrationError::Json)
+                                })
+                                .map_err(ElementError::Migration)?;
+                                f(content).map_err(ElementError::Callback)
+                            };
+                            let mut failure = None;
+                            let result =
+                                map.next_value_seed(ElementsSeed::new(&mut migrate, &mut failure));
+                            match failure {
+                                Some(ElementError::Migration(error)) => {
+                                    return Err(DeserializationError::custom(error))
+                                }
+                                Some(ElementError::Callback(error)) => *self.error = Some(error),
+                                None => (),
+                            }
+                            result?;
+                        }
+                        None => {
+                            map.next_value_seed(ElementsSeed::new(&mut *self.f, &mut *self.error))?
+                        }
+                    }
+                    data = true;
+                }
+                // 版数は、dataより後に含まれる場合も検証する
+                // (versionを持たないデータファイルと同様に、変換せずに読み込んだ小計は変換後の小計と一致する)
+                VERSION_FIELD => {
+                    if version.is_some() {
+                        return Err(DeserializationError::duplicate_field(VERSION_FIELD));
+                    }
+                    let value = map.next_value::<Value>()?;
+                    version = Some(
+                        migration::check_version(value).map_err(DeserializationError::custom)?,
+                    );
+                }
+                "last_update" => {
+                    // 既にlast_updateに内容が含まれていないか判定
+                    if last_update.is_some() {
+                        return Err(DeserializationError::duplicate_field(SUMMARY_FIELDS[1]));
+                    }
+                    // 日付と時刻をパースし、格納する
+                    last_update = Some(map.next_value_seed(DATETIME.seed())?);
+                }
+                key => {
+                    return Err(DeserializationError::unknown_field(
+                        key,
+                        VERSIONED_SUMMARY_FIELDS,
+                    ))
+                }
+            }
+        }
+
+        // dataが含まれていたか判定
+        if !data {
+            return Err(DeserializationError::missing_field(SUMMARY_FIELDS[0]));
+        }
+        // last_updateの中身を取り出す
+        last_update.ok_or_else(
+            // フィールドが不足していることを伝える
+            || DeserializationError::missing_field(SUMMARY_FIELDS[1]),
+        )
+    }
+}
+
+/// Summary構造体を、日毎の小計ごとに書き出します。
+///
+/// 書き出される内容は、`serde_json::to_writer`でSummary構造体を書き出した場合と一致します。
+pub struct SummaryWriter<W: Write> {
+    writer: W,
+    first: bool,
+    dates: DateRepresentation,
+}
+
+impl<W: Write> SummaryWriter<W> {
+    /// 書き出し先を指定して生成し、小計の一覧の書き出しを開始します。
+    pub fn new(writer: W) -> Result<Self, serde_json::Error> {
+        SummaryWriter::with_dates(writer, DateRepresentation::default())
+    }
+
+    /// 書き出し先と小計の日付の表現形式を指定して生成し、小計の一覧の書き出しを開始します。
+    pub fn with_dates(mut writer: W, dates: DateRepresentation) -> Result<Self, serde_json::Error> {
+        writer
+            .write_all(br#"{"data":["#)
+            .map_err(serde_json::Error::io)?;
+        Ok(SummaryWriter {
+            writer,
+            first: true,
+            dates,
+        })
+    }
+
+    /// 日毎の小計を1件書き出します。
+    pub fn write(&mut self, content: &SummaryContent) -> Result<(), serde_json::Error> {
+        // 2件目以降は区切り文字を挿入する
+        if !self.first {
+            self.writer.write_all(b",").map_err(serde_json::Error::io)?;
+        }
+        self.first = false;
+        serde_json::to_writer(&mut self.writer, &content.with_dates(self.dates))
+    }
+
+    /// 最終更新日時を書き出して終了し、書き出し先を返却します。
+    pub fn finish(mut self, last_update: &DateTime<Local>) -> Result<W, serde_json::Error> {
+        self.writer
+            .write_all(br#"],"last_update":"#)
+            .map_err(serde_json::Error::io)?;
+        serde_json::to_writer(&mut self.writer, &DATETIME.format(last_update))?;
+        self.writer.write_all(b"}").map_err(serde_json::Error::io)?;
+        self.writer.flush().map_err(serde_json::Error::io)?;
+        Ok(self.writer)
+    }
+}
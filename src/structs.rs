@@ -1,4 +1,8 @@
+pub mod age_distribution;
+#[cfg(any(test, feature = "proptest"))]
+pub mod arbitrary;
 pub mod last_update;
 pub mod news;
 pub mod status;
 pub mod summary;
+pub mod vaccination;
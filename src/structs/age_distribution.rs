@@ -0,0 +1,839 @@
+//! 年代別陽性者数を格納する構造体を定義しています。
+//!
+//! 陽性者の個票(`PatientRecord`)から、年代ごとの陽性者数と、日毎の内訳を集計できます。
+
+use chrono::{DateTime, Local, NaiveDate};
+use serde::{
+    de::{Error as DeserializationError, MapAccess, Visitor},
+    ser::SerializeStruct,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::{
+    collections::BTreeMap,
+    error::Error as StdError,
+    fmt::{Display, Error, Formatter},
+    str::FromStr,
+};
+
+use crate::{
+    population::{age_band::AgeBand, PopulationDataset, PopulationError},
+    utils::formats::codec::{DATE, DATETIME},
+};
+
+/// AgeDistributionをシリアライズする際のフィールド名です。
+pub(crate) const AGE_DISTRIBUTION_FIELDS: &[&str; 3] = &["data", "daily", "last_update"];
+/// AgeDistributionContentをシリアライズする際のフィールド名です。
+pub(crate) const AGE_DISTRIBUTION_CONTENT_FIELDS: &[&str; 2] = &["age_group", "count"];
+/// DailyAgeDistributionをシリアライズする際のフィールド名です。
+pub(crate) const DAILY_AGE_DISTRIBUTION_FIELDS: &[&str; 2] = &["date", "data"];
+/// PatientRecordをシリアライズする際のフィールド名です。
+pub(crate) const PATIENT_RECORD_FIELDS: &[&str; 2] = &["date", "age_group"];
+
+/// PatientRecord構造体のフィールド名です。
+enum PatientRecordField {
+    Date,
+    AgeGroup,
+}
+
+/// AgeDistributionContent構造体のフィールド名です。
+enum AgeDistributionContentField {
+    AgeGroup,
+    Count,
+}
+
+/// DailyAgeDistribution構造体のフィールド名です。
+enum DailyAgeDistributionField {
+    Date,
+    Data,
+}
+
+/// AgeDistribution構造体のフィールド名です。
+enum AgeDistributionField {
+    Data,
+    Daily,
+    LastUpdate,
+}
+
+/// 陽性者の年代の区分です。年代が公表されていない陽性者は、`Undisclosed`に区分します。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum AgeGroup {
+    /// 年代が公表されている陽性者です。
+    Band(AgeBand),
+    /// 年代が公表されていない陽性者です。
+    Undisclosed,
+}
+
+impl AgeGroup {
+    /// 全ての区分を、対策サイトで表示する順に格納しています。
+    pub const ALL: [AgeGroup; 11] = [
+        AgeGroup::Band(AgeBand::Under10),
+        AgeGroup::Band(AgeBand::Teens),
+        AgeGroup::Band(AgeBand::Twenties),
+        AgeGroup::Band(AgeBand::Thirties),
+        AgeGroup::Band(AgeBand::Forties),
+        AgeGroup::Band(AgeBand::Fifties),
+        AgeGroup::Band(AgeBand::Sixties),
+        AgeGroup::Band(AgeBand::Seventies),
+        AgeGroup::Band(AgeBand::Eighties),
+        AgeGroup::Band(AgeBand::Over90),
+        AgeGroup::Undisclosed,
+    ];
+
+    /// 年齢が属する区分を返却します。年齢が公表されていない場合は`Undisclosed`を返却します。
+    pub fn of(age: Option<u32>) -> AgeGroup {
+        age.map_or(AgeGroup::Undisclosed, |age| {
+            AgeGroup::Band(AgeBand::of(age))
+        })
+    }
+
+    /// 対策サイトで使用する、区分の表記を返却します。
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AgeGroup::Band(band) => band.as_str(),
+            AgeGroup::Undisclosed => "非公表",
+        }
+    }
+}
+
+impl Display for AgeGroup {
+    fn fmt(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        write!(formatter, "{}", self.as_str())
+    }
+}
+
+/// 年代の区分として解釈できない文字列です。
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct UnknownAgeGroup(pub String);
+
+impl Display for UnknownAgeGroup {
+    fn fmt(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        write!(formatter, "unknown age group `{}`", self.0)
+    }
+}
+
+impl StdError for UnknownAgeGroup {}
+
+impl FromStr for AgeGroup {
+    type Err = UnknownAgeGroup;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        AgeGroup::ALL
+            .into_iter()
+            .find(|group| group.as_str() == value)
+            .ok_or_else(|| UnknownAgeGroup(value.to_string()))
+    }
+}
+
+/// AgeGroupのシリアライズ処理の実装です。
+impl Serialize for AgeGroup {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+/// AgeGroupのデシリアライズ処理の実装です。
+impl<'de> Deserialize<'de> for AgeGroup {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+        value.parse().map_err(DeserializationError::custom)
+    }
+}
+
+/// 陽性者の個票のうち、年代別陽性者数の集計に使用する項目です。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PatientRecord {
+    /// 陽性が判明した日付です。日本標準時における日付として扱います。
+    pub date: NaiveDate,
+    /// 陽性者の年代の区分です。
+    pub age_group: AgeGroup,
+}
+
+/// 年代ごとの陽性者数です。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct AgeDistributionContent {
+    /// 年代の区分です。
+    pub age_group: AgeGroup,
+    /// 陽性者数です。
+    pub count: u32,
+}
+
+/// ある日付の、年代ごとの陽性者数です。
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DailyAgeDistribution {
+    /// 陽性が判明した日付です。日本標準時における日付として扱います。
+    pub date: NaiveDate,
+    /// 年代ごとの陽性者数を、`AgeGroup::ALL`の順に格納します。
+    pub data: Vec<AgeDistributionContent>,
+}
+
+/// 年代別陽性者数です。
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct AgeDistribution {
+    /// 年代ごとの陽性者数の累計を、`AgeGroup::ALL`の順に格納します。
+    pub data: Vec<AgeDistributionContent>,
+    /// 日毎の内訳を、日付の昇順に格納します。内訳を持たない場合は`None`です。
+    pub daily: Option<Vec<DailyAgeDistribution>>,
+    pub last_update: DateTime<Local>,
+}
+
+/// ある年代の、人口10万人あたりの陽性者数です。
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AgeIncidence {
+    /// 年代です。
+    pub age_band: AgeBand,
+    /// 陽性者数です。
+    pub count: u32,
+    /// 年代の人口です。
+    pub population: u64,
+    /// 人口10万人あたりの陽性者数です。
+    pub rate: f64,
+}
+
+/// 年代ごとの陽性者数を、全ての区分について`AgeGroup::ALL`の順に並べます。
+fn contents(counts: &BTreeMap<AgeGroup, u32>) -> Vec<AgeDistributionContent> {
+    AgeGroup::ALL
+        .into_iter()
+        .map(|age_group| AgeDistributionContent {
+            age_group,
+            count: counts.get(&age_group).copied().unwrap_or(0),
+        })
+        .collect()
+}
+
+impl AgeDistribution {
+    /// 陽性者の個票から、年代別陽性者数を集計します。
+    ///
+    /// `daily`に`true`を指定した場合は、個票が存在する日付ごとの内訳も集計します。
+    pub fn from_records<'a>(
+        records: impl IntoIterator<Item = &'a PatientRecord>,
+        last_update: DateTime<Local>,
+        daily: bool,
+    ) -> AgeDistribution {
+        let mut total = BTreeMap::new();
+        let mut by_date = BTreeMap::<NaiveDate, BTreeMap<AgeGroup, u32>>::new();
+        for record in records {
+            *total.entry(record.age_group).or_insert(0u32) += 1;
+            if daily {
+                *by_date
+                    .entry(record.date)
+                    .or_default()
+                    .entry(record.age_group)
+                    .or_insert(0) += 1;
+            }
+        }
+
+        AgeDistribution {
+            data: contents(&total),
+            daily: daily.then(|| {
+                by_date
+                    .iter()
+                    .map(|(date, counts)| DailyAgeDistribution {
+                        date: *date,
+                        data: contents(counts),
+                    })
+                    .collect()
+            }),
+            last_update,
+        }
+    }
+
+    /// 指定された区分の陽性者数を返却します。区分が含まれていない場合は0を返却します。
+    pub fn count(&self, age_group: AgeGroup) -> u32 {
+        self.data
+            .iter()
+            .find(|content| content.age_group == age_group)
+            .map_or(0, |content| content.count)
+    }
+
+    /// 全ての区分の陽性者数の合計を返却します。
+    pub fn total(&self) -> u64 {
+        self.data
+            .iter()
+            .map(|content| u64::from(content.count))
+            .sum()
+    }
+
+    /// 年代ごとの陽性者数を、地域の年代ごとの人口10万人あたりの値に換算します。
+    ///
+    /// 年代が公表されていない陽性者は、人口と対応しないため含めません。
+    /// データセットには、地域の全ての年代の人口が必要です。`Populations::bundled`のデータセットは総人口のみを含むため、
+    /// 年代ごとの人口を含むデータセットを`Populations::insert`で追加してから使用してください。
+    /// 年代の人口が存在しない場合は、`MissingAgeBand`のエラーを返却します。
+    pub fn incidence(
+        &self,
+        dataset: &PopulationDataset,
+        region: &str,
+    ) -> Result<Vec<AgeIncidence>, PopulationError> {
+        AgeBand::ALL
+            .into_iter()
+            .map(|age_band| {
+                let count = self.count(AgeGroup::Band(age_band));
+                Ok(AgeIncidence {
+                    age_band,
+                    count,
+                    population: dataset.population(region, Some(age_band))?,
+                    rate: dataset.per_100k(region, Some(age_band), count as f64)?,
+                })
+            })
+            .collect()
+    }
+}
+
+/// PatientRecordのシリアライズ処理の実装です。
+impl Serialize for PatientRecord {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("PatientRecord", 2)?;
+        state.serialize_field("date", &DATE.format(&self.date))?;
+        state.serialize_field("age_group", &self.age_group)?;
+        state.end()
+    }
+}
+
+/// AgeDistributionContentのシリアライズ処理の実装です。
+impl Serialize for AgeDistributionContent {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("AgeDistributionContent", 2)?;
+        state.serialize_field("age_group", &self.age_group)?;
+        state.serialize_field("count", &self.count)?;
+        state.end()
+    }
+}
+
+/// DailyAgeDistributionのシリアライズ処理の実装です。
+impl Serialize for DailyAgeDistribution {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("DailyAgeDistribution", 2)?;
+        // 日付は、小計と同じ書式で出力する
+        state.serialize_field("date", &DATE.format(&self.date))?;
+        state.serialize_field("data", &self.data)?;
+        state.end()
+    }
+}
+
+/// AgeDistributionのシリアライズ処理の実装です。
+impl Serialize for AgeDistribution {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        // 省略可能なフィールドは、値が存在する場合のみ数える
+        let len = 2 + self.daily.is_some() as usize;
+        let mut state = serializer.serialize_struct("AgeDistribution", len)?;
+        state.serialize_field("data", &self.data)?;
+        // 日毎の内訳を持たない場合は、フィールドを出力しない
+        if let Some(daily) = &self.daily {
+            state.serialize_field("daily", daily)?;
+        }
+        state.serialize_field("last_update", &DATETIME.format(&self.last_update))?;
+        state.end()
+    }
+}
+
+/// AgeIncidenceのシリアライズ処理の実装です。
+impl Serialize for AgeIncidence {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("AgeIncidence", 4)?;
+        state.serialize_field("age_band", &self.age_band)?;
+        state.serialize_field("count", &self.count)?;
+        state.serialize_field("population", &self.population)?;
+        state.serialize_field("rate", &self.rate)?;
+        state.end()
+    }
+}
+
+/// 年代ごとの陽性者数の一覧に、同じ区分が重複して含まれていないか検証します。
+fn check_age_groups<E>(data: &[AgeDistributionContent]) -> Result<(), E>
+where
+    E: DeserializationError,
+{
+    for (index, content) in data.iter().enumerate() {
+        if data[..index]
+            .iter()
+            .any(|previous| previous.age_group == content.age_group)
+        {
+            return Err(DeserializationError::custom(format!(
+                "duplicate age group `{}`",
+                content.age_group
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// PatientRecordFieldのVisitorを定義します。
+///
+/// ※この構造体は、Visitorトレイトを実装することを意図しています。
+struct PatientRecordFieldVisitor;
+
+impl<'de> Visitor<'de> for PatientRecordFieldVisitor {
+    type Value = PatientRecordField;
+
+    fn expecting(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        write!(formatter, "`date` or `age_group` not found")
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: DeserializationError,
+    {
+        match value {
+            "date" => Ok(PatientRecordField::Date),
+            "age_group" => Ok(PatientRecordField::AgeGroup),
+            _ => Err(DeserializationError::unknown_field(
+                value,
+                PATIENT_RECORD_FIELDS,
+            )),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for PatientRecordField {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as Deserializer<'de>>::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_identifier(PatientRecordFieldVisitor)
+    }
+}
+
+/// PatientRecordのVisitorを定義します。
+///
+/// ※この構造体は、Visitorトレイトを実装することを意図しています。
+struct PatientRecordVisitor;
+
+impl<'de> Visitor<'de> for PatientRecordVisitor {
+    type Value = PatientRecord;
+
+    fn expecting(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        write!(formatter, "format is not correct.")
+    }
+
+    fn visit_map<M>(self, mut map: M) -> Result<Self::Value, <M as MapAccess<'de>>::Error>
+    where
+        M: MapAccess<'de>,
+    {
+        let mut date = None;
+        let mut age_group = None;
+
+        // 連想配列のキーを取得する
+        while let Some(key) = map.next_key::<PatientRecordField>()? {
+            match key {
+                PatientRecordField::Date => {
+                    // 既にdateに内容が含まれていないか判定
+                    if date.is_some() {
+                        return Err(DeserializationError::duplicate_field(
+                            PATIENT_RECORD_FIELDS[0],
+                        ));
+                    }
+                    // 日付をパースし、格納する
+                    date = Some(map.next_value_seed(DATE.seed())?);
+                }
+                PatientRecordField::AgeGroup => {
+                    // 既にage_groupに内容が含まれていないか判定
+                    if age_group.is_some() {
+                        return Err(DeserializationError::duplicate_field(
+                            PATIENT_RECORD_FIELDS[1],
+                        ));
+                    }
+                    // 年代の区分をパースし、格納する
+                    age_group = Some(map.next_value::<AgeGroup>()?);
+                }
+            }
+        }
+
+        // dateの中身を取り出す
+        let date = date.ok_or_else(
+            // フィールドが不足していることを伝える
+            || DeserializationError::missing_field(PATIENT_RECORD_FIELDS[0]),
+        )?;
+        // age_groupの中身を取り出す
+        let age_group = age_group.ok_or_else(
+            // フィールドが不足していることを伝える
+            || DeserializationError::missing_field(PATIENT_RECORD_FIELDS[1]),
+        )?;
+
+        // PatientRecordを返却
+        Ok(PatientRecord { date, age_group })
+    }
+}
+
+impl<'de> Deserialize<'de> for PatientRecord {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as Deserializer<'de>>::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            "PatientRecord",
+            PATIENT_RECORD_FIELDS,
+            PatientRecordVisitor,
+        )
+    }
+}
+
+/// AgeDistributionContentFieldのVisitorを定義します。
+///
+/// ※この構造体は、Visitorトレイトを実装することを意図しています。
+struct AgeDistributionContentFieldVisitor;
+
+impl<'de> Visitor<'de> for AgeDistributionContentFieldVisitor {
+    type Value = AgeDistributionContentField;
+
+    fn expecting(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        write!(formatter, "`age_group` or `count` not found")
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: DeserializationError,
+    {
+        match value {
+            "age_group" => Ok(AgeDistributionContentField::AgeGroup),
+            "count" => Ok(AgeDistributionContentField::Count),
+            _ => Err(DeserializationError::unknown_field(
+                value,
+                AGE_DISTRIBUTION_CONTENT_FIELDS,
+            )),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for AgeDistributionContentField {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as Deserializer<'de>>::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_identifier(AgeDistributionContentFieldVisitor)
+    }
+}
+
+/// AgeDistributionContentのVisitorを定義します。
+///
+/// ※この構造体は、Visitorトレイトを実装することを意図しています。
+struct AgeDistributionContentVisitor;
+
+impl<'de> Visitor<'de> for AgeDistributionContentVisitor {
+    type Value = AgeDistributionContent;
+
+    fn expecting(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        write!(formatter, "format is not correct.")
+    }
+
+    fn visit_map<M>(self, mut map: M) -> Result<Self::Value, <M as MapAccess<'de>>::Error>
+    where
+        M: MapAccess<'de>,
+    {
+        let mut age_group = None;
+        let mut count = None;
+
+        // 連想配列のキーを取得する
+        while let Some(key) = map.next_key::<AgeDistributionContentField>()? {
+            match key {
+                AgeDistributionContentField::AgeGroup => {
+                    // 既にage_groupに内容が含まれていないか判定
+                    if age_group.is_some() {
+                        return Err(DeserializationError::duplicate_field(
+                            AGE_DISTRIBUTION_CONTENT_FIELDS[0],
+                        ));
+                    }
+                    // 年代の区分をパースし、格納する
+                    age_group = Some(map.next_value::<AgeGroup>()?);
+                }
+                AgeDistributionContentField::Count => {
+                    // 既にcountに内容が含まれていないか判定
+                    if count.is_some() {
+                        return Err(DeserializationError::duplicate_field(
+                            AGE_DISTRIBUTION_CONTENT_FIELDS[1],
+                        ));
+                    }
+                    // 陽性者数をパースし、格納する
+                    count = Some(map.next_value::<u32>()?);
+                }
+            }
+        }
+
+        // age_groupの中身を取り出す
+        let age_group = age_group.ok_or_else(
+            // フィールドが不足していることを伝える
+            || DeserializationError::missing_field(AGE_DISTRIBUTION_CONTENT_FIELDS[0]),
+        )?;
+        // countの中身を取り出す
+        let count = count.ok_or_else(
+            // フィールドが不足していることを伝える
+            || DeserializationError::missing_field(AGE_DISTRIBUTION_CONTENT_FIELDS[1]),
+        )?;
+
+        // AgeDistributionContentを返却
+        Ok(AgeDistributionContent { age_group, count })
+    }
+}
+
+impl<'de> Deserialize<'de> for AgeDistributionContent {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as Deserializer<'de>>::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            "AgeDistributionContent",
+            AGE_DISTRIBUTION_CONTENT_FIELDS,
+            AgeDistributionContentVisitor,
+        )
+    }
+}
+
+/// DailyAgeDistributionFieldのVisitorを定義します。
+///
+/// ※この構造体は、Visitorトレイトを実装することを意図しています。
+struct DailyAgeDistributionFieldVisitor;
+
+impl<'de> Visitor<'de> for DailyAgeDistributionFieldVisitor {
+    type Value = DailyAgeDistributionField;
+
+    fn expecting(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        write!(formatter, "`date` or `data` not found")
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: DeserializationError,
+    {
+        match value {
+            "date" => Ok(DailyAgeDistributionField::Date),
+            "data" => Ok(DailyAgeDistributionField::Data),
+            _ => Err(DeserializationError::unknown_field(
+                value,
+                DAILY_AGE_DISTRIBUTION_FIELDS,
+            )),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for DailyAgeDistributionField {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as Deserializer<'de>>::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_identifier(DailyAgeDistributionFieldVisitor)
+    }
+}
+
+/// DailyAgeDistributionのVisitorを定義します。
+///
+/// ※この構造体は、Visitorトレイトを実装することを意図しています。
+struct DailyAgeDistributionVisitor;
+
+impl<'de> Visitor<'de> for DailyAgeDistributionVisitor {
+    type Value = DailyAgeDistribution;
+
+    fn expecting(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        write!(formatter, "format is not correct.")
+    }
+
+    fn visit_map<M>(self, mut map: M) -> Result<Self::Value, <M as MapAccess<'de>>::Error>
+    where
+        M: MapAccess<'de>,
+    {
+        let mut date = None;
+        let mut data = None;
+
+        // 連想配列のキーを取得する
+        while let Some(key) = map.next_key::<DailyAgeDistributionField>()? {
+            match key {
+                DailyAgeDistributionField::Date => {
+                    // 既にdateに内容が含まれていないか判定
+                    if date.is_some() {
+                        return Err(DeserializationError::duplicate_field(
+                            DAILY_AGE_DISTRIBUTION_FIELDS[0],
+                        ));
+                    }
+                    // 日付をパースし、格納する
+                    date = Some(map.next_value_seed(DATE.seed())?);
+                }
+                DailyAgeDistributionField::Data => {
+                    // 既にdataに内容が含まれていないか判定
+                    if data.is_some() {
+                        return Err(DeserializationError::duplicate_field(
+                            DAILY_AGE_DISTRIBUTION_FIELDS[1],
+                        ));
+                    }
+                    // 年代ごとの陽性者数をパースし、区分が重複していないか検証した上で格納する
+                    let contents = map.next_value::<Vec<AgeDistributionContent>>()?;
+                    check_age_groups(&contents)?;
+                    data = Some(contents);
+                }
+            }
+        }
+
+        // dateの中身を取り出す
+        let date = date.ok_or_else(
+            // フィールドが不足していることを伝える
+            || DeserializationError::missing_field(DAILY_AGE_DISTRIBUTION_FIELDS[0]),
+        )?;
+        // dataの中身を取り出す
+        let data = data.ok_or_else(
+            // フィールドが不足していることを伝える
+            || DeserializationError::missing_field(DAILY_AGE_DISTRIBUTION_FIELDS[1]),
+        )?;
+
+        // DailyAgeDistributionを返却
+        Ok(DailyAgeDistribution { date, data })
+    }
+}
+
+impl<'de> Deserialize<'de> for DailyAgeDistribution {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as Deserializer<'de>>::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            "DailyAgeDistribution",
+            DAILY_AGE_DISTRIBUTION_FIELDS,
+            DailyAgeDistributionVisitor,
+        )
+    }
+}
+
+/// AgeDistributionFieldのVisitorを定義します。
+///
+/// ※この構造体は、Visitorトレイトを実装することを意図しています。
+struct AgeDistributionFieldVisitor;
+
+impl<'de> Visitor<'de> for AgeDistributionFieldVisitor {
+    type Value = AgeDistributionField;
+
+    fn expecting(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        write!(formatter, "`data`, `daily` or `last_update` not found")
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: DeserializationError,
+    {
+        match value {
+            "data" => Ok(AgeDistributionField::Data),
+            "daily" => Ok(AgeDistributionField::Daily),
+            "last_update" => Ok(AgeDistributionField::LastUpdate),
+            _ => Err(DeserializationError::unknown_field(
+                value,
+                AGE_DISTRIBUTION_FIELDS,
+            )),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for AgeDistributionField {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as Deserializer<'de>>::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_identifier(AgeDistributionFieldVisitor)
+    }
+}
+
+/// AgeDistributionのVisitorを定義します。
+///
+/// ※この構造体は、Visitorトレイトを実装することを意図しています。
+struct AgeDistributionVisitor;
+
+impl<'de> Visitor<'de> for AgeDistributionVisitor {
+    type Value = AgeDistribution;
+
+    fn expecting(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        write!(formatter, "format is not correct.")
+    }
+
+    fn visit_map<M>(self, mut map: M) -> Result<Self::Value, <M as MapAccess<'de>>::Error>
+    where
+        M: MapAccess<'de>,
+    {
+        let mut data = None;
+        let mut daily = None;
+        let mut last_update = None;
+
+        // 連想配列のキーを取得する
+        while let Some(key) = map.next_key::<AgeDistributionField>()? {
+            match key {
+                AgeDistributionField::Data => {
+                    // 既にdataに内容が含まれていないか判定
+                    if data.is_some() {
+                        return Err(DeserializationError::duplicate_field(
+                            AGE_DISTRIBUTION_FIELDS[0],
+                        ));
+                    }
+                    // 年代ごとの陽性者数をパースし、区分が重複していないか検証した上で格納する
+                    let contents = map.next_value::<Vec<AgeDistributionContent>>()?;
+                    check_age_groups(&contents)?;
+                    data = Some(contents);
+                }
+                AgeDistributionField::Daily => {
+                    // 既にdailyに内容が含まれていないか判定
+                    if daily.is_some() {
+                        return Err(DeserializationError::duplicate_field(
+                            AGE_DISTRIBUTION_FIELDS[1],
+                        ));
+                    }
+                    // 日毎の内訳をパースし、格納する
+                    daily = Some(map.next_value::<Vec<DailyAgeDistribution>>()?);
+                }
+                AgeDistributionField::LastUpdate => {
+                    // 既にlast_updateに内容が含まれていないか判定
+                    if last_update.is_some() {
+                        return Err(DeserializationError::duplicate_field(
+                            AGE_DISTRIBUTION_FIELDS[2],
+                        ));
+                    }
+                    // 日付と時刻をパースし、格納する
+                    last_update = Some(map.next_value_seed(DATETIME.seed())?);
+                }
+            }
+        }
+
+        // dataの中身を取り出す
+        let data = data.ok_or_else(
+            // フィールドが不足していることを伝える
+            || DeserializationError::missing_field(AGE_DISTRIBUTION_FIELDS[0]),
+        )?;
+        // last_updateの中身を取り出す
+        let last_update = last_update.ok_or_else(
+            // フィールドが不足していることを伝える
+            || DeserializationError::missing_field(AGE_DISTRIBUTION_FIELDS[2]),
+        )?;
+
+        // AgeDistributionを返却する。日毎の内訳は省略できる
+        Ok(AgeDistribution {
+            data,
+            daily,
+            last_update,
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for AgeDistribution {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as Deserializer<'de>>::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            "AgeDistribution",
+            AGE_DISTRIBUTION_FIELDS,
+            AgeDistributionVisitor,
+        )
+    }
+}
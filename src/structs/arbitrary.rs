@@ -0,0 +1,401 @@
+//! proptestクレートにより、構造体を無作為に生成する`Arbitrary`の実装を提供します。
+//!
+//! `proptest`フィーチャーを有効にした場合のみ使用できます。
+//! 生成される値は、シリアライズ後にデシリアライズした結果が元の値と一致する範囲に限定しています。
+
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveTime};
+use proptest::{
+    arbitrary::{any, Arbitrary},
+    collection::vec,
+    option,
+    sample::{select, subsequence},
+    strategy::{BoxedStrategy, Strategy},
+};
+
+use crate::population::age_band::AgeBand;
+
+use super::{
+    age_distribution::{AgeDistribution, AgeDistributionContent, AgeGroup, DailyAgeDistribution},
+    last_update::LastUpdate,
+    news::{
+        link::{NewsUrl, UrlPolicy},
+        localized::{LocalizedNewsItem, LocalizedNewsItems, LocalizedText},
+        NewsItem, NewsItems,
+    },
+    status::{Attributes, Status},
+    summary::{Summary, SummaryContent},
+    vaccination::{CoverageGroup, PerDose, Vaccination, VaccinationContent, VaccinationCoverage},
+};
+
+/// 生成する日付の範囲の始点です。
+fn origin() -> NaiveDate {
+    NaiveDate::from_ymd_opt(2000, 1, 1).unwrap()
+}
+
+/// 2000年から2099年までの日付を生成します。
+fn dates() -> impl Strategy<Value = NaiveDate> {
+    (0i64..36524).prop_map(|days| origin() + Duration::days(days))
+}
+
+/// 分単位の日時を生成します。
+///
+/// 夏時間の切り替え等により、ローカルのタイムゾーンで一意に定まらない日時は除外します。
+fn datetimes() -> impl Strategy<Value = DateTime<Local>> {
+    (dates(), 0u32..24 * 60).prop_filter_map("ambiguous local datetime", |(date, minutes)| {
+        let time = NaiveTime::from_hms_opt(minutes / 60, minutes % 60, 0)?;
+        date.and_time(time).and_local_timezone(Local).single()
+    })
+}
+
+/// 制御文字を含まない、お知らせの本文を生成します。
+fn texts() -> impl Strategy<Value = String> {
+    "\\PC{0,40}"
+}
+
+/// 区分が重複せず、`AgeGroup::ALL`の順に並んだ、年代ごとの陽性者数の一覧を生成します。
+fn age_contents() -> impl Strategy<Value = Vec<AgeDistributionContent>> {
+    subsequence(AgeGroup::ALL.to_vec(), 0..=AgeGroup::ALL.len()).prop_flat_map(|age_groups| {
+        let len = age_groups.len();
+        vec(any::<u32>(), len).prop_map(move |counts| {
+            age_groups
+                .iter()
+                .zip(counts)
+                .map(|(age_group, count)| AgeDistributionContent {
+                    age_group: *age_group,
+                    count,
+                })
+                .collect()
+        })
+    })
+}
+
+/// 0%から100%までの接種率を生成します。
+///
+/// JSON形式等で10進数として書き出した後も同じ値として読み込めるよう、1/8%単位の値に限定します。
+fn rates() -> impl Strategy<Value = PerDose<f64>> {
+    let rate = || (0u32..=800).prop_map(|eighths| f64::from(eighths) / 8.0);
+    (rate(), rate(), rate(), rate()).prop_map(|(first, second, third, booster)| PerDose {
+        first,
+        second,
+        third,
+        booster,
+    })
+}
+
+/// 全ての接種率の区分を、`CoverageGroup::All`・若い年代の順に返却します。
+fn coverage_groups() -> Vec<CoverageGroup> {
+    std::iter::once(CoverageGroup::All)
+        .chain(AgeBand::ALL.into_iter().map(CoverageGroup::Band))
+        .collect()
+}
+
+impl Arbitrary for Attributes {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        select(Attributes::ALL).boxed()
+    }
+}
+
+impl Arbitrary for Status {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    /// 深さ4段階までの、Statusの階層を生成します。
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        let leaf = (any::<Attributes>(), any::<u32>(), option::of(datetimes())).prop_map(
+            |(attr, value, last_update)| Status {
+                attr,
+                value,
+                children: None,
+                last_update,
+            },
+        );
+        leaf.prop_recursive(3, 24, 4, |inner| {
+            (
+                any::<Attributes>(),
+                any::<u32>(),
+                option::of(vec(inner, 0..4)),
+                option::of(datetimes()),
+            )
+                .prop_map(|(attr, value, children, last_update)| Status {
+                    attr,
+                    value,
+                    children,
+                    last_update,
+                })
+        })
+        .boxed()
+    }
+}
+
+impl Arbitrary for SummaryContent {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (dates(), any::<u32>())
+            .prop_map(|(date, sum)| SummaryContent { date, sum })
+            .boxed()
+    }
+}
+
+impl Arbitrary for Summary {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    /// 日付が重複せず、昇順に並んだ小計の一覧を生成します。
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (dates(), vec((1i64..4, any::<u32>()), 0..32), datetimes())
+            .prop_map(|(start, days, last_update)| {
+                let mut date = start;
+                let data = days
+                    .into_iter()
+                    .map(|(gap, sum)| {
+                        date += Duration::days(gap);
+                        SummaryContent { date, sum }
+                    })
+                    .collect();
+                Summary { data, last_update }
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for LastUpdate<Local> {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        datetimes()
+            .prop_map(|datetime| LastUpdate { datetime })
+            .boxed()
+    }
+}
+
+impl Arbitrary for NewsUrl {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    /// `http`または`https`の、ホスト名とパスからなる絶対URLを生成します。
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            select(&["http", "https"][..]),
+            "[a-z][a-z0-9]{0,9}(\\.[a-z][a-z0-9]{0,9}){1,2}",
+            "(/[A-Za-z0-9_-]{1,8}){0,3}/?",
+        )
+            .prop_filter_map("invalid url", |(scheme, host, path)| {
+                UrlPolicy::LENIENT
+                    .parse(&format!("{}://{}{}", scheme, host, path))
+                    .ok()
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for NewsItem {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (dates(), texts(), any::<NewsUrl>())
+            .prop_map(|(date, text, url)| NewsItem { date, text, url })
+            .boxed()
+    }
+}
+
+impl Arbitrary for NewsItems {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        vec(any::<NewsItem>(), 0..16)
+            .prop_map(|news_items| NewsItems { news_items })
+            .boxed()
+    }
+}
+
+impl Arbitrary for LocalizedText {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            texts(),
+            option::of(texts()),
+            option::of(texts()),
+            option::of(texts()),
+        )
+            .prop_map(|(ja, en, zh, ko)| LocalizedText { ja, en, zh, ko })
+            .boxed()
+    }
+}
+
+impl Arbitrary for LocalizedNewsItem {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (dates(), any::<LocalizedText>(), any::<NewsUrl>())
+            .prop_map(|(date, text, url)| LocalizedNewsItem { date, text, url })
+            .boxed()
+    }
+}
+
+impl Arbitrary for LocalizedNewsItems {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        vec(any::<LocalizedNewsItem>(), 0..8)
+            .prop_map(|news_items| LocalizedNewsItems { news_items })
+            .boxed()
+    }
+}
+
+impl Arbitrary for AgeGroup {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        select(&AgeGroup::ALL[..]).boxed()
+    }
+}
+
+impl Arbitrary for AgeDistributionContent {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (any::<AgeGroup>(), any::<u32>())
+            .prop_map(|(age_group, count)| AgeDistributionContent { age_group, count })
+            .boxed()
+    }
+}
+
+impl Arbitrary for DailyAgeDistribution {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (dates(), age_contents())
+            .prop_map(|(date, data)| DailyAgeDistribution { date, data })
+            .boxed()
+    }
+}
+
+impl Arbitrary for AgeDistribution {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    /// 日毎の内訳は、日付が重複せず昇順に並んだ一覧を生成します。
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            age_contents(),
+            option::of((dates(), vec((1i64..4, age_contents()), 0..8))),
+            datetimes(),
+        )
+            .prop_map(|(data, daily, last_update)| AgeDistribution {
+                data,
+                daily: daily.map(|(start, days)| {
+                    let mut date = start;
+                    days.into_iter()
+                        .map(|(gap, data)| {
+                            date += Duration::days(gap);
+                            DailyAgeDistribution { date, data }
+                        })
+                        .collect()
+                }),
+                last_update,
+            })
+            .boxed()
+    }
+}
+
+impl<T: Arbitrary + 'static> Arbitrary for PerDose<T> {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (any::<T>(), any::<T>(), any::<T>(), any::<T>())
+            .prop_map(|(first, second, third, booster)| PerDose {
+                first,
+                second,
+                third,
+                booster,
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for CoverageGroup {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        select(coverage_groups()).boxed()
+    }
+}
+
+impl Arbitrary for VaccinationContent {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (dates(), any::<PerDose<u32>>())
+            .prop_map(|(date, doses)| VaccinationContent { date, doses })
+            .boxed()
+    }
+}
+
+impl Arbitrary for VaccinationCoverage {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (any::<CoverageGroup>(), rates())
+            .prop_map(|(age_group, rates)| VaccinationCoverage { age_group, rates })
+            .boxed()
+    }
+}
+
+impl Arbitrary for Vaccination {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    /// 日付が重複せず昇順に並んだ接種回数と、区分が重複しない接種率の一覧を生成します。
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        let groups = coverage_groups();
+        let len = groups.len();
+        (
+            dates(),
+            vec((1i64..4, any::<PerDose<u32>>()), 0..16),
+            subsequence(groups, 0..=len),
+            datetimes(),
+        )
+            .prop_flat_map(|(start, days, groups, last_update)| {
+                let mut date = start;
+                let data = days
+                    .into_iter()
+                    .map(|(gap, doses)| {
+                        date += Duration::days(gap);
+                        VaccinationContent { date, doses }
+                    })
+                    .collect::<Vec<VaccinationContent>>();
+                vec(rates(), groups.len()).prop_map(move |rates| Vaccination {
+                    data: data.clone(),
+                    coverage: groups
+                        .iter()
+                        .zip(rates)
+                        .map(|(age_group, rates)| VaccinationCoverage {
+                            age_group: *age_group,
+                            rates,
+                        })
+                        .collect(),
+                    last_update,
+                })
+            })
+            .boxed()
+    }
+}
@@ -1,14 +1,20 @@
-use crate::utils::formats::DATETIME_FORMAT;
+use crate::utils::formats::{
+    codec::DATETIME,
+    era::{format_era_datetime, Digits},
+};
 use chrono::{DateTime, Local, TimeZone};
 use serde::{
     de::{Error as DeserializationError, MapAccess, Visitor},
     ser::SerializeStruct,
     Deserialize, Deserializer, Serialize, Serializer,
 };
-use std::fmt::{Error, Formatter};
+use std::{
+    fmt::{Error, Formatter},
+    hash::{Hash, Hasher},
+};
 
 /// シリアライズする際のフィールド名です。
-const FIELDS: &'static [&'static str] = &["update_data"];
+pub(crate) const FIELDS: &[&str; 1] = &["last_update"];
 
 #[derive(Clone, Debug)]
 /// LastUpdate構造体のフィールド名です。
@@ -23,6 +29,28 @@ pub struct LastUpdate<Local: TimeZone> {
     pub datetime: DateTime<Local>,
 }
 
+/// タイムゾーンの型は比較できない場合があるため、日時のみを比較します。
+impl<Local: TimeZone> PartialEq for LastUpdate<Local> {
+    fn eq(&self, other: &Self) -> bool {
+        self.datetime == other.datetime
+    }
+}
+
+impl<Local: TimeZone> Eq for LastUpdate<Local> {}
+
+impl<Local: TimeZone> Hash for LastUpdate<Local> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.datetime.hash(state);
+    }
+}
+
+impl<Local: TimeZone> LastUpdate<Local> {
+    /// 最終更新日時を、和暦の文字列として返却します。(例: `令和2年3月25日 21時40分`)
+    pub fn era_datetime(&self, digits: Digits) -> Option<String> {
+        format_era_datetime(self.datetime.naive_local(), digits)
+    }
+}
+
 /// シリアライズ処理の実装です。
 impl Serialize for LastUpdate<Local> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -30,14 +58,9 @@ impl Serialize for LastUpdate<Local> {
         S: Serializer,
     {
         // 構造体のシリアライズを開始する
-        let mut state = serializer.serialize_struct("LastUpdate", 1).unwrap();
+        let mut state = serializer.serialize_struct("LastUpdate", 1)?;
         // datetimeフィールドをシリアライズする
-        state
-            .serialize_field(
-                "last_update",
-                &format!("{}", self.datetime.format(DATETIME_FORMAT).to_string()),
-            )
-            .unwrap();
+        state.serialize_field("last_update", &DATETIME.format(&self.datetime))?;
         // ステートを終了し、結果を返却する
         state.end()
     }
@@ -95,7 +118,7 @@ impl<'de> Visitor<'de> for LastUpdateVisitor {
         let mut update_date = None;
 
         // 連想配列のキーを取得し
-        while let Some(key) = map.next_key::<LastUpdateField>().unwrap() {
+        while let Some(key) = map.next_key::<LastUpdateField>()? {
             match key {
                 // DateTimeを取り出し
                 LastUpdateField::DateTime => {
@@ -104,14 +127,7 @@ impl<'de> Visitor<'de> for LastUpdateVisitor {
                         return Err(DeserializationError::duplicate_field(FIELDS[0]));
                     }
                     // 日付と時刻をパースし、格納する
-                    update_date = Some(
-                        Local
-                            .datetime_from_str(
-                                &map.next_value::<String>().unwrap(),
-                                DATETIME_FORMAT,
-                            )
-                            .unwrap(),
-                    );
+                    update_date = Some(map.next_value_seed(DATETIME.seed())?);
                 }
             }
         }
@@ -6,10 +6,20 @@ use serde::{
 };
 use std::fmt::{Error, Formatter};
 
-use crate::utils::formats::DATE_FORMAT;
+use crate::utils::formats::{
+    codec::DATE,
+    era::{format_era_date, Digits},
+};
+use link::{NewsUrl, UrlPolicy};
+
+pub mod borrowed;
+pub mod builder;
+pub mod curation;
+pub mod link;
+pub mod localized;
 
 ///  NewsItemをシリアライズする際のフィールド名です。
-const NEWS_ITEM_FIELDS: &'static [&'static str] = &["date", "text", "url"];
+pub(crate) const NEWS_ITEM_FIELDS: &[&str; 3] = &["date", "text", "url"];
 
 #[derive(Clone, Debug)]
 /// NewsItem構造体のフィールド名です。
@@ -19,18 +29,25 @@ enum NewsItemField {
     Url,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct NewsItem {
     pub date: NaiveDate,
     pub text: String,
-    pub url: String,
+    pub url: NewsUrl,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
 pub struct NewsItems {
     pub news_items: Vec<NewsItem>,
 }
 
+impl NewsItem {
+    /// お知らせの日付を、和暦の文字列として返却します。(例: `令和2年3月25日`)
+    pub fn era_date(&self, digits: Digits) -> Option<String> {
+        format_era_date(self.date, digits)
+    }
+}
+
 /// NewsItemのシリアライズ処理の実装です。
 impl Serialize for NewsItem {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -38,18 +55,13 @@ impl Serialize for NewsItem {
         S: Serializer,
     {
         // 構造体のシリアライズを開始する
-        let mut state = serializer.serialize_struct("NewsItem", 1).unwrap();
+        let mut state = serializer.serialize_struct("NewsItem", 3)?;
         // dateフィールドをシリアライズする
-        state
-            .serialize_field(
-                "date",
-                &format!("{}", &self.date.format(DATE_FORMAT).to_string()),
-            )
-            .unwrap();
+        state.serialize_field("date", &DATE.format(&self.date))?;
         // textフィールドをシリアライズする
-        state.serialize_field("text", &self.text).unwrap();
+        state.serialize_field("text", &self.text)?;
         // urlフィールドをシリアライズする
-        state.serialize_field("url", &self.url).unwrap();
+        state.serialize_field("url", &self.url)?;
         // ステートを終了し、結果を返却する
         state.end()
     }
@@ -91,10 +103,14 @@ impl<'de> Deserialize<'de> for NewsItemField {
 
 /// NewsItemのVisitorを定義します。
 ///
+/// URLは、指定されたポリシーで検証します。
+///
 /// ※この構造体は、Visitorトレイトを実装することを意図しています。
-struct NewsItemVisitor;
+struct NewsItemVisitor<'p> {
+    policy: &'p UrlPolicy,
+}
 
-impl<'de> Visitor<'de> for NewsItemVisitor {
+impl<'de> Visitor<'de> for NewsItemVisitor<'_> {
     // 変換する対象の構造体型を定義
     type Value = NewsItem;
 
@@ -111,7 +127,7 @@ impl<'de> Visitor<'de> for NewsItemVisitor {
         let mut url = None;
 
         // 連想配列のキーを取得する
-        while let Some(key) = map.next_key::<NewsItemField>().unwrap() {
+        while let Some(key) = map.next_key::<NewsItemField>()? {
             match key {
                 // dateを取り出す
                 NewsItemField::Date => {
@@ -120,13 +136,7 @@ impl<'de> Visitor<'de> for NewsItemVisitor {
                         return Err(DeserializationError::duplicate_field(NEWS_ITEM_FIELDS[0]));
                     }
                     // 日付と時刻をパースし、格納する
-                    date = Some(
-                        NaiveDate::parse_from_str(
-                            &map.next_value::<String>().unwrap(),
-                            DATE_FORMAT,
-                        )
-                        .unwrap(),
-                    );
+                    date = Some(map.next_value_seed(DATE.seed())?);
                 }
                 NewsItemField::Text => {
                     // 既にtextに内容が含まれていないか判定
@@ -134,15 +144,15 @@ impl<'de> Visitor<'de> for NewsItemVisitor {
                         return Err(DeserializationError::duplicate_field(NEWS_ITEM_FIELDS[1]));
                     }
                     // Stringをパースし、格納する
-                    text = Some(map.next_value::<String>().unwrap());
+                    text = Some(map.next_value::<String>()?);
                 }
                 NewsItemField::Url => {
                     // 既にurlに内容が含まれていないか判定
                     if url.is_some() {
                         return Err(DeserializationError::duplicate_field(NEWS_ITEM_FIELDS[2]));
                     }
-                    // Stringをパースし、格納する
-                    url = Some(map.next_value::<String>().unwrap());
+                    // URLをパースして検証し、格納する
+                    url = Some(map.next_value_seed(self.policy.seed())?);
                 }
             }
         }
@@ -164,11 +174,7 @@ impl<'de> Visitor<'de> for NewsItemVisitor {
         )?;
 
         // Patientsを返却
-        Ok(NewsItem {
-            date: date,
-            text: text,
-            url: url,
-        })
+        Ok(NewsItem { date, text, url })
     }
 }
 
@@ -177,6 +183,12 @@ impl<'de> Deserialize<'de> for NewsItem {
     where
         D: Deserializer<'de>,
     {
-        deserializer.deserialize_struct("NewsItem", NEWS_ITEM_FIELDS, NewsItemVisitor)
+        deserializer.deserialize_struct(
+            "NewsItem",
+            NEWS_ITEM_FIELDS,
+            NewsItemVisitor {
+                policy: &UrlPolicy::LENIENT,
+            },
+        )
     }
 }
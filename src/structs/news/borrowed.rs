@@ -0,0 +1,223 @@
+use chrono::NaiveDate;
+use serde::{
+    de::{Error as DeserializationError, MapAccess, Visitor},
+    ser::SerializeStruct,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::{
+    borrow::Cow,
+    fmt::{Error, Formatter},
+};
+
+use super::{link::UrlError, NewsItem, NewsItemField, NEWS_ITEM_FIELDS};
+use crate::utils::formats::codec::DATE;
+
+/// 入力を借用する、NewsItem構造体です。
+///
+/// デシリアライズの際、エスケープを含まない文字列は入力から借用するため、
+/// 大量のお知らせを読み込む場合でもメモリの確保を抑えられます。
+/// URLはデシリアライズの際には検証せず、NewsItem構造体への変換時に検証します。
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct NewsItemRef<'a> {
+    pub date: NaiveDate,
+    pub text: Cow<'a, str>,
+    pub url: Cow<'a, str>,
+}
+
+/// 入力を借用する、NewsItems構造体です。
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct NewsItemsRef<'a> {
+    #[serde(borrow)]
+    pub news_items: Vec<NewsItemRef<'a>>,
+}
+
+impl NewsItemRef<'_> {
+    /// 借用している文字列を複製し、NewsItem構造体に変換します。
+    ///
+    /// URLは、既定のポリシー(`UrlPolicy::default()`)で検証します。
+    pub fn into_owned(self) -> Result<NewsItem, UrlError> {
+        Ok(NewsItem {
+            date: self.date,
+            text: self.text.into_owned(),
+            url: self.url.parse()?,
+        })
+    }
+}
+
+impl<'a> From<&'a NewsItem> for NewsItemRef<'a> {
+    fn from(item: &'a NewsItem) -> Self {
+        NewsItemRef {
+            date: item.date,
+            text: Cow::Borrowed(&item.text),
+            url: Cow::Borrowed(item.url.as_str()),
+        }
+    }
+}
+
+impl NewsItemsRef<'_> {
+    /// 借用している文字列を複製し、NewsItems構造体に変換します。
+    ///
+    /// URLは、既定のポリシー(`UrlPolicy::default()`)で検証します。
+    pub fn into_owned(self) -> Result<super::NewsItems, UrlError> {
+        Ok(super::NewsItems {
+            news_items: self
+                .news_items
+                .into_iter()
+                .map(NewsItemRef::into_owned)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/// NewsItemRefのシリアライズ処理の実装です。
+///
+/// NewsItemと同じ形式でシリアライズします。
+impl Serialize for NewsItemRef<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        // 構造体のシリアライズを開始する
+        let mut state = serializer.serialize_struct("NewsItem", 3)?;
+        // dateフィールドをシリアライズする
+        state.serialize_field("date", &DATE.format(&self.date))?;
+        // textフィールドをシリアライズする
+        state.serialize_field("text", &self.text)?;
+        // urlフィールドをシリアライズする
+        state.serialize_field("url", &self.url)?;
+        // ステートを終了し、結果を返却する
+        state.end()
+    }
+}
+
+/// 可能な限り入力を借用して、文字列をデシリアライズします。
+///
+/// `Cow<str>`のDeserialize実装は常に文字列を複製するため、本構造体を経由します。
+struct BorrowedStr<'a>(Cow<'a, str>);
+
+/// BorrowedStrのVisitorを定義します。
+///
+/// ※この構造体は、Visitorトレイトを実装することを意図しています。
+struct BorrowedStrVisitor;
+
+impl<'de> Visitor<'de> for BorrowedStrVisitor {
+    type Value = BorrowedStr<'de>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        write!(formatter, "a string")
+    }
+
+    fn visit_borrowed_str<E>(self, value: &'de str) -> Result<Self::Value, E>
+    where
+        E: DeserializationError,
+    {
+        // 入力から借用する
+        Ok(BorrowedStr(Cow::Borrowed(value)))
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: DeserializationError,
+    {
+        // エスケープを含む等、借用できない場合は複製する
+        Ok(BorrowedStr(Cow::Owned(value.to_string())))
+    }
+
+    fn visit_string<E>(self, value: String) -> Result<Self::Value, E>
+    where
+        E: DeserializationError,
+    {
+        Ok(BorrowedStr(Cow::Owned(value)))
+    }
+}
+
+impl<'de> Deserialize<'de> for BorrowedStr<'de> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as Deserializer<'de>>::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(BorrowedStrVisitor)
+    }
+}
+
+/// NewsItemRefのVisitorを定義します。
+///
+/// ※この構造体は、Visitorトレイトを実装することを意図しています。
+struct NewsItemRefVisitor;
+
+impl<'de> Visitor<'de> for NewsItemRefVisitor {
+    // 変換する対象の構造体型を定義
+    type Value = NewsItemRef<'de>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        write!(formatter, "format is not correct.")
+    }
+
+    fn visit_map<M>(self, mut map: M) -> Result<Self::Value, <M as MapAccess<'de>>::Error>
+    where
+        M: MapAccess<'de>,
+    {
+        let mut date = None;
+        let mut text = None;
+        let mut url = None;
+
+        // 連想配列のキーを取得する
+        while let Some(key) = map.next_key::<NewsItemField>()? {
+            match key {
+                // dateを取り出す
+                NewsItemField::Date => {
+                    // 既にdateに内容が含まれていないか判定
+                    if date.is_some() {
+                        return Err(DeserializationError::duplicate_field(NEWS_ITEM_FIELDS[0]));
+                    }
+                    // 文字列を複製せずに日付をパースし、格納する
+                    date = Some(map.next_value_seed(DATE.seed())?);
+                }
+                NewsItemField::Text => {
+                    // 既にtextに内容が含まれていないか判定
+                    if text.is_some() {
+                        return Err(DeserializationError::duplicate_field(NEWS_ITEM_FIELDS[1]));
+                    }
+                    // 文字列を借用し、格納する
+                    text = Some(map.next_value::<BorrowedStr>()?.0);
+                }
+                NewsItemField::Url => {
+                    // 既にurlに内容が含まれていないか判定
+                    if url.is_some() {
+                        return Err(DeserializationError::duplicate_field(NEWS_ITEM_FIELDS[2]));
+                    }
+                    // 文字列を借用し、格納する
+                    url = Some(map.next_value::<BorrowedStr>()?.0);
+                }
+            }
+        }
+
+        // dateの中身を取り出す
+        let date = date.ok_or_else(
+            // フィールドが不足していることを伝える
+            || DeserializationError::missing_field(NEWS_ITEM_FIELDS[0]),
+        )?;
+        // textの中身を取り出す
+        let text = text.ok_or_else(
+            // フィールドが不足していることを伝える
+            || DeserializationError::missing_field(NEWS_ITEM_FIELDS[1]),
+        )?;
+        // urlの中身を取り出す
+        let url = url.ok_or_else(
+            // フィールドが不足していることを伝える
+            || DeserializationError::missing_field(NEWS_ITEM_FIELDS[2]),
+        )?;
+
+        // NewsItemRefを返却
+        Ok(NewsItemRef { date, text, url })
+    }
+}
+
+impl<'de: 'a, 'a> Deserialize<'de> for NewsItemRef<'a> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as Deserializer<'de>>::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct("NewsItem", NEWS_ITEM_FIELDS, NewsItemRefVisitor)
+    }
+}
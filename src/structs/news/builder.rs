@@ -0,0 +1,112 @@
+//! NewsItem構造体を組み立てるビルダーを提供します。
+
+use chrono::NaiveDate;
+use std::{
+    error::Error as StdError,
+    fmt::{Display, Error, Formatter},
+};
+
+use super::{
+    link::{NewsUrl, UrlError, UrlPolicy},
+    NewsItem, NEWS_ITEM_FIELDS,
+};
+use crate::utils::formats::codec::DateInput;
+
+/// NewsItemの組み立てに失敗した際のエラーです。
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NewsItemBuildError {
+    /// 必須のフィールドが指定されていません。
+    MissingField(&'static str),
+    /// 本文が空です。
+    EmptyText,
+    /// 日付を解釈できません。
+    InvalidDate(String),
+    /// URLが不正、またはポリシーを満たしていません。
+    InvalidUrl(UrlError),
+}
+
+impl Display for NewsItemBuildError {
+    fn fmt(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        match self {
+            NewsItemBuildError::MissingField(field) => {
+                write!(formatter, "`{}` is not specified", field)
+            }
+            NewsItemBuildError::EmptyText => write!(formatter, "at `text`: text is empty"),
+            NewsItemBuildError::InvalidDate(message) => {
+                write!(formatter, "at `date`: {}", message)
+            }
+            NewsItemBuildError::InvalidUrl(error) => write!(formatter, "at `url`: {}", error),
+        }
+    }
+}
+
+impl StdError for NewsItemBuildError {}
+
+/// NewsItem構造体を組み立てるビルダーです。
+///
+/// `NewsItem::builder`により生成し、`build`で検証した上でNewsItemを生成します。
+/// URLは、既定では`UrlPolicy::LENIENT`で検証します。
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct NewsItemBuilder {
+    date: Option<Result<NaiveDate, String>>,
+    text: Option<String>,
+    url: Option<String>,
+    policy: UrlPolicy,
+}
+
+impl NewsItem {
+    /// 空のビルダーを生成します。
+    pub fn builder() -> NewsItemBuilder {
+        NewsItemBuilder::default()
+    }
+}
+
+impl NewsItemBuilder {
+    /// 日付を指定します。文字列(`2020/03/25`等)でも指定できます。
+    pub fn date(mut self, date: impl DateInput<NaiveDate>) -> Self {
+        self.date = Some(date.into_date());
+        self
+    }
+
+    /// 本文を指定します。
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.text = Some(text.into());
+        self
+    }
+
+    /// リンク先のURLを指定します。
+    pub fn url(mut self, url: impl AsRef<str>) -> Self {
+        self.url = Some(url.as_ref().to_string());
+        self
+    }
+
+    /// URLを検証する際のポリシーを指定します。
+    pub fn policy(mut self, policy: UrlPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// 内容を検証し、NewsItemを生成します。
+    pub fn build(self) -> Result<NewsItem, NewsItemBuildError> {
+        let date = self
+            .date
+            .ok_or(NewsItemBuildError::MissingField(NEWS_ITEM_FIELDS[0]))?
+            .map_err(NewsItemBuildError::InvalidDate)?;
+        let text = self
+            .text
+            .ok_or(NewsItemBuildError::MissingField(NEWS_ITEM_FIELDS[1]))?;
+        if text.trim().is_empty() {
+            return Err(NewsItemBuildError::EmptyText);
+        }
+        let url: NewsUrl = self
+            .policy
+            .parse(
+                &self
+                    .url
+                    .ok_or(NewsItemBuildError::MissingField(NEWS_ITEM_FIELDS[2]))?,
+            )
+            .map_err(NewsItemBuildError::InvalidUrl)?;
+
+        Ok(NewsItem { date, text, url })
+    }
+}
@@ -0,0 +1,196 @@
+use chrono::NaiveDate;
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, HashMap},
+    error::Error as StdError,
+    fmt::{Display, Error, Formatter},
+    num::NonZeroUsize,
+    ops::RangeBounds,
+};
+
+use super::{NewsItem, NewsItems};
+use crate::{document::DocumentType, migration::upgrade_json};
+
+/// 同じURLを持つお知らせの組です。
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DuplicateUrl {
+    /// 重複しているURLです。
+    pub url: String,
+    /// 重複しているお知らせの番号(0始まり)です。
+    pub indices: Vec<usize>,
+}
+
+impl Display for DuplicateUrl {
+    fn fmt(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        let indices = self
+            .indices
+            .iter()
+            .map(|index| format!("news_items[{}]", index))
+            .collect::<Vec<String>>()
+            .join(", ");
+        write!(formatter, "duplicate url `{}` at {}", self.url, indices)
+    }
+}
+
+/// お知らせの一覧を厳格に読み込む際のエラーです。
+#[derive(Debug)]
+pub enum StrictLoadError {
+    /// JSONとして不正な入力です。
+    Json(serde_json::Error),
+    /// 同じURLを持つお知らせが含まれています。
+    Duplicates(Vec<DuplicateUrl>),
+}
+
+impl Display for StrictLoadError {
+    fn fmt(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        match self {
+            StrictLoadError::Json(error) => write!(formatter, "{}", error),
+            StrictLoadError::Duplicates(duplicates) => {
+                let duplicates = duplicates
+                    .iter()
+                    .map(DuplicateUrl::to_string)
+                    .collect::<Vec<String>>()
+                    .join("; ");
+                write!(formatter, "{}", duplicates)
+            }
+        }
+    }
+}
+
+impl StdError for StrictLoadError {}
+
+impl From<serde_json::Error> for StrictLoadError {
+    fn from(error: serde_json::Error) -> Self {
+        StrictLoadError::Json(error)
+    }
+}
+
+impl NewsItems {
+    /// JSON形式の文字列からお知らせの一覧を読み込み、同じURLを持つお知らせが含まれている場合はエラーとします。
+    ///
+    /// 版数(`version`)を含むデータファイルは、現在の版に変換した上で読み込みます。
+    pub fn from_json_strict(json: &str) -> Result<NewsItems, StrictLoadError> {
+        let json = upgrade_json(DocumentType::News, json).map_err(serde_json::Error::from)?;
+        let news_items = serde_json::from_str::<NewsItems>(&json)?;
+        let duplicates = news_items.duplicates();
+        if duplicates.is_empty() {
+            Ok(news_items)
+        } else {
+            Err(StrictLoadError::Duplicates(duplicates))
+        }
+    }
+
+    /// 同じURLを持つお知らせの組を、最初に出現した順に返却します。
+    ///
+    /// URLは正規化した上で比較するため、末尾のスラッシュの有無等の表記の違いは同じURLとして扱います。
+    pub fn duplicates(&self) -> Vec<DuplicateUrl> {
+        let mut indices: HashMap<&str, Vec<usize>> = HashMap::new();
+        let mut order = Vec::new();
+        for (index, item) in self.news_items.iter().enumerate() {
+            let url = item.url.as_url().as_str();
+            let entry = indices.entry(url).or_insert_with(|| {
+                order.push(url);
+                Vec::new()
+            });
+            entry.push(index);
+        }
+
+        order
+            .into_iter()
+            .filter_map(|url| {
+                let indices = indices.remove(url)?;
+                (indices.len() > 1).then(|| DuplicateUrl {
+                    url: url.to_string(),
+                    indices,
+                })
+            })
+            .collect()
+    }
+
+    /// お知らせを日付の新しい順に並び替えます。
+    ///
+    /// 同じ日付のお知らせは、元の順序を維持します。
+    pub fn sort_newest_first(&mut self) {
+        self.news_items.sort_by_key(|item| Reverse(item.date));
+    }
+
+    /// 同じURLを持つお知らせを、日付の最も新しい1件のみ残して取り除きます。URLは正規化した上で比較します。
+    ///
+    /// 日付も同じ場合は、先に出現したお知らせを残します。
+    /// 残ったお知らせは、元の順序を維持します。
+    pub fn dedup_by_url(&mut self) {
+        // URLごとに、残すお知らせの番号を決定する
+        let mut newest: HashMap<&str, usize> = HashMap::new();
+        for (index, item) in self.news_items.iter().enumerate() {
+            match newest.entry(item.url.as_url().as_str()) {
+                Entry::Occupied(mut entry) => {
+                    if self.news_items[*entry.get()].date < item.date {
+                        entry.insert(index);
+                    }
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(index);
+                }
+            }
+        }
+
+        let mut keep = vec![false; self.news_items.len()];
+        for index in newest.into_values() {
+            keep[index] = true;
+        }
+        let mut keep = keep.into_iter();
+        self.news_items.retain(|_| keep.next().unwrap_or(false));
+    }
+
+    /// 重複を取り除いた上で、日付の新しい順に並び替えます。
+    pub fn curate(&mut self) {
+        self.dedup_by_url();
+        self.sort_newest_first();
+    }
+
+    /// 日付が指定された範囲に含まれるお知らせのみを返却します。
+    pub fn filter_by_date<R>(&self, range: R) -> NewsItems
+    where
+        R: RangeBounds<NaiveDate>,
+    {
+        self.filter(|item| range.contains(&item.date))
+    }
+
+    /// 本文に指定された語句を含むお知らせのみを返却します。
+    ///
+    /// 英字の大文字と小文字は区別しません。
+    pub fn filter_by_keyword(&self, keyword: &str) -> NewsItems {
+        let keyword = keyword.to_lowercase();
+        self.filter(|item| item.text.to_lowercase().contains(&keyword))
+    }
+
+    /// 指定された条件を満たすお知らせのみを返却します。
+    fn filter<F>(&self, predicate: F) -> NewsItems
+    where
+        F: Fn(&NewsItem) -> bool,
+    {
+        NewsItems {
+            news_items: self
+                .news_items
+                .iter()
+                .filter(|item| predicate(item))
+                .cloned()
+                .collect(),
+        }
+    }
+
+    /// 1ページあたりの件数を指定して、ページの総数を返却します。
+    pub fn page_count(&self, per_page: NonZeroUsize) -> usize {
+        self.news_items.len().div_ceil(per_page.get())
+    }
+
+    /// 指定されたページ(0始まり)に含まれるお知らせを返却します。
+    ///
+    /// ページが範囲外の場合は、空のスライスを返却します。
+    pub fn page(&self, index: usize, per_page: NonZeroUsize) -> &[NewsItem] {
+        self.news_items
+            .chunks(per_page.get())
+            .nth(index)
+            .unwrap_or_default()
+    }
+}
@@ -0,0 +1,455 @@
+use serde::{
+    de::{
+        DeserializeSeed, Error as DeserializationError, IgnoredAny, MapAccess, SeqAccess, Visitor,
+    },
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::{
+    error::Error as StdError,
+    fmt::{Display, Error, Formatter},
+    str::FromStr,
+};
+use url::Url;
+
+use super::{NewsItem, NewsItemVisitor, NewsItems, NEWS_ITEM_FIELDS};
+use crate::{document::DocumentType, migration::upgrade_json};
+
+/// NewsItemsをシリアライズする際のフィールド名です。
+pub(crate) const NEWS_ITEMS_FIELDS: &[&str; 1] = &["news_items"];
+
+/// お知らせのリンク先として検証済みのURLです。
+///
+/// 相対パスやフラグメントのみの参照は受け付けず、常に`http`または`https`の絶対URLを保持します。
+/// 検証によって公開済みのリンクが書き換わらないよう、シリアライズの際は入力された文字列をそのまま出力します。
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NewsUrl {
+    /// パースされ、正規化されたURLです。
+    url: Url,
+    /// 入力された文字列です。
+    original: String,
+}
+
+impl NewsUrl {
+    /// 入力された文字列を返却します。
+    pub fn as_str(&self) -> &str {
+        &self.original
+    }
+
+    /// パースされ、正規化されたURLを`url::Url`として返却します。
+    ///
+    /// 末尾のスラッシュの補完や、日本語のパスのパーセントエンコードにより、`as_str`とは異なる場合があります。
+    pub fn as_url(&self) -> &Url {
+        &self.url
+    }
+}
+
+impl Display for NewsUrl {
+    fn fmt(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        write!(formatter, "{}", self.original)
+    }
+}
+
+impl AsRef<str> for NewsUrl {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl PartialEq<str> for NewsUrl {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for NewsUrl {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+/// 既定のポリシー(`UrlPolicy::default()`)で検証します。
+impl FromStr for NewsUrl {
+    type Err = UrlError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        UrlPolicy::LENIENT.parse(value)
+    }
+}
+
+impl Serialize for NewsUrl {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+/// 指定されたポリシーで検証しながら、NewsUrlを読み込むDeserializeSeedです。
+///
+/// ※この構造体は、Visitorトレイトを実装することを意図しています。
+pub(crate) struct NewsUrlSeed<'p> {
+    policy: &'p UrlPolicy,
+}
+
+impl<'de> Visitor<'de> for NewsUrlSeed<'_> {
+    type Value = NewsUrl;
+
+    fn expecting(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        write!(formatter, "an absolute URL")
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: DeserializationError,
+    {
+        self.policy
+            .parse(value)
+            .map_err(DeserializationError::custom)
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for NewsUrlSeed<'_> {
+    type Value = NewsUrl;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(self)
+    }
+}
+
+/// 既定のポリシー(`UrlPolicy::default()`)で検証します。
+impl<'de> Deserialize<'de> for NewsUrl {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as Deserializer<'de>>::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        UrlPolicy::LENIENT.seed().deserialize(deserializer)
+    }
+}
+
+/// URLの検証に失敗した際のエラーです。
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum UrlError {
+    /// フラグメントのみの参照(`#section`等)です。
+    FragmentOnly(String),
+    /// スキームを含まない相対パスです。
+    Relative(String),
+    /// URLとして不正な文字列です。
+    Invalid {
+        /// 検証した文字列です。
+        value: String,
+        /// パースに失敗した理由です。
+        error: url::ParseError,
+    },
+    /// `http`・`https`以外のスキームです。
+    UnsupportedScheme {
+        /// 検証した文字列です。
+        value: String,
+        /// URLのスキームです。
+        scheme: String,
+    },
+    /// `https`が必須のポリシーで、`http`のURLが指定されました。
+    HttpsRequired(String),
+    /// 許可されていないドメインのURLです。
+    DomainNotAllowed {
+        /// 検証した文字列です。
+        value: String,
+        /// URLのホスト名です。
+        host: String,
+        /// 許可されているドメインの一覧です。
+        allowed: Vec<String>,
+    },
+}
+
+impl Display for UrlError {
+    fn fmt(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        match self {
+            UrlError::FragmentOnly(value) => write!(
+                formatter,
+                "url `{}` is a fragment-only reference; an absolute URL is required",
+                value
+            ),
+            UrlError::Relative(value) => write!(
+                formatter,
+                "url `{}` is relative; an absolute URL starting with `https://` is required",
+                value
+            ),
+            UrlError::Invalid { value, error } => {
+                write!(formatter, "invalid url `{}`: {}", value, error)
+            }
+            UrlError::UnsupportedScheme { value, scheme } => write!(
+                formatter,
+                "url `{}` has unsupported scheme `{}` (expected `http` or `https`)",
+                value, scheme
+            ),
+            UrlError::HttpsRequired(value) => {
+                write!(formatter, "url `{}` must use `https`", value)
+            }
+            UrlError::DomainNotAllowed {
+                value,
+                host,
+                allowed,
+            } => write!(
+                formatter,
+                "url `{}` has host `{}`, which is not in the allowed domains ({})",
+                value,
+                host,
+                allowed.join(", ")
+            ),
+        }
+    }
+}
+
+impl StdError for UrlError {}
+
+/// お知らせのリンク先として受け付けるURLの条件です。
+///
+/// 既定値(`UrlPolicy::default()`)は、`http`または`https`の絶対URLであれば全て受け付けます。
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct UrlPolicy {
+    /// `https`のURLのみを受け付けるかを指定します。
+    pub require_https: bool,
+    /// 受け付けるドメインの一覧です。サブドメインも含めて受け付けます。
+    ///
+    /// 空の場合は、全てのドメインを受け付けます。
+    pub allowed_domains: Vec<String>,
+}
+
+impl UrlPolicy {
+    /// `http`または`https`の絶対URLであれば全て受け付ける、既定のポリシーです。
+    pub const LENIENT: UrlPolicy = UrlPolicy {
+        require_https: false,
+        allowed_domains: Vec::new(),
+    };
+
+    /// 京都府・京都市と本サイトのドメインで、`https`のURLのみを受け付けるポリシーを返却します。
+    pub fn kyoto() -> Self {
+        UrlPolicy {
+            require_https: true,
+            allowed_domains: vec![
+                "pref.kyoto.jp".to_string(),
+                "city.kyoto.lg.jp".to_string(),
+                "kyoto.stopcovid19.jp".to_string(),
+            ],
+        }
+    }
+
+    /// 文字列をURLとしてパースし、ポリシーを満たすか検証します。
+    pub fn parse(&self, value: &str) -> Result<NewsUrl, UrlError> {
+        let url = Url::parse(value).map_err(|error| match error {
+            url::ParseError::RelativeUrlWithoutBase if value.trim_start().starts_with('#') => {
+                UrlError::FragmentOnly(value.to_string())
+            }
+            url::ParseError::RelativeUrlWithoutBase => UrlError::Relative(value.to_string()),
+            error => UrlError::Invalid {
+                value: value.to_string(),
+                error,
+            },
+        })?;
+        self.check(&url, value)?;
+        Ok(NewsUrl {
+            url,
+            original: value.to_string(),
+        })
+    }
+
+    /// 検証済みのURLが、ポリシーを満たすか検証します。
+    pub fn validate(&self, url: &NewsUrl) -> Result<(), UrlError> {
+        self.check(&url.url, url.as_str())
+    }
+
+    /// お知らせの一覧の全てのURLが、ポリシーを満たすか検証します。
+    ///
+    /// 満たさないお知らせが存在する場合は、その番号(0始まり)とエラーを返却します。
+    pub fn validate_all(&self, news_items: &NewsItems) -> Result<(), (usize, UrlError)> {
+        for (index, item) in news_items.news_items.iter().enumerate() {
+            self.validate(&item.url).map_err(|error| (index, error))?;
+        }
+        Ok(())
+    }
+
+    /// JSON形式の文字列からお知らせの一覧を読み込み、その際に全てのURLを検証します。
+    ///
+    /// エラーには、ポリシーを満たさなかったURLの行番号と列番号が含まれます。
+    /// 版数(`version`)を含むデータファイルは、現在の版に変換した上で読み込みます。
+    pub fn news_items_from_json(&self, json: &str) -> Result<NewsItems, serde_json::Error> {
+        let json = upgrade_json(DocumentType::News, json)?;
+        let mut deserializer = serde_json::Deserializer::from_str(&json);
+        let news_items = NewsItemsSeed { policy: self }.deserialize(&mut deserializer)?;
+        deserializer.end()?;
+        Ok(news_items)
+    }
+
+    /// このポリシーでURLを検証する、DeserializeSeedを返却します。
+    pub(crate) fn seed(&self) -> NewsUrlSeed<'_> {
+        NewsUrlSeed { policy: self }
+    }
+
+    /// パース済みのURLが、ポリシーを満たすか検証します。
+    fn check(&self, url: &Url, value: &str) -> Result<(), UrlError> {
+        match url.scheme() {
+            "https" => (),
+            "http" if self.require_https => return Err(UrlError::HttpsRequired(value.to_string())),
+            "http" => (),
+            scheme => {
+                return Err(UrlError::UnsupportedScheme {
+                    value: value.to_string(),
+                    scheme: scheme.to_string(),
+                })
+            }
+        }
+
+        if !self.allowed_domains.is_empty() {
+            // ホスト名は、パースの際に小文字へ正規化されている
+            let host = url.host_str().unwrap_or_default();
+            let allowed = self.allowed_domains.iter().any(|domain| {
+                let domain = domain.to_ascii_lowercase();
+                host == domain
+                    || host
+                        .strip_suffix(domain.as_str())
+                        .is_some_and(|subdomain| subdomain.ends_with('.'))
+            });
+            if !allowed {
+                return Err(UrlError::DomainNotAllowed {
+                    value: value.to_string(),
+                    host: host.to_string(),
+                    allowed: self.allowed_domains.clone(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// NewsItems構造体のフィールド名です。
+///
+/// 未知のフィールドは、NewsItemsのDeserialize実装と同様に無視します。
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "snake_case")]
+enum NewsItemsField {
+    NewsItems,
+    #[serde(other)]
+    Ignored,
+}
+
+/// 指定されたポリシーでURLを検証しながら、NewsItemsを読み込むDeserializeSeedです。
+///
+/// ※この構造体は、Visitorトレイトを実装することを意図しています。
+struct NewsItemsSeed<'p> {
+    policy: &'p UrlPolicy,
+}
+
+impl<'de> DeserializeSeed<'de> for NewsItemsSeed<'_> {
+    type Value = NewsItems;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct("NewsItems", NEWS_ITEMS_FIELDS, self)
+    }
+}
+
+impl<'de> Visitor<'de> for NewsItemsSeed<'_> {
+    type Value = NewsItems;
+
+    fn expecting(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        write!(formatter, "format is not correct.")
+    }
+
+    fn visit_map<M>(self, mut map: M) -> Result<Self::Value, <M as MapAccess<'de>>::Error>
+    where
+        M: MapAccess<'de>,
+    {
+        let mut news_items = None;
+
+        // 連想配列のキーを取得する
+        while let Some(key) = map.next_key::<NewsItemsField>()? {
+            match key {
+                NewsItemsField::NewsItems => {
+                    // 既にnews_itemsに内容が含まれていないか判定
+                    if news_items.is_some() {
+                        return Err(DeserializationError::duplicate_field(NEWS_ITEMS_FIELDS[0]));
+                    }
+                    news_items = Some(map.next_value_seed(NewsItemListSeed {
+                        policy: self.policy,
+                    })?);
+                }
+                NewsItemsField::Ignored => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+
+        // news_itemsの中身を取り出す
+        let news_items = news_items.ok_or_else(
+            // フィールドが不足していることを伝える
+            || DeserializationError::missing_field(NEWS_ITEMS_FIELDS[0]),
+        )?;
+        Ok(NewsItems { news_items })
+    }
+}
+
+/// 指定されたポリシーでURLを検証しながら、お知らせの配列を読み込むDeserializeSeedです。
+///
+/// ※この構造体は、Visitorトレイトを実装することを意図しています。
+struct NewsItemListSeed<'p> {
+    policy: &'p UrlPolicy,
+}
+
+impl<'de> DeserializeSeed<'de> for NewsItemListSeed<'_> {
+    type Value = Vec<NewsItem>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de> Visitor<'de> for NewsItemListSeed<'_> {
+    type Value = Vec<NewsItem>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        write!(formatter, "an array of NewsItem")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut news_items = Vec::new();
+        while let Some(item) = seq.next_element_seed(NewsItemSeed {
+            policy: self.policy,
+        })? {
+            news_items.push(item);
+        }
+        Ok(news_items)
+    }
+}
+
+/// 指定されたポリシーでURLを検証しながら、NewsItemを読み込むDeserializeSeedです。
+struct NewsItemSeed<'p> {
+    policy: &'p UrlPolicy,
+}
+
+impl<'de> DeserializeSeed<'de> for NewsItemSeed<'_> {
+    type Value = NewsItem;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            "NewsItem",
+            NEWS_ITEM_FIELDS,
+            NewsItemVisitor {
+                policy: self.policy,
+            },
+        )
+    }
+}
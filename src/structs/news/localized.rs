@@ -0,0 +1,430 @@
+use chrono::NaiveDate;
+use serde::{
+    de::{Error as DeserializationError, MapAccess, Visitor},
+    ser::{SerializeMap, SerializeStruct},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::{
+    borrow::Cow,
+    error::Error as StdError,
+    fmt::{Display, Error, Formatter},
+    str::FromStr,
+};
+
+use super::{
+    borrowed::{NewsItemRef, NewsItemsRef},
+    link::{NewsUrl, UrlPolicy},
+    NewsItem, NewsItemField, NewsItems, NEWS_ITEM_FIELDS,
+};
+use crate::utils::formats::codec::DATE;
+
+/// お知らせの本文を提供する言語です。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Locale {
+    /// 日本語です。全てのお知らせで必須です。
+    Ja,
+    /// 英語です。
+    En,
+    /// 中国語です。
+    Zh,
+    /// 韓国語です。
+    Ko,
+}
+
+/// 不明な言語が指定された際のエラーです。
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct UnknownLocale(pub String);
+
+impl Display for UnknownLocale {
+    fn fmt(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        write!(
+            formatter,
+            "unknown locale `{}` (expected one of: {})",
+            self.0,
+            Locale::ALL
+                .iter()
+                .map(Locale::as_str)
+                .collect::<Vec<&str>>()
+                .join(", ")
+        )
+    }
+}
+
+impl StdError for UnknownLocale {}
+
+impl Locale {
+    /// 全ての言語の一覧です。
+    pub const ALL: &'static [Locale] = &[Locale::Ja, Locale::En, Locale::Zh, Locale::Ko];
+
+    /// シリアライズ時の名前(言語コード)を返却します。
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Locale::Ja => "ja",
+            Locale::En => "en",
+            Locale::Zh => "zh",
+            Locale::Ko => "ko",
+        }
+    }
+}
+
+impl Display for Locale {
+    fn fmt(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        write!(formatter, "{}", self.as_str())
+    }
+}
+
+impl FromStr for Locale {
+    type Err = UnknownLocale;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Locale::ALL
+            .iter()
+            .find(|locale| locale.as_str() == value)
+            .copied()
+            .ok_or_else(|| UnknownLocale(value.to_string()))
+    }
+}
+
+/// 言語ごとのお知らせの本文です。
+///
+/// 日本語の本文は必須で、その他の言語は翻訳されている場合のみ保持します。
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct LocalizedText {
+    pub ja: String,
+    pub en: Option<String>,
+    pub zh: Option<String>,
+    pub ko: Option<String>,
+}
+
+impl LocalizedText {
+    /// 日本語の本文のみを持つ本文を生成します。
+    pub fn new(ja: impl Into<String>) -> Self {
+        LocalizedText {
+            ja: ja.into(),
+            en: None,
+            zh: None,
+            ko: None,
+        }
+    }
+
+    /// 指定された言語の本文を返却します。翻訳されていない場合は`None`を返却します。
+    pub fn get(&self, locale: Locale) -> Option<&str> {
+        match locale {
+            Locale::Ja => Some(&self.ja),
+            Locale::En => self.en.as_deref(),
+            Locale::Zh => self.zh.as_deref(),
+            Locale::Ko => self.ko.as_deref(),
+        }
+    }
+
+    /// 指定された言語の本文を返却します。翻訳されていない場合は日本語の本文を返却します。
+    pub fn get_or_ja(&self, locale: Locale) -> &str {
+        self.get(locale).unwrap_or(&self.ja)
+    }
+
+    /// 指定された言語の本文を格納する場所を返却します。
+    fn slot(&mut self, locale: Locale) -> Option<&mut Option<String>> {
+        match locale {
+            Locale::Ja => None,
+            Locale::En => Some(&mut self.en),
+            Locale::Zh => Some(&mut self.zh),
+            Locale::Ko => Some(&mut self.ko),
+        }
+    }
+}
+
+/// 翻訳されていないお知らせの扱いです。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Fallback {
+    /// 日本語の本文で出力します。
+    Japanese,
+    /// 出力から除外します。
+    Skip,
+}
+
+/// 複数の言語の本文を持つ、お知らせです。
+///
+/// 本文は言語ごとの連想配列としてシリアライズします。
+/// 単一の言語を選択した場合は、NewsItem構造体と同じ形式で出力できます。
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct LocalizedNewsItem {
+    pub date: NaiveDate,
+    pub text: LocalizedText,
+    pub url: NewsUrl,
+}
+
+/// 複数の言語の本文を持つ、お知らせの一覧です。
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct LocalizedNewsItems {
+    pub news_items: Vec<LocalizedNewsItem>,
+}
+
+impl LocalizedNewsItem {
+    /// 指定された言語の本文を持つ、NewsItemRef構造体を返却します。
+    ///
+    /// 翻訳されていない場合は`None`を返却します。
+    pub fn select(&self, locale: Locale) -> Option<NewsItemRef<'_>> {
+        self.text.get(locale).map(|text| NewsItemRef {
+            date: self.date,
+            text: Cow::Borrowed(text),
+            url: Cow::Borrowed(self.url.as_str()),
+        })
+    }
+
+    /// 指定された言語の本文を持つ、NewsItem構造体に変換します。
+    ///
+    /// 翻訳されていない場合は`None`を返却します。
+    pub fn localize(&self, locale: Locale) -> Option<NewsItem> {
+        self.text.get(locale).map(|text| NewsItem {
+            date: self.date,
+            text: text.to_string(),
+            url: self.url.clone(),
+        })
+    }
+}
+
+impl LocalizedNewsItems {
+    /// 指定された言語の本文を持つ、NewsItemsRef構造体を返却します。
+    ///
+    /// 文字列を複製しないため、言語ごとの`news.json`を出力する際に使用できます。
+    pub fn select(&self, locale: Locale, fallback: Fallback) -> NewsItemsRef<'_> {
+        NewsItemsRef {
+            news_items: self
+                .news_items
+                .iter()
+                .filter_map(|item| match fallback {
+                    Fallback::Japanese => item.select(locale).or_else(|| item.select(Locale::Ja)),
+                    Fallback::Skip => item.select(locale),
+                })
+                .collect(),
+        }
+    }
+
+    /// 指定された言語の本文を持つ、NewsItems構造体に変換します。
+    pub fn localize(&self, locale: Locale, fallback: Fallback) -> NewsItems {
+        NewsItems {
+            news_items: self
+                .news_items
+                .iter()
+                .filter_map(|item| match fallback {
+                    Fallback::Japanese => {
+                        item.localize(locale).or_else(|| item.localize(Locale::Ja))
+                    }
+                    Fallback::Skip => item.localize(locale),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// 日本語の本文のみを持つお知らせとして変換します。
+impl From<NewsItem> for LocalizedNewsItem {
+    fn from(item: NewsItem) -> Self {
+        LocalizedNewsItem {
+            date: item.date,
+            text: LocalizedText::new(item.text),
+            url: item.url,
+        }
+    }
+}
+
+/// 日本語の本文のみを持つお知らせの一覧として変換します。
+impl From<NewsItems> for LocalizedNewsItems {
+    fn from(news_items: NewsItems) -> Self {
+        LocalizedNewsItems {
+            news_items: news_items.news_items.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// LocalizedTextのシリアライズ処理の実装です。
+///
+/// 翻訳されていない言語は出力しません。
+impl Serialize for LocalizedText {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let len = Locale::ALL
+            .iter()
+            .filter(|locale| self.get(**locale).is_some())
+            .count();
+        let mut state = serializer.serialize_map(Some(len))?;
+        for locale in Locale::ALL {
+            if let Some(text) = self.get(*locale) {
+                state.serialize_entry(locale.as_str(), text)?;
+            }
+        }
+        state.end()
+    }
+}
+
+/// LocalizedTextのVisitorを定義します。
+///
+/// ※この構造体は、Visitorトレイトを実装することを意図しています。
+struct LocalizedTextVisitor;
+
+impl<'de> Visitor<'de> for LocalizedTextVisitor {
+    type Value = LocalizedText;
+
+    fn expecting(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        write!(formatter, "a string or a map of locale to string")
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: DeserializationError,
+    {
+        // 文字列のみの場合は、日本語の本文として扱う
+        Ok(LocalizedText::new(value))
+    }
+
+    fn visit_map<M>(self, mut map: M) -> Result<Self::Value, <M as MapAccess<'de>>::Error>
+    where
+        M: MapAccess<'de>,
+    {
+        let mut ja = None;
+        let mut text = LocalizedText::new(String::new());
+
+        // 連想配列のキーを取得する
+        while let Some(key) = map.next_key::<Cow<str>>()? {
+            let locale = key
+                .parse::<Locale>()
+                .map_err(DeserializationError::custom)?;
+            match text.slot(locale) {
+                // 日本語の本文を取り出す
+                None => {
+                    if ja.is_some() {
+                        return Err(DeserializationError::duplicate_field(Locale::Ja.as_str()));
+                    }
+                    ja = Some(map.next_value::<String>()?);
+                }
+                // その他の言語の本文を取り出す
+                Some(slot) => {
+                    if slot.is_some() {
+                        return Err(DeserializationError::duplicate_field(locale.as_str()));
+                    }
+                    *slot = Some(map.next_value::<String>()?);
+                }
+            }
+        }
+
+        // 日本語の本文は必須とする
+        text.ja = ja.ok_or_else(|| DeserializationError::missing_field(Locale::Ja.as_str()))?;
+        Ok(text)
+    }
+}
+
+impl<'de> Deserialize<'de> for LocalizedText {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as Deserializer<'de>>::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(LocalizedTextVisitor)
+    }
+}
+
+/// LocalizedNewsItemのシリアライズ処理の実装です。
+impl Serialize for LocalizedNewsItem {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        // 構造体のシリアライズを開始する
+        let mut state = serializer.serialize_struct("LocalizedNewsItem", 3)?;
+        // dateフィールドをシリアライズする
+        state.serialize_field("date", &DATE.format(&self.date))?;
+        // textフィールドをシリアライズする
+        state.serialize_field("text", &self.text)?;
+        // urlフィールドをシリアライズする
+        state.serialize_field("url", &self.url)?;
+        // ステートを終了し、結果を返却する
+        state.end()
+    }
+}
+
+/// LocalizedNewsItemのVisitorを定義します。
+///
+/// ※この構造体は、Visitorトレイトを実装することを意図しています。
+struct LocalizedNewsItemVisitor;
+
+impl<'de> Visitor<'de> for LocalizedNewsItemVisitor {
+    // 変換する対象の構造体型を定義
+    type Value = LocalizedNewsItem;
+
+    fn expecting(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        write!(formatter, "format is not correct.")
+    }
+
+    fn visit_map<M>(self, mut map: M) -> Result<Self::Value, <M as MapAccess<'de>>::Error>
+    where
+        M: MapAccess<'de>,
+    {
+        let mut date = None;
+        let mut text = None;
+        let mut url = None;
+
+        // 連想配列のキーを取得する
+        while let Some(key) = map.next_key::<NewsItemField>()? {
+            match key {
+                // dateを取り出す
+                NewsItemField::Date => {
+                    // 既にdateに内容が含まれていないか判定
+                    if date.is_some() {
+                        return Err(DeserializationError::duplicate_field(NEWS_ITEM_FIELDS[0]));
+                    }
+                    // 日付をパースし、格納する
+                    date = Some(map.next_value_seed(DATE.seed())?);
+                }
+                NewsItemField::Text => {
+                    // 既にtextに内容が含まれていないか判定
+                    if text.is_some() {
+                        return Err(DeserializationError::duplicate_field(NEWS_ITEM_FIELDS[1]));
+                    }
+                    // 言語ごとの本文をパースし、格納する
+                    text = Some(map.next_value::<LocalizedText>()?);
+                }
+                NewsItemField::Url => {
+                    // 既にurlに内容が含まれていないか判定
+                    if url.is_some() {
+                        return Err(DeserializationError::duplicate_field(NEWS_ITEM_FIELDS[2]));
+                    }
+                    // URLをパースして検証し、格納する
+                    url = Some(map.next_value_seed(UrlPolicy::LENIENT.seed())?);
+                }
+            }
+        }
+
+        // dateの中身を取り出す
+        let date = date.ok_or_else(
+            // フィールドが不足していることを伝える
+            || DeserializationError::missing_field(NEWS_ITEM_FIELDS[0]),
+        )?;
+        // textの中身を取り出す
+        let text = text.ok_or_else(
+            // フィールドが不足していることを伝える
+            || DeserializationError::missing_field(NEWS_ITEM_FIELDS[1]),
+        )?;
+        // urlの中身を取り出す
+        let url = url.ok_or_else(
+            // フィールドが不足していることを伝える
+            || DeserializationError::missing_field(NEWS_ITEM_FIELDS[2]),
+        )?;
+
+        // LocalizedNewsItemを返却
+        Ok(LocalizedNewsItem { date, text, url })
+    }
+}
+
+impl<'de> Deserialize<'de> for LocalizedNewsItem {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as Deserializer<'de>>::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            "LocalizedNewsItem",
+            NEWS_ITEM_FIELDS,
+            LocalizedNewsItemVisitor,
+        )
+    }
+}
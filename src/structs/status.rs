@@ -1,49 +1,113 @@
-use chrono::{DateTime, Local, TimeZone};
+use chrono::{DateTime, Local};
 use serde::{
     de::{Error as DeserializationError, MapAccess, Visitor},
     ser::SerializeStruct,
     Deserialize, Deserializer, Serialize, Serializer,
 };
-use std::fmt::{Error, Formatter};
+use std::{
+    error::Error as StdError,
+    fmt::{Display, Error, Formatter},
+    str::FromStr,
+};
+
+use crate::utils::formats::codec::DATETIME;
 
-use crate::utils::formats::DATETIME_FORMAT;
+pub mod builder;
 
 /// Statusをシリアライズする際のフィールド名です。
-const STATUS_FIELDS: &'static [&'static str] = &["attr", "value", "children", "last_update"];
+pub(crate) const STATUS_FIELDS: &[&str; 4] = &["attr", "value", "children", "last_update"];
+
+/// 属性の列挙子と、シリアライズする際の属性名の対応表から、Attributesを定義します。
+///
+/// 属性名の一覧・`as_str`・シリアライズ処理が同じ対応表から生成されるよう、属性はこの対応表にのみ追加します。
+macro_rules! attributes {
+    ($($(#[$meta:meta])* $variant:ident => $name:literal,)*) => {
+        /// COVID-19に関連する情報の属性を列挙しています。
+        #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+        pub enum Attributes {
+            $($(#[$meta])* $variant,)*
+        }
+
+        impl Attributes {
+            /// 全ての属性を、定義順に列挙した配列です。
+            pub const ALL: &'static [Attributes] = &[$(Attributes::$variant,)*];
+
+            /// シリアライズした際の属性名を返却します。
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $(Attributes::$variant => $name,)*
+                }
+            }
+        }
+    };
+}
 
-/// COVID-19に関連する情報の属性を列挙しています。
-#[derive(Clone, Debug, Deserialize, Serialize)]
-pub enum Attributes {
+attributes! {
     /// 宿泊施設で療養している人数の属性です。
-    #[serde(rename = "accommodations")]
-    Accommodations,
+    Accommodations => "accommodations",
     /// 医療・行政機関等により調整作業を行なっている人数の属性です。
-    #[serde(rename = "coodinating")]
-    Coodinating,
+    Coodinating => "coodinating",
     /// COVID-19によりお亡くなりになられた人数の属性です。
-    #[serde(rename = "dead")]
-    Dead,
+    Dead => "dead",
     /// 自宅療養中の人数の属性です。
-    #[serde(rename = "home")]
-    Home,
+    Home => "home",
     /// 入院中の人数の属性です。
-    #[serde(rename = "hospitalizations")]
-    Hospitalizations,
+    Hospitalizations => "hospitalizations",
     /// PCR検査件数の属性です。
-    #[serde(rename = "inspections")]
-    Inspections,
+    Inspections => "inspections",
     /// 退院した人数の属性です。
-    #[serde(rename = "leave")]
-    Leave,
+    Leave => "leave",
     /// 陽性者数の属性です。
-    #[serde(rename = "patients")]
-    Patients,
+    Patients => "patients",
     /// 症状の重症化により、高度重症病床を利用されている人数の属性です。
-    #[serde(rename = "severely_patients")]
-    SeverelyPatients,
+    SeverelyPatients => "severely_patients",
     /// 重症化のうち、他の方法による対応を受けている人数の属性です。
-    #[serde(rename = "other")]
-    Other,
+    Other => "other",
+}
+
+/// 未知の属性名が指定された際のエラーです。
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct UnknownAttribute(pub String);
+
+impl Display for UnknownAttribute {
+    fn fmt(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        write!(formatter, "unknown attribute `{}`", self.0)
+    }
+}
+
+impl StdError for UnknownAttribute {}
+
+impl FromStr for Attributes {
+    type Err = UnknownAttribute;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Attributes::ALL
+            .iter()
+            .find(|attr| attr.as_str() == value)
+            .cloned()
+            .ok_or_else(|| UnknownAttribute(value.to_string()))
+    }
+}
+
+/// Attributesのシリアライズ処理の実装です。
+impl Serialize for Attributes {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+/// Attributesのデシリアライズ処理の実装です。
+impl<'de> Deserialize<'de> for Attributes {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+        value.parse().map_err(DeserializationError::custom)
+    }
 }
 
 enum StatusField {
@@ -54,7 +118,7 @@ enum StatusField {
 }
 
 /// COVID-19に関する情報を格納する構造体です。
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Status {
     pub attr: Attributes,
     pub value: u32,
@@ -62,36 +126,40 @@ pub struct Status {
     pub last_update: Option<DateTime<Local>>,
 }
 
-/// Summaryのシリアライズ処理の実装です。
+impl Status {
+    /// 自身と子属性を深さ優先で探索し、指定された属性を持つ最初のStatusを返却します。
+    pub fn find(&self, attr: &Attributes) -> Option<&Status> {
+        if self.attr == *attr {
+            return Some(self);
+        }
+        self.children
+            .iter()
+            .flatten()
+            .find_map(|child| child.find(attr))
+    }
+}
+
+/// Statusのシリアライズ処理の実装です。
 impl Serialize for Status {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
+        // 省略可能なフィールドは、値が存在する場合のみ数える
+        let len = 2 + self.children.is_some() as usize + self.last_update.is_some() as usize;
         // 構造体のシリアライズを開始する
-        let mut state = serializer.serialize_struct("Summary", 1).unwrap();
+        let mut state = serializer.serialize_struct("Status", len)?;
         // attrフィールドをシリアライズする
-        state.serialize_field("attr", &self.attr).unwrap();
+        state.serialize_field("attr", &self.attr)?;
         // valueフィールドをシリアライズする
-        state.serialize_field("value", &self.value).unwrap();
+        state.serialize_field("value", &self.value)?;
         // childrenフィールドをシリアライズする
-        match &self.children {
-            Some(v) => {
-                state.serialize_field("children", v).unwrap();
-            }
-            None => (),
+        if let Some(v) = &self.children {
+            state.serialize_field("children", v)?;
         }
         // last_updateフィールドをシリアライズする
-        match self.last_update {
-            Some(v) => {
-                state
-                    .serialize_field(
-                        "last_update",
-                        &format!("{}", v.format(DATETIME_FORMAT).to_string()),
-                    )
-                    .unwrap();
-            }
-            None => (),
+        if let Some(v) = self.last_update {
+            state.serialize_field("last_update", &DATETIME.format(&v))?;
         }
         // ステートを終了し、結果を返却する
         state.end()
@@ -159,7 +227,7 @@ impl<'de> Visitor<'de> for StatusVisitor {
         let mut last_update = None;
 
         // 連想配列のキーを取得する
-        while let Some(key) = map.next_key::<StatusField>().unwrap() {
+        while let Some(key) = map.next_key::<StatusField>()? {
             match key {
                 // Attributesを取り出す
                 StatusField::Attr => {
@@ -168,7 +236,7 @@ impl<'de> Visitor<'de> for StatusVisitor {
                         return Err(DeserializationError::duplicate_field(STATUS_FIELDS[0]));
                     }
                     // Attributesをパースし、格納する
-                    attr = Some(map.next_value::<Attributes>().unwrap());
+                    attr = Some(map.next_value::<Attributes>()?);
                 }
                 // 整数値を取り出す
                 StatusField::Value => {
@@ -177,7 +245,7 @@ impl<'de> Visitor<'de> for StatusVisitor {
                         return Err(DeserializationError::duplicate_field(STATUS_FIELDS[1]));
                     }
                     // 整数値をパースし、格納する
-                    value = Some(map.next_value::<u32>().unwrap());
+                    value = Some(map.next_value::<u32>()?);
                 }
                 // 子属性を取り出す
                 StatusField::Children => {
@@ -186,7 +254,7 @@ impl<'de> Visitor<'de> for StatusVisitor {
                         return Err(DeserializationError::duplicate_field(STATUS_FIELDS[2]));
                     }
                     // 子属性の内容をパースし、格納する
-                    children = Some(map.next_value::<Vec<Status>>().unwrap());
+                    children = Some(map.next_value::<Vec<Status>>()?);
                 }
                 // 最終更新日時を取り出す
                 StatusField::LastUpdate => {
@@ -195,14 +263,7 @@ impl<'de> Visitor<'de> for StatusVisitor {
                         return Err(DeserializationError::duplicate_field(STATUS_FIELDS[3]));
                     }
                     // last_updateの内容をパースし、格納する
-                    last_update = Some(
-                        Local
-                            .datetime_from_str(
-                                &map.next_value::<String>().unwrap(),
-                                DATETIME_FORMAT,
-                            )
-                            .unwrap(),
-                    );
+                    last_update = Some(map.next_value_seed(DATETIME.seed())?);
                 }
             }
         }
@@ -224,10 +285,10 @@ impl<'de> Visitor<'de> for StatusVisitor {
 
         // Summaryを返却
         Ok(Status {
-            attr: attr,
-            value: value,
-            children: children,
-            last_update: last_update,
+            attr,
+            value,
+            children,
+            last_update,
         })
     }
 }
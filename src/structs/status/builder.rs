@@ -0,0 +1,181 @@
+//! Status構造体を、階層ごとに組み立てるビルダーを提供します。
+
+use chrono::{DateTime, Local};
+use std::{
+    error::Error as StdError,
+    fmt::{Display, Error, Formatter},
+};
+
+use super::{Attributes, Status};
+use crate::utils::formats::codec::DateInput;
+
+/// Statusの組み立てに失敗した際のエラーです。
+///
+/// `path`には、エラーが発生したStatusの位置を、属性名を`.`で連結した形式(例: `patients.hospitalizations`)で格納します。
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum StatusBuildError {
+    /// 値が指定されていません。
+    MissingValue { path: String },
+    /// 同じ属性の子属性が、複数指定されています。
+    DuplicateChild { path: String, attr: &'static str },
+    /// 子属性の値の合計が、親の値を超えています。
+    ChildrenExceedParent {
+        path: String,
+        value: u32,
+        total: u64,
+    },
+    /// 最終更新日時を解釈できません。
+    InvalidLastUpdate { path: String, message: String },
+}
+
+impl Display for StatusBuildError {
+    fn fmt(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        match self {
+            StatusBuildError::MissingValue { path } => {
+                write!(formatter, "at `{}`: value is not specified", path)
+            }
+            StatusBuildError::DuplicateChild { path, attr } => {
+                write!(formatter, "at `{}`: duplicate child `{}`", path, attr)
+            }
+            StatusBuildError::ChildrenExceedParent { path, value, total } => write!(
+                formatter,
+                "at `{}`: sum of children ({}) exceeds value ({})",
+                path, total, value
+            ),
+            StatusBuildError::InvalidLastUpdate { path, message } => {
+                write!(formatter, "at `{}`: {}", path, message)
+            }
+        }
+    }
+}
+
+impl StdError for StatusBuildError {}
+
+/// Status構造体を組み立てるビルダーです。
+///
+/// `Status::builder`により生成し、`build`で検証した上でStatusを生成します。
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct StatusBuilder {
+    attr: Attributes,
+    value: Option<u32>,
+    children: Option<Vec<StatusBuilder>>,
+    last_update: Option<Result<DateTime<Local>, String>>,
+}
+
+impl Status {
+    /// 属性を指定して、ビルダーを生成します。
+    pub fn builder(attr: Attributes) -> StatusBuilder {
+        StatusBuilder {
+            attr,
+            value: None,
+            children: None,
+            last_update: None,
+        }
+    }
+}
+
+impl StatusBuilder {
+    /// 値を指定します。
+    pub fn value(mut self, value: u32) -> Self {
+        self.value = Some(value);
+        self
+    }
+
+    /// 子属性を追加します。
+    pub fn child(mut self, child: StatusBuilder) -> Self {
+        self.children.get_or_insert_with(Vec::new).push(child);
+        self
+    }
+
+    /// 子属性をまとめて追加します。
+    pub fn children<I>(mut self, children: I) -> Self
+    where
+        I: IntoIterator<Item = StatusBuilder>,
+    {
+        self.children.get_or_insert_with(Vec::new).extend(children);
+        self
+    }
+
+    /// 最終更新日時を指定します。文字列は`2020/03/25 21:40`等の形式で指定できます。
+    pub fn last_update(mut self, last_update: impl DateInput<DateTime<Local>>) -> Self {
+        self.last_update = Some(last_update.into_date());
+        self
+    }
+
+    /// 内容を検証し、Statusを生成します。
+    pub fn build(self) -> Result<Status, StatusBuildError> {
+        self.build_at(None)
+    }
+
+    /// 親の位置を指定して、内容を検証し、Statusを生成します。
+    fn build_at(self, parent: Option<&str>) -> Result<Status, StatusBuildError> {
+        let path = match parent {
+            Some(parent) => format!("{}.{}", parent, self.attr.as_str()),
+            None => self.attr.as_str().to_string(),
+        };
+
+        let value = self
+            .value
+            .ok_or_else(|| StatusBuildError::MissingValue { path: path.clone() })?;
+        let last_update = self.last_update.transpose().map_err(|message| {
+            StatusBuildError::InvalidLastUpdate {
+                path: path.clone(),
+                message,
+            }
+        })?;
+
+        let children = match self.children {
+            Some(children) => {
+                // 同じ属性の子属性が含まれていないか確認する
+                for (index, child) in children.iter().enumerate() {
+                    let attr = child.attr.as_str();
+                    if children[..index].iter().any(|v| v.attr.as_str() == attr) {
+                        return Err(StatusBuildError::DuplicateChild { path, attr });
+                    }
+                }
+                let children = children
+                    .into_iter()
+                    .map(|child| child.build_at(Some(&path)))
+                    .collect::<Result<Vec<Status>, StatusBuildError>>()?;
+                // 子属性の値の合計が、親の値を超えていないか確認する
+                let total = children.iter().map(|v| u64::from(v.value)).sum::<u64>();
+                if total > u64::from(value) {
+                    return Err(StatusBuildError::ChildrenExceedParent { path, value, total });
+                }
+                Some(children)
+            }
+            None => None,
+        };
+
+        Ok(Status {
+            attr: self.attr,
+            value,
+            children,
+            last_update,
+        })
+    }
+}
+
+/// Status構造体を、属性と値の階層として宣言的に組み立てます。
+///
+/// 属性は`Attributes`の列挙子名で指定し、子属性は`{}`の中に`,`区切りで列挙します。
+/// 末尾に`; last_update: ...`を指定すると、最上位のStatusに最終更新日時を設定します。
+/// 内容は`StatusBuilder::build`で検証され、`Result<Status, StatusBuildError>`を返却します。
+/// (例: `status!(Patients => 4096 { Hospitalizations => 100, Accommodations => 32 })`)
+#[macro_export]
+macro_rules! status {
+    (@builder $attr:ident => $value:tt $({
+        $($child:ident => $child_value:tt $({ $($grandchild:tt)* })?),* $(,)?
+    })?) => {
+        $crate::structs::status::Status::builder($crate::structs::status::Attributes::$attr)
+            .value($value)
+            $($(.child($crate::status!(
+                @builder $child => $child_value $({ $($grandchild)* })?
+            )))*)?
+    };
+    ($attr:ident => $value:tt $({ $($children:tt)* })? $(; last_update: $last_update:expr)?) => {
+        $crate::status!(@builder $attr => $value $({ $($children)* })?)
+            $(.last_update($last_update))?
+            .build()
+    };
+}
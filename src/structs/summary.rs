@@ -1,5 +1,9 @@
-use crate::utils::formats::DATETIME_FORMAT;
-use chrono::{DateTime, Local, TimeZone, Utc};
+use crate::utils::formats::{
+    codec::{DateCodec, DATE, DATETIME, TIMESTAMP},
+    era::{format_era_date, format_era_datetime, Digits},
+    jst,
+};
+use chrono::{DateTime, Local, NaiveDate, NaiveTime, Utc};
 use serde::{
     de::{Error as DeserializationError, MapAccess, Visitor},
     ser::SerializeStruct,
@@ -7,20 +11,23 @@ use serde::{
 };
 use std::fmt::{Error, Formatter};
 
+pub mod builder;
+pub mod revision;
+
 /// Summaryをシリアライズする際のフィールド名です。
-const SUMMARY_FIELDS: &'static [&'static str] = &["date", "sum"];
+pub(crate) const SUMMARY_FIELDS: &[&str; 2] = &["data", "last_update"];
 /// SummaryContentをシリアライズする際のフィールド名です。
-const SUMMARYCONTENT_FIELDS: &'static [&'static str] = &["data", "last_update"];
+pub(crate) const SUMMARYCONTENT_FIELDS: &[&str; 2] = &["date", "sum"];
 
 #[derive(Clone, Debug)]
 /// Summary構造体のフィールド名です。
-enum SummaryField {
+pub(crate) enum SummaryField {
     Data,
     LastUpdate,
 }
 
 // 小計を列挙する構造体です。
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Summary {
     pub data: Vec<SummaryContent>,
     pub last_update: DateTime<Local>,
@@ -33,29 +40,110 @@ enum SummaryContentField {
 }
 
 /// 小計を格納する構造体です。
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct SummaryContent {
-    pub date: DateTime<Utc>,
+    /// 小計の対象日です。日本標準時における日付として扱います。
+    pub date: NaiveDate,
     pub sum: u32,
 }
 
+/// 小計の日付を、文字列で表現する際の形式を列挙しています。
+///
+/// デシリアライズする際は、いずれの形式も受け付けます。
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum DateRepresentation {
+    /// 日付のみで表現します。(例: `2020/03/25`)
+    #[default]
+    CalendarDay,
+    /// 日本標準時の0時0分を、協定世界時のRFC3339形式で表現します。(例: `2020-03-24T15:00:00+00:00`)
+    ///
+    /// 以前の形式との互換性のために使用します。
+    Legacy,
+}
+
+impl DateRepresentation {
+    /// 日付の読み書きに使用するコーデックを返却します。
+    pub fn codec(&self) -> &'static DateCodec {
+        match self {
+            DateRepresentation::CalendarDay => &DATE,
+            DateRepresentation::Legacy => &TIMESTAMP,
+        }
+    }
+}
+
+/// 小計の日付の表現形式を指定して、構造体をシリアライズします。
+///
+/// `Summary::with_dates`・`SummaryContent::with_dates`により生成します。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct WithDates<'a, T> {
+    value: &'a T,
+    dates: DateRepresentation,
+}
+
+impl Summary {
+    /// 最終更新日時を、和暦の文字列として返却します。(例: `令和2年3月25日 21時40分`)
+    pub fn era_last_update(&self, digits: Digits) -> Option<String> {
+        format_era_datetime(self.last_update.naive_local(), digits)
+    }
+
+    /// 小計の日付の表現形式を指定して、シリアライズできるようにします。
+    pub fn with_dates(&self, dates: DateRepresentation) -> WithDates<'_, Summary> {
+        WithDates { value: self, dates }
+    }
+}
+
+impl SummaryContent {
+    /// 小計の日付を、日本標準時における和暦の文字列として返却します。(例: `令和2年3月25日`)
+    pub fn era_date(&self, digits: Digits) -> Option<String> {
+        format_era_date(self.date, digits)
+    }
+
+    /// 小計の対象日の、日本標準時における0時0分を返却します。
+    ///
+    /// 以前の`date`フィールドの値(`DateTime<Utc>`)に相当します。
+    pub fn timestamp(&self) -> DateTime<Utc> {
+        self.date
+            .and_time(NaiveTime::MIN)
+            .and_local_timezone(jst())
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    /// 小計の日付の表現形式を指定して、シリアライズできるようにします。
+    pub fn with_dates(&self, dates: DateRepresentation) -> WithDates<'_, SummaryContent> {
+        WithDates { value: self, dates }
+    }
+}
+
 /// Summaryのシリアライズ処理の実装です。
 impl Serialize for Summary {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.with_dates(DateRepresentation::default())
+            .serialize(serializer)
+    }
+}
+
+/// 日付の表現形式を指定した、Summaryのシリアライズ処理の実装です。
+impl Serialize for WithDates<'_, Summary> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
         // 構造体のシリアライズを開始する
-        let mut state = serializer.serialize_struct("Summary", 1).unwrap();
+        let mut state = serializer.serialize_struct("Summary", 2)?;
         // dataフィールドをシリアライズする
-        state.serialize_field("data", &self.data).unwrap();
+        state.serialize_field(
+            "data",
+            &WithDates {
+                value: &self.value.data,
+                dates: self.dates,
+            },
+        )?;
         // last_updateフィールドをシリアライズする
-        state
-            .serialize_field(
-                "last_update",
-                &format!("{}", self.last_update.format(DATETIME_FORMAT).to_string()),
-            )
-            .unwrap();
+        state.serialize_field("last_update", &DATETIME.format(&self.value.last_update))?;
         // ステートを終了し、結果を返却する
         state.end()
     }
@@ -115,7 +203,7 @@ impl<'de> Visitor<'de> for SummaryVisitor {
         let mut last_update = None;
 
         // 連想配列のキーを取得する
-        while let Some(key) = map.next_key::<SummaryField>().unwrap() {
+        while let Some(key) = map.next_key::<SummaryField>()? {
             match key {
                 // SummaryContentの可変長配列を取り出す
                 SummaryField::Data => {
@@ -124,22 +212,15 @@ impl<'de> Visitor<'de> for SummaryVisitor {
                         return Err(DeserializationError::duplicate_field(SUMMARY_FIELDS[0]));
                     }
                     // SummaryContentの可変長配列をパースし、格納する
-                    data = Some(map.next_value::<Vec<SummaryContent>>().unwrap());
+                    data = Some(map.next_value::<Vec<SummaryContent>>()?);
                 }
                 SummaryField::LastUpdate => {
                     // 既にlast_updateに内容が含まれていないか判定
                     if last_update.is_some() {
-                        return Err(DeserializationError::duplicate_field(SUMMARY_FIELDS[0]));
+                        return Err(DeserializationError::duplicate_field(SUMMARY_FIELDS[1]));
                     }
                     // 日付と時刻をパースし、格納する
-                    last_update = Some(
-                        Local
-                            .datetime_from_str(
-                                &map.next_value::<String>().unwrap(),
-                                DATETIME_FORMAT,
-                            )
-                            .unwrap(),
-                    );
+                    last_update = Some(map.next_value_seed(DATETIME.seed())?);
                 }
             }
         }
@@ -156,10 +237,7 @@ impl<'de> Visitor<'de> for SummaryVisitor {
         )?;
 
         // Summaryを返却
-        Ok(Summary {
-            data: data,
-            last_update: last_update,
-        })
+        Ok(Summary { data, last_update })
     }
 }
 
@@ -174,18 +252,37 @@ impl<'de> Deserialize<'de> for Summary {
 
 /// SummaryContentのシリアライズ処理の実装です。
 impl Serialize for SummaryContent {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.with_dates(DateRepresentation::default())
+            .serialize(serializer)
+    }
+}
+
+/// 日付の表現形式を指定した、SummaryContentの一覧のシリアライズ処理の実装です。
+impl Serialize for WithDates<'_, Vec<SummaryContent>> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_seq(self.value.iter().map(|v| v.with_dates(self.dates)))
+    }
+}
+
+/// 日付の表現形式を指定した、SummaryContentのシリアライズ処理の実装です。
+impl Serialize for WithDates<'_, SummaryContent> {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
         // 構造体のシリアライズを開始する
-        let mut state = serializer.serialize_struct("SummaryContent", 1).unwrap();
+        let mut state = serializer.serialize_struct("SummaryContent", 2)?;
         // dateフィールドをシリアライズする
-        state
-            .serialize_field("date", &self.date.to_rfc3339())
-            .unwrap();
+        state.serialize_field("date", &self.dates.codec().format(&self.value.date))?;
         // sumフィールドをシリアライズする
-        state.serialize_field("sum", &self.sum).unwrap();
+        state.serialize_field("sum", &self.value.sum)?;
         // ステートを終了し、結果を返却する
         state.end()
     }
@@ -248,7 +345,7 @@ impl<'de> Visitor<'de> for SummaryContentVisitor {
         let mut sum = None;
 
         // 連想配列のキーを取得する
-        while let Some(key) = map.next_key::<SummaryContentField>().unwrap() {
+        while let Some(key) = map.next_key::<SummaryContentField>()? {
             match key {
                 // dateを取り出す
                 SummaryContentField::Date => {
@@ -258,13 +355,8 @@ impl<'de> Visitor<'de> for SummaryContentVisitor {
                             SUMMARYCONTENT_FIELDS[0],
                         ));
                     }
-                    // 日付と時刻をパースし、格納する
-                    date = Some(
-                        map.next_value::<String>()
-                            .unwrap()
-                            .parse::<DateTime<Utc>>()
-                            .unwrap(),
-                    );
+                    // 日付をパースし、格納する(以前のRFC3339形式も受け付ける)
+                    date = Some(map.next_value_seed(DATE.seed())?);
                 }
                 SummaryContentField::Sum => {
                     // 既にsumに内容が含まれていないか判定
@@ -274,7 +366,7 @@ impl<'de> Visitor<'de> for SummaryContentVisitor {
                         ));
                     }
                     // 整数値をパースし、格納する
-                    sum = Some(map.next_value::<u32>().unwrap());
+                    sum = Some(map.next_value::<u32>()?);
                 }
             }
         }
@@ -291,10 +383,7 @@ impl<'de> Visitor<'de> for SummaryContentVisitor {
         )?;
 
         // Patientsを返却
-        Ok(SummaryContent {
-            date: date,
-            sum: sum,
-        })
+        Ok(SummaryContent { date, sum })
     }
 }
 
@@ -0,0 +1,147 @@
+//! Summary構造体を、日毎の小計を追加しながら組み立てるビルダーを提供します。
+
+use chrono::{DateTime, Local, NaiveDate};
+use std::{
+    error::Error as StdError,
+    fmt::{Display, Error, Formatter},
+};
+
+use super::{Summary, SummaryContent};
+use crate::utils::formats::codec::{DateInput, DATE};
+
+/// Summaryの組み立てに失敗した際のエラーです。
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SummaryBuildError {
+    /// 最終更新日時が指定されていません。
+    MissingLastUpdate,
+    /// 最終更新日時を解釈できません。
+    InvalidLastUpdate(String),
+    /// 小計の日付を解釈できません。
+    InvalidDate {
+        /// 小計の番号(0始まり)です。
+        index: usize,
+        /// エラーの内容です。
+        message: String,
+    },
+    /// 小計の日付が、直前の小計の日付より後になっていません。
+    OutOfOrder {
+        /// 小計の番号(0始まり)です。
+        index: usize,
+        /// 直前の小計の日付です。
+        previous: NaiveDate,
+        /// 小計の日付です。
+        date: NaiveDate,
+    },
+}
+
+impl Display for SummaryBuildError {
+    fn fmt(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        match self {
+            SummaryBuildError::MissingLastUpdate => {
+                write!(formatter, "last_update is not specified")
+            }
+            SummaryBuildError::InvalidLastUpdate(message) => {
+                write!(formatter, "at `last_update`: {}", message)
+            }
+            SummaryBuildError::InvalidDate { index, message } => {
+                write!(formatter, "at `data[{}].date`: {}", index, message)
+            }
+            SummaryBuildError::OutOfOrder {
+                index,
+                previous,
+                date,
+            } => write!(
+                formatter,
+                "at `data[{}].date`: {} is not after {}",
+                index,
+                DATE.format(date),
+                DATE.format(previous)
+            ),
+        }
+    }
+}
+
+impl StdError for SummaryBuildError {}
+
+/// Summary構造体を組み立てるビルダーです。
+///
+/// `Summary::builder`により生成し、`build`で検証した上でSummaryを生成します。
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SummaryBuilder {
+    data: Vec<(Result<NaiveDate, String>, u32)>,
+    last_update: Option<Result<DateTime<Local>, String>>,
+}
+
+impl Summary {
+    /// 空のビルダーを生成します。
+    pub fn builder() -> SummaryBuilder {
+        SummaryBuilder::default()
+    }
+}
+
+impl SummaryBuilder {
+    /// 日毎の小計を追加します。日付は、文字列(`2020/03/25`等)でも指定できます。
+    ///
+    /// 小計は、日付の昇順に追加する必要があります。
+    pub fn day(mut self, date: impl DateInput<NaiveDate>, sum: u32) -> Self {
+        self.data.push((date.into_date(), sum));
+        self
+    }
+
+    /// 小計をまとめて追加します。
+    pub fn contents<I>(mut self, contents: I) -> Self
+    where
+        I: IntoIterator<Item = SummaryContent>,
+    {
+        self.data
+            .extend(contents.into_iter().map(|v| (Ok(v.date), v.sum)));
+        self
+    }
+
+    /// 最終更新日時を指定します。文字列は`2020/03/25 21:40`等の形式で指定できます。
+    pub fn last_update(mut self, last_update: impl DateInput<DateTime<Local>>) -> Self {
+        self.last_update = Some(last_update.into_date());
+        self
+    }
+
+    /// 内容を検証し、Summaryを生成します。
+    pub fn build(self) -> Result<Summary, SummaryBuildError> {
+        let last_update = self
+            .last_update
+            .ok_or(SummaryBuildError::MissingLastUpdate)?
+            .map_err(SummaryBuildError::InvalidLastUpdate)?;
+
+        let mut data: Vec<SummaryContent> = Vec::with_capacity(self.data.len());
+        for (index, (date, sum)) in self.data.into_iter().enumerate() {
+            let date = date.map_err(|message| SummaryBuildError::InvalidDate { index, message })?;
+            // 日付が重複せず、昇順に並んでいるか確認する
+            if let Some(previous) = data.last().map(|v| v.date) {
+                if date <= previous {
+                    return Err(SummaryBuildError::OutOfOrder {
+                        index,
+                        previous,
+                        date,
+                    });
+                }
+            }
+            data.push(SummaryContent { date, sum });
+        }
+
+        Ok(Summary { data, last_update })
+    }
+}
+
+/// Summary構造体を、最終更新日時と日毎の小計の一覧として宣言的に組み立てます。
+///
+/// 日付・日時は、値と文字列(`2020/03/25`・`2020/03/25 21:40`等)のいずれでも指定できます。
+/// 内容は`SummaryBuilder::build`で検証され、`Result<Summary, SummaryBuildError>`を返却します。
+/// (例: `summary!(last_update: "2020/03/25 21:40"; "2020/03/24" => 5, "2020/03/25" => 10)`)
+#[macro_export]
+macro_rules! summary {
+    (last_update: $last_update:expr $(; $($date:expr => $sum:expr),* $(,)?)?) => {
+        $crate::structs::summary::Summary::builder()
+            $($(.day($date, $sum))*)?
+            .last_update($last_update)
+            .build()
+    };
+}
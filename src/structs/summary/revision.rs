@@ -0,0 +1,132 @@
+//! 2つの版のSummaryを比較し、過去の日付の小計の訂正(重複計上の訂正等)を検出します。
+
+use chrono::{DateTime, Local, NaiveDate};
+use std::fmt::{Display, Error, Formatter};
+
+use super::Summary;
+use crate::{
+    analysis::daily_sums,
+    utils::formats::codec::{DATE, DATETIME},
+};
+
+/// 過去の日付の小計の訂正です。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Revision {
+    /// 訂正された小計の日付です。
+    pub date: NaiveDate,
+    /// 訂正前の値です。以前の版に存在しなかった日付の場合は`None`です。
+    pub old: Option<u64>,
+    /// 訂正後の値です。新しい版から削除された日付の場合は`None`です。
+    pub new: Option<u64>,
+}
+
+impl Revision {
+    /// 訂正による値の増減を返却します。存在しない値は0として扱います。
+    pub fn delta(&self) -> i64 {
+        let signed = |value: Option<u64>| i64::try_from(value.unwrap_or(0)).unwrap_or(i64::MAX);
+        signed(self.new) - signed(self.old)
+    }
+}
+
+impl Display for Revision {
+    fn fmt(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        let value = |value: Option<u64>| value.map_or("-".to_string(), |v| v.to_string());
+        write!(
+            formatter,
+            "{}: {} -> {} ({:+})",
+            DATE.format(&self.date),
+            value(self.old),
+            value(self.new),
+            self.delta()
+        )
+    }
+}
+
+/// 2つの版のSummaryの間で訂正された、過去の日付の小計の一覧です。
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SummaryRevisions {
+    /// 以前の版の最終更新日時です。
+    pub previous_last_update: DateTime<Local>,
+    /// 新しい版の最終更新日時です。
+    pub current_last_update: DateTime<Local>,
+    /// 訂正された小計を、日付の昇順に格納します。
+    pub revisions: Vec<Revision>,
+    /// 以前の版の期間における、以前の版の累計です。
+    pub previous_total: u64,
+    /// 以前の版の期間における、新しい版の累計です。
+    pub revised_total: u64,
+}
+
+impl SummaryRevisions {
+    /// 訂正が存在しないかを返却します。
+    pub fn is_empty(&self) -> bool {
+        self.revisions.is_empty()
+    }
+
+    /// 訂正による累計の増減を返却します。
+    pub fn net_effect(&self) -> i64 {
+        self.revisions.iter().map(Revision::delta).sum()
+    }
+}
+
+impl Display for SummaryRevisions {
+    fn fmt(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        writeln!(
+            formatter,
+            "{} -> {}: {} revision(s), total {} -> {} ({:+})",
+            DATETIME.format(&self.previous_last_update),
+            DATETIME.format(&self.current_last_update),
+            self.revisions.len(),
+            self.previous_total,
+            self.revised_total,
+            self.net_effect()
+        )?;
+        for revision in &self.revisions {
+            writeln!(formatter, "  {}", revision)?;
+        }
+        Ok(())
+    }
+}
+
+impl Summary {
+    /// 以前の版のSummaryと比較し、以前の版の期間内で値が変化した日付を返却します。
+    ///
+    /// 以前の版の最後の日付より後の小計は、新たに追加されたものとして扱い、訂正には含めません。
+    /// 同じ日付の小計が複数存在する場合は、その合計を比較します。
+    pub fn revisions_since(&self, previous: &Summary) -> SummaryRevisions {
+        let old = daily_sums(previous);
+        let Some(last) = old.keys().next_back().copied() else {
+            return SummaryRevisions {
+                previous_last_update: previous.last_update,
+                current_last_update: self.last_update,
+                revisions: Vec::new(),
+                previous_total: 0,
+                revised_total: 0,
+            };
+        };
+        let mut new = daily_sums(self);
+        new.retain(|date, _| *date <= last);
+
+        // 両方の版の日付を昇順に走査し、値が異なる日付を訂正として記録する
+        let mut dates = old.keys().chain(new.keys()).copied().collect::<Vec<_>>();
+        dates.sort();
+        dates.dedup();
+        let revisions = dates
+            .into_iter()
+            .map(|date| Revision {
+                date,
+                old: old.get(&date).copied(),
+                new: new.get(&date).copied(),
+            })
+            .filter(|revision| revision.old != revision.new)
+            .collect();
+
+        SummaryRevisions {
+            previous_last_update: previous.last_update,
+            current_last_update: self.last_update,
+            revisions,
+            previous_total: old.values().sum(),
+            revised_total: new.values().sum(),
+        }
+    }
+}
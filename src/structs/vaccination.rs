@@ -0,0 +1,726 @@
+//! ワクチン接種の実績を格納する構造体を定義しています。
+//!
+//! 日毎の接種回数を接種の回次(1回目・2回目・3回目・追加接種)ごとに保持し、年代ごとの累計の接種率とともに読み書きします。
+
+use chrono::{DateTime, Local, NaiveDate};
+use serde::{
+    de::{Error as DeserializationError, MapAccess, Visitor},
+    ser::SerializeStruct,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::{
+    error::Error as StdError,
+    fmt::{Display, Error, Formatter},
+    str::FromStr,
+};
+
+use crate::{
+    population::{age_band::AgeBand, PopulationDataset, PopulationError},
+    utils::formats::codec::{DATE, DATETIME},
+};
+
+/// Vaccinationをシリアライズする際のフィールド名です。
+pub(crate) const VACCINATION_FIELDS: &[&str; 3] = &["data", "coverage", "last_update"];
+/// VaccinationContentをシリアライズする際のフィールド名です。
+pub(crate) const VACCINATION_CONTENT_FIELDS: &[&str; 5] =
+    &["date", "first", "second", "third", "booster"];
+/// VaccinationCoverageをシリアライズする際のフィールド名です。
+pub(crate) const VACCINATION_COVERAGE_FIELDS: &[&str; 5] =
+    &["age_group", "first", "second", "third", "booster"];
+
+/// VaccinationContent構造体のフィールド名です。
+enum VaccinationContentField {
+    Date,
+    Dose(Dose),
+}
+
+/// VaccinationCoverage構造体のフィールド名です。
+enum VaccinationCoverageField {
+    AgeGroup,
+    Dose(Dose),
+}
+
+/// Vaccination構造体のフィールド名です。
+enum VaccinationField {
+    Data,
+    Coverage,
+    LastUpdate,
+}
+
+/// ワクチン接種の回次です。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Dose {
+    /// 1回目の接種です。
+    First,
+    /// 2回目の接種です。
+    Second,
+    /// 3回目の接種です。
+    Third,
+    /// 4回目以降の追加接種です。
+    Booster,
+}
+
+impl Dose {
+    /// 全ての回次を、接種の順に格納しています。
+    pub const ALL: [Dose; 4] = [Dose::First, Dose::Second, Dose::Third, Dose::Booster];
+
+    /// シリアライズした際のフィールド名を返却します。
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Dose::First => "first",
+            Dose::Second => "second",
+            Dose::Third => "third",
+            Dose::Booster => "booster",
+        }
+    }
+}
+
+impl Display for Dose {
+    fn fmt(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        write!(formatter, "{}", self.as_str())
+    }
+}
+
+/// 回次ごとの値です。
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct PerDose<T> {
+    /// 1回目の接種の値です。
+    pub first: T,
+    /// 2回目の接種の値です。
+    pub second: T,
+    /// 3回目の接種の値です。
+    pub third: T,
+    /// 4回目以降の追加接種の値です。
+    pub booster: T,
+}
+
+impl<T: Copy> PerDose<T> {
+    /// 指定された回次の値を返却します。
+    pub fn get(&self, dose: Dose) -> T {
+        match dose {
+            Dose::First => self.first,
+            Dose::Second => self.second,
+            Dose::Third => self.third,
+            Dose::Booster => self.booster,
+        }
+    }
+
+    /// 指定された回次の値への参照を返却します。
+    fn get_mut(&mut self, dose: Dose) -> &mut T {
+        match dose {
+            Dose::First => &mut self.first,
+            Dose::Second => &mut self.second,
+            Dose::Third => &mut self.third,
+            Dose::Booster => &mut self.booster,
+        }
+    }
+
+    /// 各回次の値を変換します。
+    pub fn map<U>(&self, f: impl Fn(T) -> U) -> PerDose<U> {
+        PerDose {
+            first: f(self.first),
+            second: f(self.second),
+            third: f(self.third),
+            booster: f(self.booster),
+        }
+    }
+}
+
+/// 接種率を集計する年代の区分です。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CoverageGroup {
+    /// 全ての年代です。
+    All,
+    /// 特定の年代です。
+    Band(AgeBand),
+}
+
+impl CoverageGroup {
+    /// 対策サイトで使用する、区分の表記を返却します。
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CoverageGroup::All => "全体",
+            CoverageGroup::Band(band) => band.as_str(),
+        }
+    }
+}
+
+impl Display for CoverageGroup {
+    fn fmt(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        write!(formatter, "{}", self.as_str())
+    }
+}
+
+/// 接種率の区分として解釈できない文字列です。
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct UnknownCoverageGroup(pub String);
+
+impl Display for UnknownCoverageGroup {
+    fn fmt(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        write!(formatter, "unknown coverage group `{}`", self.0)
+    }
+}
+
+impl StdError for UnknownCoverageGroup {}
+
+impl FromStr for CoverageGroup {
+    type Err = UnknownCoverageGroup;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if value == CoverageGroup::All.as_str() {
+            return Ok(CoverageGroup::All);
+        }
+        value
+            .parse::<AgeBand>()
+            .map(CoverageGroup::Band)
+            .map_err(|_| UnknownCoverageGroup(value.to_string()))
+    }
+}
+
+/// CoverageGroupのシリアライズ処理の実装です。
+impl Serialize for CoverageGroup {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+/// CoverageGroupのデシリアライズ処理の実装です。
+impl<'de> Deserialize<'de> for CoverageGroup {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+        value.parse().map_err(DeserializationError::custom)
+    }
+}
+
+/// ある日付の、回次ごとの接種回数です。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct VaccinationContent {
+    /// 接種日です。日本標準時における日付として扱います。
+    pub date: NaiveDate,
+    /// 回次ごとの接種回数です。
+    pub doses: PerDose<u32>,
+}
+
+impl VaccinationContent {
+    /// 全ての回次の接種回数の合計を返却します。
+    pub fn total(&self) -> u64 {
+        Dose::ALL
+            .iter()
+            .map(|dose| u64::from(self.doses.get(*dose)))
+            .sum()
+    }
+}
+
+/// ある年代の、回次ごとの累計の接種率です。
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct VaccinationCoverage {
+    /// 年代の区分です。
+    pub age_group: CoverageGroup,
+    /// 回次ごとの接種率(%)です。
+    pub rates: PerDose<f64>,
+}
+
+impl VaccinationCoverage {
+    /// 回次ごとの累計の接種回数と人口から、接種率を求めます。人口が0の場合は`None`を返却します。
+    pub fn from_counts(
+        age_group: CoverageGroup,
+        counts: PerDose<u64>,
+        population: u64,
+    ) -> Option<VaccinationCoverage> {
+        (population > 0).then(|| VaccinationCoverage {
+            age_group,
+            rates: counts.map(|count| count as f64 * 100.0 / population as f64),
+        })
+    }
+}
+
+/// ワクチン接種の実績です。
+#[derive(Clone, Debug, PartialEq)]
+pub struct Vaccination {
+    /// 日毎の接種回数を、日付の昇順に格納します。
+    pub data: Vec<VaccinationContent>,
+    /// 年代ごとの累計の接種率です。
+    pub coverage: Vec<VaccinationCoverage>,
+    pub last_update: DateTime<Local>,
+}
+
+impl Vaccination {
+    /// 回次ごとの、接種回数の累計を返却します。
+    pub fn cumulative(&self) -> PerDose<u64> {
+        self.data
+            .iter()
+            .fold(PerDose::default(), |sum, content| PerDose {
+                first: sum.first + u64::from(content.doses.first),
+                second: sum.second + u64::from(content.doses.second),
+                third: sum.third + u64::from(content.doses.third),
+                booster: sum.booster + u64::from(content.doses.booster),
+            })
+    }
+
+    /// 指定された年代の区分の接種率を返却します。
+    pub fn coverage_of(&self, age_group: CoverageGroup) -> Option<&VaccinationCoverage> {
+        self.coverage
+            .iter()
+            .find(|coverage| coverage.age_group == age_group)
+    }
+
+    /// 接種回数の累計と、地域の総人口から、全ての年代の接種率を求めます。
+    pub fn overall_coverage(
+        &self,
+        dataset: &PopulationDataset,
+        region: &str,
+    ) -> Result<VaccinationCoverage, PopulationError> {
+        let population = dataset.population(region, None)?;
+        VaccinationCoverage::from_counts(CoverageGroup::All, self.cumulative(), population)
+            .ok_or_else(|| PopulationError::ZeroPopulation {
+                region: region.to_string(),
+                age_band: None,
+            })
+    }
+}
+
+/// VaccinationContentのシリアライズ処理の実装です。
+impl Serialize for VaccinationContent {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("VaccinationContent", 5)?;
+        // 日付は、小計と同じ書式で出力する
+        state.serialize_field("date", &DATE.format(&self.date))?;
+        state.serialize_field("first", &self.doses.first)?;
+        state.serialize_field("second", &self.doses.second)?;
+        state.serialize_field("third", &self.doses.third)?;
+        state.serialize_field("booster", &self.doses.booster)?;
+        state.end()
+    }
+}
+
+/// VaccinationCoverageのシリアライズ処理の実装です。
+impl Serialize for VaccinationCoverage {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("VaccinationCoverage", 5)?;
+        state.serialize_field("age_group", &self.age_group)?;
+        state.serialize_field("first", &self.rates.first)?;
+        state.serialize_field("second", &self.rates.second)?;
+        state.serialize_field("third", &self.rates.third)?;
+        state.serialize_field("booster", &self.rates.booster)?;
+        state.end()
+    }
+}
+
+/// Vaccinationのシリアライズ処理の実装です。
+impl Serialize for Vaccination {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("Vaccination", 3)?;
+        state.serialize_field("data", &self.data)?;
+        state.serialize_field("coverage", &self.coverage)?;
+        state.serialize_field("last_update", &DATETIME.format(&self.last_update))?;
+        state.end()
+    }
+}
+
+/// 年代ごとの接種率の一覧に、同じ区分が重複して含まれていないか検証します。
+fn check_coverage_groups<E>(coverage: &[VaccinationCoverage]) -> Result<(), E>
+where
+    E: DeserializationError,
+{
+    for (index, content) in coverage.iter().enumerate() {
+        if coverage[..index]
+            .iter()
+            .any(|previous| previous.age_group == content.age_group)
+        {
+            return Err(DeserializationError::custom(format!(
+                "duplicate coverage group `{}`",
+                content.age_group
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// 1回目・2回目の接種回数が含まれているか検証し、回次ごとの接種回数を返却します。
+///
+/// 3回目以降の接種が始まる前のデータファイルは、該当するフィールドを持たないため、0として扱います。
+fn require_doses<E>(doses: PerDose<Option<u32>>) -> Result<PerDose<u32>, E>
+where
+    E: DeserializationError,
+{
+    for dose in [Dose::First, Dose::Second] {
+        if doses.get(dose).is_none() {
+            return Err(DeserializationError::missing_field(dose.as_str()));
+        }
+    }
+    Ok(doses.map(|count| count.unwrap_or(0)))
+}
+
+/// 接種率(%)が、0以上100以下の有限の値であるか検証します。
+fn check_rate<E>(dose: Dose, rate: f64) -> Result<f64, E>
+where
+    E: DeserializationError,
+{
+    if !(0.0..=100.0).contains(&rate) {
+        return Err(DeserializationError::custom(format!(
+            "coverage rate `{}` of `{}` must be between 0 and 100",
+            rate, dose
+        )));
+    }
+    Ok(rate)
+}
+
+/// 回次のフィールド名から、回次を返却します。
+fn dose_field(value: &str) -> Option<Dose> {
+    Dose::ALL.into_iter().find(|dose| dose.as_str() == value)
+}
+
+/// VaccinationContentFieldのVisitorを定義します。
+///
+/// ※この構造体は、Visitorトレイトを実装することを意図しています。
+struct VaccinationContentFieldVisitor;
+
+impl<'de> Visitor<'de> for VaccinationContentFieldVisitor {
+    type Value = VaccinationContentField;
+
+    fn expecting(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        write!(
+            formatter,
+            "`date`, `first`, `second`, `third` or `booster` not found"
+        )
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: DeserializationError,
+    {
+        match value {
+            "date" => Ok(VaccinationContentField::Date),
+            _ => dose_field(value)
+                .map(VaccinationContentField::Dose)
+                .ok_or_else(|| {
+                    DeserializationError::unknown_field(value, VACCINATION_CONTENT_FIELDS)
+                }),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for VaccinationContentField {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as Deserializer<'de>>::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_identifier(VaccinationContentFieldVisitor)
+    }
+}
+
+/// VaccinationContentのVisitorを定義します。
+///
+/// ※この構造体は、Visitorトレイトを実装することを意図しています。
+struct VaccinationContentVisitor;
+
+impl<'de> Visitor<'de> for VaccinationContentVisitor {
+    type Value = VaccinationContent;
+
+    fn expecting(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        write!(formatter, "format is not correct.")
+    }
+
+    fn visit_map<M>(self, mut map: M) -> Result<Self::Value, <M as MapAccess<'de>>::Error>
+    where
+        M: MapAccess<'de>,
+    {
+        let mut date = None;
+        let mut doses = PerDose::<Option<u32>>::default();
+
+        // 連想配列のキーを取得する
+        while let Some(key) = map.next_key::<VaccinationContentField>()? {
+            match key {
+                VaccinationContentField::Date => {
+                    // 既にdateに内容が含まれていないか判定
+                    if date.is_some() {
+                        return Err(DeserializationError::duplicate_field(
+                            VACCINATION_CONTENT_FIELDS[0],
+                        ));
+                    }
+                    // 日付をパースし、格納する
+                    date = Some(map.next_value_seed(DATE.seed())?);
+                }
+                VaccinationContentField::Dose(dose) => {
+                    // 既に同じ回次に内容が含まれていないか判定
+                    let count = doses.get_mut(dose);
+                    if count.is_some() {
+                        return Err(DeserializationError::duplicate_field(dose.as_str()));
+                    }
+                    // 接種回数をパースし、格納する
+                    *count = Some(map.next_value::<u32>()?);
+                }
+            }
+        }
+
+        // dateの中身を取り出す
+        let date = date.ok_or_else(
+            // フィールドが不足していることを伝える
+            || DeserializationError::missing_field(VACCINATION_CONTENT_FIELDS[0]),
+        )?;
+
+        // VaccinationContentを返却する
+        Ok(VaccinationContent {
+            date,
+            doses: require_doses(doses)?,
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for VaccinationContent {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as Deserializer<'de>>::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            "VaccinationContent",
+            VACCINATION_CONTENT_FIELDS,
+            VaccinationContentVisitor,
+        )
+    }
+}
+
+/// VaccinationCoverageFieldのVisitorを定義します。
+///
+/// ※この構造体は、Visitorトレイトを実装することを意図しています。
+struct VaccinationCoverageFieldVisitor;
+
+impl<'de> Visitor<'de> for VaccinationCoverageFieldVisitor {
+    type Value = VaccinationCoverageField;
+
+    fn expecting(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        write!(
+            formatter,
+            "`age_group`, `first`, `second`, `third` or `booster` not found"
+        )
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: DeserializationError,
+    {
+        match value {
+            "age_group" => Ok(VaccinationCoverageField::AgeGroup),
+            _ => dose_field(value)
+                .map(VaccinationCoverageField::Dose)
+                .ok_or_else(|| {
+                    DeserializationError::unknown_field(value, VACCINATION_COVERAGE_FIELDS)
+                }),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for VaccinationCoverageField {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as Deserializer<'de>>::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_identifier(VaccinationCoverageFieldVisitor)
+    }
+}
+
+/// VaccinationCoverageのVisitorを定義します。
+///
+/// ※この構造体は、Visitorトレイトを実装することを意図しています。
+struct VaccinationCoverageVisitor;
+
+impl<'de> Visitor<'de> for VaccinationCoverageVisitor {
+    type Value = VaccinationCoverage;
+
+    fn expecting(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        write!(formatter, "format is not correct.")
+    }
+
+    fn visit_map<M>(self, mut map: M) -> Result<Self::Value, <M as MapAccess<'de>>::Error>
+    where
+        M: MapAccess<'de>,
+    {
+        let mut age_group = None;
+        let mut rates = PerDose::<Option<f64>>::default();
+
+        // 連想配列のキーを取得する
+        while let Some(key) = map.next_key::<VaccinationCoverageField>()? {
+            match key {
+                VaccinationCoverageField::AgeGroup => {
+                    // 既にage_groupに内容が含まれていないか判定
+                    if age_group.is_some() {
+                        return Err(DeserializationError::duplicate_field(
+                            VACCINATION_COVERAGE_FIELDS[0],
+                        ));
+                    }
+                    // 年代の区分をパースし、格納する
+                    age_group = Some(map.next_value::<CoverageGroup>()?);
+                }
+                VaccinationCoverageField::Dose(dose) => {
+                    // 既に同じ回次に内容が含まれていないか判定
+                    let rate = rates.get_mut(dose);
+                    if rate.is_some() {
+                        return Err(DeserializationError::duplicate_field(dose.as_str()));
+                    }
+                    // 接種率をパースし、範囲を検証した上で格納する
+                    *rate = Some(check_rate(dose, map.next_value::<f64>()?)?);
+                }
+            }
+        }
+
+        // age_groupの中身を取り出す
+        let age_group = age_group.ok_or_else(
+            // フィールドが不足していることを伝える
+            || DeserializationError::missing_field(VACCINATION_COVERAGE_FIELDS[0]),
+        )?;
+
+        // VaccinationCoverageを返却する
+        Ok(VaccinationCoverage {
+            age_group,
+            // 接種が始まる前の回次は、フィールドを持たないため、0として扱う
+            rates: rates.map(|rate| rate.unwrap_or(0.0)),
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for VaccinationCoverage {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as Deserializer<'de>>::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct(
+            "VaccinationCoverage",
+            VACCINATION_COVERAGE_FIELDS,
+            VaccinationCoverageVisitor,
+        )
+    }
+}
+
+/// VaccinationFieldのVisitorを定義します。
+///
+/// ※この構造体は、Visitorトレイトを実装することを意図しています。
+struct VaccinationFieldVisitor;
+
+impl<'de> Visitor<'de> for VaccinationFieldVisitor {
+    type Value = VaccinationField;
+
+    fn expecting(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        write!(formatter, "`data`, `coverage` or `last_update` not found")
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+    where
+        E: DeserializationError,
+    {
+        match value {
+            "data" => Ok(VaccinationField::Data),
+            "coverage" => Ok(VaccinationField::Coverage),
+            "last_update" => Ok(VaccinationField::LastUpdate),
+            _ => Err(DeserializationError::unknown_field(
+                value,
+                VACCINATION_FIELDS,
+            )),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for VaccinationField {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as Deserializer<'de>>::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_identifier(VaccinationFieldVisitor)
+    }
+}
+
+/// VaccinationのVisitorを定義します。
+///
+/// ※この構造体は、Visitorトレイトを実装することを意図しています。
+struct VaccinationVisitor;
+
+impl<'de> Visitor<'de> for VaccinationVisitor {
+    type Value = Vaccination;
+
+    fn expecting(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        write!(formatter, "format is not correct.")
+    }
+
+    fn visit_map<M>(self, mut map: M) -> Result<Self::Value, <M as MapAccess<'de>>::Error>
+    where
+        M: MapAccess<'de>,
+    {
+        let mut data = None;
+        let mut coverage = None;
+        let mut last_update = None;
+
+        // 連想配列のキーを取得する
+        while let Some(key) = map.next_key::<VaccinationField>()? {
+            match key {
+                VaccinationField::Data => {
+                    // 既にdataに内容が含まれていないか判定
+                    if data.is_some() {
+                        return Err(DeserializationError::duplicate_field(VACCINATION_FIELDS[0]));
+                    }
+                    // 日毎の接種回数をパースし、格納する
+                    data = Some(map.next_value::<Vec<VaccinationContent>>()?);
+                }
+                VaccinationField::Coverage => {
+                    // 既にcoverageに内容が含まれていないか判定
+                    if coverage.is_some() {
+                        return Err(DeserializationError::duplicate_field(VACCINATION_FIELDS[1]));
+                    }
+                    // 年代ごとの接種率をパースし、区分が重複していないか検証した上で格納する
+                    let contents = map.next_value::<Vec<VaccinationCoverage>>()?;
+                    check_coverage_groups(&contents)?;
+                    coverage = Some(contents);
+                }
+                VaccinationField::LastUpdate => {
+                    // 既にlast_updateに内容が含まれていないか判定
+                    if last_update.is_some() {
+                        return Err(DeserializationError::duplicate_field(VACCINATION_FIELDS[2]));
+                    }
+                    // 日付と時刻をパースし、格納する
+                    last_update = Some(map.next_value_seed(DATETIME.seed())?);
+                }
+            }
+        }
+
+        // dataの中身を取り出す
+        let data = data.ok_or_else(
+            // フィールドが不足していることを伝える
+            || DeserializationError::missing_field(VACCINATION_FIELDS[0]),
+        )?;
+        // last_updateの中身を取り出す
+        let last_update = last_update.ok_or_else(
+            // フィールドが不足していることを伝える
+            || DeserializationError::missing_field(VACCINATION_FIELDS[2]),
+        )?;
+
+        // Vaccinationを返却する
+        Ok(Vaccination {
+            data,
+            // 接種率を公表していない場合は、空の一覧として扱う
+            coverage: coverage.unwrap_or_default(),
+            last_update,
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for Vaccination {
+    fn deserialize<D>(deserializer: D) -> Result<Self, <D as Deserializer<'de>>::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_struct("Vaccination", VACCINATION_FIELDS, VaccinationVisitor)
+    }
+}
@@ -1,2 +1,36 @@
 #[cfg(test)]
+pub mod age_distribution_test;
+#[cfg(test)]
+pub mod analysis_test;
+#[cfg(test)]
+pub mod archive_test;
+#[cfg(test)]
+pub mod builder_test;
+#[cfg(test)]
+pub mod convert_test;
+#[cfg(test)]
+pub mod document_test;
+#[cfg(test)]
+pub mod feed_test;
+#[cfg(test)]
+pub mod formats_test;
+#[cfg(test)]
+pub mod migration_test;
+#[cfg(test)]
+pub mod news_test;
+#[cfg(test)]
+pub mod population_test;
+#[cfg(test)]
+pub mod revision_test;
+#[cfg(test)]
+pub mod roundtrip_test;
+#[cfg(all(test, feature = "store"))]
+pub mod store_test;
+#[cfg(test)]
+pub mod stream_test;
+#[cfg(test)]
 pub mod structs_test;
+#[cfg(test)]
+pub mod typescript_test;
+#[cfg(test)]
+pub mod vaccination_test;
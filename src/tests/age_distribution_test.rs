@@ -0,0 +1,185 @@
+use crate::{
+    population::{age_band::AgeBand, PopulationDataset, PopulationError, Populations, PREFECTURE},
+    structs::age_distribution::{AgeDistribution, AgeGroup, PatientRecord},
+    tests::structs_test::dummy_localdate,
+};
+use chrono::NaiveDate;
+
+/// 陽性者の個票のテスト用のデータを生成します。
+pub(crate) fn test_data_patient_records() -> Vec<PatientRecord> {
+    [
+        ("2020/03/24", Some(25)),
+        ("2020/03/24", Some(27)),
+        ("2020/03/24", None),
+        ("2020/03/25", Some(8)),
+        ("2020/03/25", Some(93)),
+        ("2020/03/25", Some(21)),
+    ]
+    .into_iter()
+    .map(|(date, age)| PatientRecord {
+        date: NaiveDate::parse_from_str(date, "%Y/%m/%d").unwrap(),
+        age_group: AgeGroup::of(age),
+    })
+    .collect()
+}
+
+/// AgeDistribution構造体のテスト用のデータを生成します。
+pub(crate) fn test_data_age_distribution() -> AgeDistribution {
+    AgeDistribution::from_records(&test_data_patient_records(), dummy_localdate(), true)
+}
+
+/// 年代の区分の表記をテストします。
+#[test]
+fn age_distribution_age_group() {
+    assert_eq!(AgeGroup::of(None), AgeGroup::Undisclosed);
+    assert_eq!(AgeGroup::of(Some(9)), AgeGroup::Band(AgeBand::Under10));
+    assert_eq!(AgeGroup::Undisclosed.to_string(), "非公表");
+    for age_group in AgeGroup::ALL {
+        assert_eq!(age_group.as_str().parse::<AgeGroup>().unwrap(), age_group);
+    }
+    assert!("不明".parse::<AgeGroup>().is_err());
+}
+
+/// 陽性者の個票から、年代別陽性者数と日毎の内訳が集計されることをテストします。
+#[test]
+fn age_distribution_from_records() {
+    let distribution = test_data_age_distribution();
+    assert_eq!(distribution.data.len(), AgeGroup::ALL.len());
+    assert_eq!(distribution.count(AgeGroup::Band(AgeBand::Twenties)), 3);
+    assert_eq!(distribution.count(AgeGroup::Band(AgeBand::Over90)), 1);
+    assert_eq!(distribution.count(AgeGroup::Undisclosed), 1);
+    assert_eq!(distribution.count(AgeGroup::Band(AgeBand::Teens)), 0);
+    assert_eq!(distribution.total(), 6);
+
+    let daily = distribution.daily.as_ref().unwrap();
+    assert_eq!(daily.len(), 2);
+    assert_eq!(daily[0].date, NaiveDate::from_ymd_opt(2020, 3, 24).unwrap());
+    assert_eq!(
+        daily[0]
+            .data
+            .iter()
+            .map(|content| content.count)
+            .sum::<u32>(),
+        3
+    );
+
+    let without_daily =
+        AgeDistribution::from_records(&test_data_patient_records(), dummy_localdate(), false);
+    assert_eq!(without_daily.daily, None);
+    assert_eq!(without_daily.data, distribution.data);
+}
+
+/// 年代別陽性者数のシリアライズ・デシリアライズをテストします。
+#[test]
+fn age_distribution_serde() {
+    let distribution = test_data_age_distribution();
+    let json = serde_json::to_value(&distribution).unwrap();
+    assert_eq!(json["data"][0]["age_group"], "10歳未満");
+    assert_eq!(json["data"][10]["age_group"], "非公表");
+    assert_eq!(json["daily"][1]["date"], "2020/03/25");
+    assert_eq!(json["last_update"], "2020/03/25 21:40");
+    assert_eq!(
+        serde_json::from_value::<AgeDistribution>(json).unwrap(),
+        distribution
+    );
+
+    // 日毎の内訳は省略できる
+    let json = r#"{"data":[{"age_group":"20代","count":2}],"last_update":"2020/03/25 21:40"}"#;
+    let distribution = serde_json::from_str::<AgeDistribution>(json).unwrap();
+    assert_eq!(distribution.daily, None);
+    assert_eq!(serde_json::to_string(&distribution).unwrap(), json);
+
+    assert!(serde_json::from_str::<AgeDistribution>(
+        r#"{"data":[{"age_group":"100代","count":2}],"last_update":"2020/03/25 21:40"}"#
+    )
+    .is_err());
+
+    let record =
+        serde_json::from_str::<PatientRecord>(r#"{"date":"2020-03-25","age_group":"非公表"}"#)
+            .unwrap();
+    assert_eq!(record.age_group, AgeGroup::Undisclosed);
+    assert_eq!(
+        serde_json::to_string(&record).unwrap(),
+        r#"{"date":"2020/03/25","age_group":"非公表"}"#
+    );
+}
+
+/// 同じ区分が重複している年代別陽性者数が、エラーとなることをテストします。
+#[test]
+fn age_distribution_duplicate_age_group() {
+    let json = r#"{"data":[{"age_group":"20代","count":2},{"age_group":"20代","count":3}],"last_update":"2020/03/25 21:40"}"#;
+    let error = serde_json::from_str::<AgeDistribution>(json).unwrap_err();
+    assert!(error.to_string().contains("duplicate age group `20代`"));
+
+    let json = r#"{"data":[],"daily":[{"date":"2020/03/25","data":[{"age_group":"非公表","count":1},{"age_group":"非公表","count":1}]}],"last_update":"2020/03/25 21:40"}"#;
+    let error = serde_json::from_str::<AgeDistribution>(json).unwrap_err();
+    assert!(error.to_string().contains("duplicate age group `非公表`"));
+
+    // 重複したフィールドは、フィールド名とともに報告される
+    let json = r#"{"data":[],"data":[],"last_update":"2020/03/25 21:40"}"#;
+    let error = serde_json::from_str::<AgeDistribution>(json).unwrap_err();
+    assert!(error.to_string().contains("duplicate field `data`"));
+}
+
+/// 年代ごとの人口10万人あたりの陽性者数をテストします。
+#[test]
+fn age_distribution_incidence() {
+    // テスト用の人口です。(実際の人口ではありません)
+    let mut csv = "region,age_band,population\n京都府,,1000000\n".to_string();
+    for age_band in AgeBand::ALL {
+        csv.push_str(&format!("京都府,{},100000\n", age_band));
+    }
+    let dataset = PopulationDataset::from_csv(2020, "テスト", csv.as_bytes()).unwrap();
+
+    let incidence = test_data_age_distribution()
+        .incidence(&dataset, PREFECTURE)
+        .unwrap();
+    assert_eq!(incidence.len(), AgeBand::ALL.len());
+    assert_eq!(incidence[2].age_band, AgeBand::Twenties);
+    assert_eq!(incidence[2].rate, 3.0);
+    assert_eq!(incidence[2].population, 100_000);
+    let json = serde_json::to_value(incidence[9]).unwrap();
+    assert_eq!(json["age_band"], "90歳以上");
+
+    let partial = PopulationDataset::from_csv(
+        2020,
+        "テスト",
+        "region,age_band,population\n京都府,,1000000\n".as_bytes(),
+    )
+    .unwrap();
+    assert!(matches!(
+        test_data_age_distribution().incidence(&partial, PREFECTURE),
+        Err(PopulationError::MissingAgeBand { .. })
+    ));
+}
+
+/// 同梱する人口のデータセットで、年代ごとの人口10万人あたりの陽性者数を求めることをテストします。
+///
+/// 同梱するデータセットは年代ごとの人口を含まないため、年代ごとの人口を追加するまではエラーとなります。
+#[test]
+fn age_distribution_incidence_bundled() {
+    let mut populations = Populations::bundled();
+    for year in populations.years().collect::<Vec<u16>>() {
+        let dataset = populations.select(Some(year)).unwrap();
+        for region in [PREFECTURE, "京都市"] {
+            assert!(matches!(
+                test_data_age_distribution().incidence(dataset, region),
+                Err(PopulationError::MissingAgeBand {
+                    age_band: AgeBand::Under10,
+                    ..
+                })
+            ));
+        }
+    }
+
+    // 年代ごとの人口を含むデータセットを追加すると、換算できる(実際の人口ではありません)
+    let mut csv = "region,age_band,population\n京都府,,2578087\n".to_string();
+    for age_band in AgeBand::ALL {
+        csv.push_str(&format!("京都府,{},200000\n", age_band));
+    }
+    populations.insert(PopulationDataset::from_csv(2020, "テスト", csv.as_bytes()).unwrap());
+    let incidence = test_data_age_distribution()
+        .incidence(populations.select(None).unwrap(), PREFECTURE)
+        .unwrap();
+    assert_eq!(incidence[2].rate, 1.5);
+}
@@ -0,0 +1,353 @@
+use crate::{
+    analysis::{
+        anomaly::AnomalyOptions,
+        daily_values,
+        distribution::{
+            beta_i, gamma_cdf, gamma_quantile, ln_gamma, student_t_cdf, student_t_quantile,
+        },
+        growth::GrowthOptions,
+        rt::{RtOptions, SerialInterval},
+    },
+    structs::summary::{Summary, SummaryContent},
+    summary,
+    tests::structs_test::dummy_localdate,
+};
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+
+/// 2020年4月1日から、指定された値の小計の一覧を生成します。
+fn test_data_series(values: &[u32]) -> Summary {
+    let start = NaiveDate::from_ymd_opt(2020, 4, 1).unwrap();
+    Summary {
+        data: values
+            .iter()
+            .enumerate()
+            .map(|(index, sum)| SummaryContent {
+                date: start + Duration::days(index as i64),
+                sum: *sum,
+            })
+            .collect(),
+        last_update: dummy_localdate(),
+    }
+}
+
+/// 曜日により変動する値の系列を生成します。日曜日と月曜日は、その他の曜日より値が小さくなります。
+fn weekly_values(days: usize) -> Vec<u32> {
+    let start = NaiveDate::from_ymd_opt(2020, 4, 1).unwrap();
+    (0..days)
+        .map(|index| {
+            let date = start + Duration::days(index as i64);
+            // 決定的な揺らぎを加える
+            let noise = (index * 7 % 5) as u32;
+            match date.weekday() {
+                Weekday::Sun => 20 + noise,
+                Weekday::Mon => 40 + noise,
+                _ => 100 + noise * 2,
+            }
+        })
+        .collect()
+}
+
+/// 欠けた日付を0とし、同じ日付の小計を合計した系列が生成されることをテストします。
+#[test]
+fn analysis_daily_values() {
+    let summary = summary!(
+        last_update: "2020/03/28 21:40";
+        "2020/03/25" => 2,
+        "2020/03/27" => 3
+    )
+    .unwrap();
+    let mut summary = summary;
+    summary.data.push(SummaryContent {
+        date: NaiveDate::from_ymd_opt(2020, 3, 27).unwrap(),
+        sum: 4,
+    });
+    assert_eq!(
+        daily_values(&summary)
+            .into_iter()
+            .map(|(_, v)| v)
+            .collect::<Vec<u64>>(),
+        vec![2, 0, 7]
+    );
+    assert!(daily_values(&test_data_series(&[])).is_empty());
+}
+
+/// 桁を誤って入力した値が、外れ値として検出されることをテストします。
+#[test]
+fn analysis_anomalies() {
+    let mut values = weekly_values(56);
+    // 5月13日(水曜日)に、桁を誤って入力する
+    values[42] *= 10;
+    // 5月20日(水曜日)に、桁を欠いて入力する
+    values[49] /= 10;
+    let summary = test_data_series(&values);
+
+    let report = summary.anomalies(&AnomalyOptions::default()).unwrap();
+    assert_eq!(report.evaluated, 56 - 14);
+    assert_eq!(
+        report
+            .anomalies
+            .iter()
+            .map(|anomaly| anomaly.date)
+            .collect::<Vec<NaiveDate>>(),
+        vec![
+            NaiveDate::from_ymd_opt(2020, 5, 13).unwrap(),
+            NaiveDate::from_ymd_opt(2020, 5, 20).unwrap(),
+        ]
+    );
+    assert!(report.anomalies[0].score > 0.0);
+    assert!(report.anomalies[1].score < 0.0);
+    assert!((report.anomalies[0].expected - values[35] as f64).abs() < 10.0);
+    assert!(report.to_string().contains("2020/05/13: "));
+}
+
+/// 曜日による変動を考慮しない場合は、曜日ごとの値の差が外れ値として検出されることをテストします。
+#[test]
+fn analysis_anomalies_seasonality() {
+    let summary = test_data_series(&weekly_values(56));
+    assert!(summary
+        .anomalies(&AnomalyOptions::default())
+        .unwrap()
+        .is_empty());
+
+    let options = AnomalyOptions {
+        seasonality: false,
+        ..AnomalyOptions::default()
+    };
+    let report = summary.anomalies(&options).unwrap();
+    assert!(!report.is_empty());
+    assert!(report.anomalies.iter().all(|anomaly| matches!(
+        anomaly.date.weekday(),
+        Weekday::Sun | Weekday::Mon
+    ) && anomaly.score < 0.0));
+}
+
+/// 一定の値の系列について、値の変化が外れ値として検出されることをテストします。
+#[test]
+fn analysis_anomalies_constant() {
+    let mut values = vec![5; 30];
+    values[28] = 7;
+    values[29] = 50;
+    let report = test_data_series(&values)
+        .anomalies(&AnomalyOptions::default())
+        .unwrap();
+    // 件数の揺らぎの範囲内の変化は、外れ値としない
+    assert_eq!(report.anomalies.len(), 1);
+    assert_eq!(report.anomalies[0].value, 50);
+    assert!((report.anomalies[0].score - 45.0 / 5f64.sqrt()).abs() < 1e-9);
+}
+
+/// 不正な条件について、エラーとなることをテストします。
+#[test]
+fn analysis_anomalies_invalid_options() {
+    let summary = test_data_series(&weekly_values(7));
+    for options in [
+        AnomalyOptions {
+            window: 0,
+            ..AnomalyOptions::default()
+        },
+        AnomalyOptions {
+            min_history: 29,
+            ..AnomalyOptions::default()
+        },
+        AnomalyOptions {
+            threshold: f64::NAN,
+            ..AnomalyOptions::default()
+        },
+    ] {
+        assert!(summary.anomalies(&options).is_err());
+    }
+}
+
+/// 2つの値が、許容誤差の範囲内で一致することを検証します。
+fn assert_close(actual: f64, expected: f64, tolerance: f64) {
+    assert!(
+        (actual - expected).abs() <= tolerance,
+        "{} is not close to {}",
+        actual,
+        expected
+    );
+}
+
+/// ガンマ分布の関数が、既知の値と一致することをテストします。
+#[test]
+fn analysis_gamma_distribution() {
+    assert_close(ln_gamma(5.0), 24f64.ln(), 1e-12);
+    assert_close(ln_gamma(0.5), std::f64::consts::PI.sqrt().ln(), 1e-12);
+    // 形状母数1のガンマ分布は、指数分布となる
+    for x in [0.1, 1.0, 5.0, 30.0] {
+        assert_close(gamma_cdf(x, 1.0, 2.0), 1.0 - (-x / 2.0).exp(), 1e-12);
+    }
+    assert_close(gamma_quantile(0.5, 1.0, 2.0), 2.0 * 2f64.ln(), 1e-9);
+    // 自由度4のカイ二乗分布の97.5%点
+    assert_close(gamma_quantile(0.975, 2.0, 2.0), 11.143287, 1e-6);
+}
+
+/// ガンマ分布を離散化した発症間隔の分布が、指定された平均を持つことをテストします。
+#[test]
+fn analysis_serial_interval() {
+    let weights = SerialInterval::Gamma { mean: 4.7, sd: 2.9 }
+        .weights()
+        .unwrap();
+    assert_eq!(weights[0], 0.0);
+    assert_close(weights.iter().sum::<f64>(), 1.0, 1e-12);
+    let mean = weights
+        .iter()
+        .enumerate()
+        .map(|(day, w)| day as f64 * w)
+        .sum::<f64>();
+    assert_close(mean, 4.7, 1e-3);
+
+    assert_eq!(
+        SerialInterval::Discrete(vec![1.0, 3.0]).weights().unwrap(),
+        vec![0.0, 0.25, 0.75]
+    );
+    assert!(SerialInterval::Gamma { mean: 1.0, sd: 1.0 }
+        .weights()
+        .is_err());
+    assert!(SerialInterval::Discrete(vec![0.0]).weights().is_err());
+    assert!(SerialInterval::Discrete(vec![-1.0, 2.0]).weights().is_err());
+}
+
+/// 指数的に増加する新規感染者数から、理論値どおりの実効再生産数が推定されることをテストします。
+#[test]
+fn analysis_reproduction_numbers() {
+    // 毎日2倍に増加する場合、発症間隔が1日後・2日後に半分ずつであれば、R = 1 / (1/2 * 1/2 + 1/2 * 1/4) = 8/3 となる
+    let values = (0..20).map(|day| 1u32 << day).collect::<Vec<u32>>();
+    let summary = test_data_series(&values);
+    let options = RtOptions::new(SerialInterval::Discrete(vec![0.5, 0.5]));
+    let series = summary.reproduction_numbers(&options).unwrap();
+
+    assert_eq!(series.data.len(), 20 - 7);
+    let last = series.data.last().unwrap();
+    assert_eq!(last.end, NaiveDate::from_ymd_opt(2020, 4, 20).unwrap());
+    assert_eq!(last.start, NaiveDate::from_ymd_opt(2020, 4, 14).unwrap());
+    assert_close(last.mean, 8.0 / 3.0, 1e-4);
+    assert!(last.lower < last.median && last.median < last.upper);
+    assert!(last.lower < 8.0 / 3.0 && 8.0 / 3.0 < last.upper);
+
+    // 同じ入力に対して、常に同じ結果となる
+    assert_eq!(summary.reproduction_numbers(&options).unwrap(), series);
+}
+
+/// 一定の新規感染者数から、1に近い実効再生産数が推定されることをテストします。
+#[test]
+fn analysis_reproduction_numbers_constant() {
+    let summary = test_data_series(&[50; 60]);
+    let options = RtOptions::new(SerialInterval::Gamma { mean: 4.7, sd: 2.9 });
+    let series = summary.reproduction_numbers(&options).unwrap();
+    let last = series.data.last().unwrap();
+    assert_close(last.mean, 1.0, 0.01);
+    assert_eq!(last.incidence, 350);
+
+    let json = serde_json::to_value(&series).unwrap();
+    assert_eq!(json["data"][0]["start"], "2020/04/02");
+    assert_eq!(json["data"][0]["end"], "2020/04/08");
+    assert_eq!(json["credibility"], 0.95);
+    assert_eq!(json["last_update"], "2020/03/25 21:40");
+
+    // 感染者が存在しない期間は推定しない
+    let mut values = vec![0; 10];
+    values.extend([5; 10]);
+    let series = test_data_series(&values)
+        .reproduction_numbers(&options)
+        .unwrap();
+    assert_eq!(
+        series.data[0].start,
+        NaiveDate::from_ymd_opt(2020, 4, 6).unwrap()
+    );
+
+    let invalid = RtOptions {
+        credibility: 1.0,
+        ..options
+    };
+    assert!(summary.reproduction_numbers(&invalid).is_err());
+}
+
+/// t分布の関数が、既知の値と一致することをテストします。
+#[test]
+fn analysis_student_t_distribution() {
+    // I_x(1, 1)は一様分布の累積分布関数となる
+    assert_close(beta_i(1.0, 1.0, 0.3), 0.3, 1e-12);
+    assert_close(beta_i(2.0, 3.0, 0.4), 0.5248, 1e-12);
+    assert_close(student_t_cdf(0.0, 5.0), 0.5, 1e-12);
+    // 自由度1のt分布は、コーシー分布となる
+    assert_close(student_t_cdf(1.0, 1.0), 0.75, 1e-12);
+    assert_close(student_t_quantile(0.975, 1.0), 12.706205, 1e-6);
+    assert_close(student_t_quantile(0.975, 5.0), 2.570582, 1e-6);
+    assert_close(student_t_quantile(0.025, 30.0), -2.042272, 1e-6);
+}
+
+/// 指数的に増加・減少する値から、増加率と倍加時間・半減時間が求められることをテストします。
+#[test]
+fn analysis_growth_rates() {
+    // 3日ごとに2倍となる場合、倍加時間は3日となる
+    let values = (0..10)
+        .map(|day| (100.0 * 2f64.powf(day as f64 / 3.0)).round() as u32)
+        .collect::<Vec<u32>>();
+    let series = test_data_series(&values)
+        .growth_rates(&GrowthOptions::default())
+        .unwrap();
+    assert_eq!(series.data.len(), 10 - 6);
+    let first = series.data[0];
+    assert_eq!(first.start, NaiveDate::from_ymd_opt(2020, 4, 1).unwrap());
+    assert_eq!(first.end, NaiveDate::from_ymd_opt(2020, 4, 7).unwrap());
+    assert_close(first.doubling_time().unwrap(), 3.0, 0.01);
+    let (lower, upper) = first.doubling_time_interval().unwrap();
+    assert!(lower < 3.0 && 3.0 < upper);
+    assert_eq!(first.halving_time(), None);
+    assert_eq!(first.halving_time_interval(), None);
+
+    // 減少する場合は、半減時間を求める
+    let values = (0..7).map(|day| 1000 >> day).collect::<Vec<u32>>();
+    let series = test_data_series(&values)
+        .growth_rates(&GrowthOptions::default())
+        .unwrap();
+    let estimate = series.data[0];
+    assert_close(estimate.halving_time().unwrap(), 1.0, 0.01);
+    assert!(estimate.halving_time_interval().is_some());
+    assert_eq!(estimate.doubling_time(), None);
+
+    let json = serde_json::to_value(&series).unwrap();
+    assert_eq!(json["data"][0]["start"], "2020/04/01");
+    assert_eq!(json["data"][0]["doubling_time"], serde_json::Value::Null);
+    assert_eq!(json["confidence"], 0.95);
+    assert_eq!(json["last_update"], "2020/03/25 21:40");
+}
+
+/// 横ばいの値や0を含む期間について、倍加時間を求めないことをテストします。
+#[test]
+fn analysis_growth_rates_flat() {
+    let options = GrowthOptions {
+        window: 5,
+        ..GrowthOptions::default()
+    };
+    let series = test_data_series(&[10, 12, 9, 11, 10, 0, 10, 11])
+        .growth_rates(&options)
+        .unwrap();
+    // 0を含む期間は推定しない
+    assert_eq!(series.data.len(), 1);
+    let estimate = series.data[0];
+    assert!(estimate.lower < 0.0 && 0.0 < estimate.upper);
+    assert_eq!(estimate.doubling_time_interval(), None);
+    assert_eq!(estimate.halving_time_interval(), None);
+
+    // 同じ入力に対して、常に同じ結果となる
+    let summary = test_data_series(&[3, 5, 4, 8, 9, 7, 12, 15]);
+    assert_eq!(
+        summary.growth_rates(&options).unwrap(),
+        summary.growth_rates(&options).unwrap()
+    );
+
+    for invalid in [
+        GrowthOptions {
+            window: 2,
+            ..options
+        },
+        GrowthOptions {
+            confidence: 0.0,
+            ..options
+        },
+    ] {
+        assert!(summary.growth_rates(&invalid).is_err());
+    }
+}
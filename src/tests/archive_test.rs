@@ -0,0 +1,151 @@
+use crate::{
+    archive::{Archive, ArchiveError},
+    document::Document,
+    status,
+    structs::{status::Attributes, summary::SummaryContent},
+    summary,
+};
+use chrono::NaiveDate;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// テスト用の一時的なディレクトリを作成します。
+fn temp_dir(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!(
+        "covid19-kyoto-archive-{}-{}",
+        name,
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&path);
+    fs::create_dir_all(&path).unwrap();
+    path
+}
+
+/// データファイルを、現在の版の形式で書き出します。
+fn write_document(path: &Path, document: Document) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, document.to_versioned_json_pretty().unwrap()).unwrap();
+}
+
+/// ディレクトリに格納されたスナップショットが、最終更新日時ごとに索引されることをテストします。
+#[test]
+fn archive_load_dir() {
+    let dir = temp_dir("load");
+    let statuses = [
+        ("2020-03-25", "2020-03-25T21:40:00+09:00", 100, 10),
+        ("2020-03-26", "2020-03-26T21:40:00+09:00", 120, 12),
+        ("2020-03-28", "2020-03-28T09:00:00+09:00", 150, 11),
+    ];
+    for (directory, last_update, patients, hospitalizations) in statuses {
+        let status = status!(Patients => patients {
+            Hospitalizations => hospitalizations
+        }; last_update: last_update)
+        .unwrap();
+        write_document(
+            &dir.join(directory).join("main_summary.json"),
+            Document::Status(status),
+        );
+    }
+    let summary = summary!(
+        last_update: "2020-03-26T21:40:00+09:00";
+        "2020/03/25" => 2,
+        "2020/03/26" => 5
+    )
+    .unwrap();
+    write_document(
+        &dir.join("2020-03-26").join("patients_summary.json"),
+        Document::Summary(summary.clone()),
+    );
+    // 同じ内容のスナップショットと、対象外のファイルは無視される
+    fs::copy(
+        dir.join("2020-03-26").join("patients_summary.json"),
+        dir.join("patients_summary_20200326.json"),
+    )
+    .unwrap();
+    fs::write(dir.join("news.json"), "{}").unwrap();
+
+    let archive = Archive::load_dir(&dir);
+    fs::remove_dir_all(&dir).unwrap();
+    let archive = archive.unwrap();
+    assert_eq!(archive.statuses().count(), 3);
+    assert_eq!(archive.summaries().count(), 1);
+
+    assert_eq!(archive.status_on("2020/03/24").unwrap(), None);
+    assert_eq!(archive.status_on("2020/03/25").unwrap().unwrap().value, 100);
+    assert_eq!(archive.status_on("2020/03/27").unwrap().unwrap().value, 120);
+    let site = archive.site_on("2020-03-26").unwrap();
+    assert_eq!(site.status.unwrap().value, 120);
+    assert_eq!(site.summary, Some(&summary));
+    assert_eq!(archive.summary_on("2020/03/25").unwrap(), None);
+    assert!(matches!(
+        archive.site_on("2020/02/30"),
+        Err(ArchiveError::InvalidDate(_))
+    ));
+
+    let history = archive.history(&Attributes::Hospitalizations).unwrap();
+    assert_eq!(
+        history.data,
+        [(25, 10), (26, 12), (28, 11)].map(|(day, sum)| SummaryContent {
+            date: NaiveDate::from_ymd_opt(2020, 3, day).unwrap(),
+            sum,
+        })
+    );
+    assert_eq!(history.last_update, *archive.statuses().last().unwrap().0);
+    assert_eq!(archive.history(&Attributes::Dead), None);
+}
+
+/// 同じ日の複数のスナップショットのうち、最も新しい値が推移に使用されることをテストします。
+#[test]
+fn archive_history_same_day() {
+    let mut archive = Archive::new();
+    for (last_update, value) in [
+        ("2020-03-25T09:00:00+09:00", 1),
+        ("2020-03-25T21:40:00+09:00", 2),
+        ("2020-03-26T00:30:00+09:00", 3),
+    ] {
+        archive
+            .insert_status(status!(Patients => value; last_update: last_update).unwrap())
+            .unwrap();
+    }
+    let history = archive.history(&Attributes::Patients).unwrap();
+    assert_eq!(
+        history.data.iter().map(|v| v.sum).collect::<Vec<u32>>(),
+        vec![2, 3]
+    );
+}
+
+/// 索引できないスナップショットについて、エラーとなることをテストします。
+#[test]
+fn archive_errors() {
+    let mut archive = Archive::new();
+    assert!(matches!(
+        archive.insert_status(status!(Patients => 1).unwrap()),
+        Err(ArchiveError::MissingLastUpdate { path: None })
+    ));
+
+    let dir = temp_dir("conflict");
+    for (name, value) in [("a", 1), ("b", 2)] {
+        let status = status!(Patients => value; last_update: "2020/03/25 21:40").unwrap();
+        write_document(
+            &dir.join(name).join("main_summary.json"),
+            Document::Status(status),
+        );
+    }
+    let error = Archive::load_dir(&dir).unwrap_err();
+    match &error {
+        ArchiveError::Conflict { path, .. } => {
+            assert_eq!(
+                path.as_deref(),
+                Some(dir.join("b").join("main_summary.json").as_path())
+            )
+        }
+        error => panic!("unexpected error: {}", error),
+    }
+
+    fs::write(dir.join("b").join("main_summary.json"), "{").unwrap();
+    let error = Archive::load_dir(&dir).unwrap_err();
+    fs::remove_dir_all(&dir).unwrap();
+    assert!(matches!(error, ArchiveError::Document { .. }));
+}
@@ -0,0 +1,159 @@
+use crate::{
+    status,
+    structs::{
+        news::{
+            builder::NewsItemBuildError,
+            link::{UrlError, UrlPolicy},
+            NewsItem,
+        },
+        status::{builder::StatusBuildError, Attributes, Status},
+        summary::{builder::SummaryBuildError, Summary},
+    },
+    summary,
+    tests::structs_test::{dummy_localdate, test_data_news_item},
+};
+use chrono::NaiveDate;
+
+/// ビルダーで組み立てたStatusが、手作業で生成した場合と一致することをテストします。
+#[test]
+fn builder_status() {
+    let status = Status::builder(Attributes::Patients)
+        .value(4096)
+        .child(Status::builder(Attributes::Accommodations).value(32))
+        .last_update(dummy_localdate())
+        .build()
+        .unwrap();
+    assert_eq!(
+        serde_json::to_string(&status).unwrap(),
+        r#"{"attr":"patients","value":4096,"children":[{"attr":"accommodations","value":32}],"last_update":"2020/03/25 21:40"}"#
+    );
+
+    let status = status!(Patients => 4096 {
+        Hospitalizations => 100 { SeverelyPatients => 3 },
+        Accommodations => 32,
+    }; last_update: "2020/03/25 21:40")
+    .unwrap();
+    assert_eq!(
+        serde_json::to_string(&status).unwrap(),
+        r#"{"attr":"patients","value":4096,"children":[{"attr":"hospitalizations","value":100,"children":[{"attr":"severely_patients","value":3}]},{"attr":"accommodations","value":32}],"last_update":"2020/03/25 21:40"}"#
+    );
+}
+
+/// 不正な内容のStatusが、組み立て時にエラーとなることをテストします。
+#[test]
+fn builder_status_errors() {
+    let error = Status::builder(Attributes::Patients)
+        .value(10)
+        .child(Status::builder(Attributes::Hospitalizations))
+        .build()
+        .unwrap_err();
+    assert_eq!(
+        error,
+        StatusBuildError::MissingValue {
+            path: "patients.hospitalizations".to_string()
+        }
+    );
+
+    let error = status!(Patients => 10 { Home => 1, Home => 2 }).unwrap_err();
+    assert_eq!(
+        error,
+        StatusBuildError::DuplicateChild {
+            path: "patients".to_string(),
+            attr: "home"
+        }
+    );
+
+    let error =
+        status!(Patients => 10 { Hospitalizations => 8 { SeverelyPatients => 9 } }).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "at `patients.hospitalizations`: sum of children (9) exceeds value (8)"
+    );
+
+    let error = status!(Patients => 10; last_update: "2020/03/25").unwrap_err();
+    assert!(matches!(error, StatusBuildError::InvalidLastUpdate { .. }));
+}
+
+/// ビルダーで組み立てたSummaryが、日付の昇順に小計を保持することをテストします。
+#[test]
+fn builder_summary() {
+    let summary = summary!(
+        last_update: "2020/03/25 21:40";
+        "2020/03/24" => 5,
+        NaiveDate::from_ymd_opt(2020, 3, 25).unwrap() => 10,
+    )
+    .unwrap();
+    assert_eq!(
+        serde_json::to_string(&summary).unwrap(),
+        r#"{"data":[{"date":"2020/03/24","sum":5},{"date":"2020/03/25","sum":10}],"last_update":"2020/03/25 21:40"}"#
+    );
+    assert!(summary!(last_update: dummy_localdate())
+        .unwrap()
+        .data
+        .is_empty());
+}
+
+/// 不正な内容のSummaryが、組み立て時にエラーとなることをテストします。
+#[test]
+fn builder_summary_errors() {
+    assert_eq!(
+        Summary::builder().day("2020/03/25", 1).build().unwrap_err(),
+        SummaryBuildError::MissingLastUpdate
+    );
+
+    let error = summary!(
+        last_update: "2020/03/25 21:40";
+        "2020/03/25" => 5,
+        "2020/03/25" => 10,
+    )
+    .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "at `data[1].date`: 2020/03/25 is not after 2020/03/25"
+    );
+
+    let error = summary!(last_update: "2020/03/25 21:40"; "2020/02/30" => 5).unwrap_err();
+    assert!(matches!(
+        error,
+        SummaryBuildError::InvalidDate { index: 0, .. }
+    ));
+}
+
+/// ビルダーで組み立てたNewsItemが、ポリシーに従って検証されることをテストします。
+#[test]
+fn builder_news_item() {
+    let expected = test_data_news_item();
+    let item = NewsItem::builder()
+        .date("2020/03/25")
+        .text(expected.text.clone())
+        .url("https://kyoto.stopcovid19.jp/")
+        .policy(UrlPolicy::kyoto())
+        .build()
+        .unwrap();
+    assert_eq!(item.date, expected.date);
+    assert_eq!(item.url, expected.url.as_str());
+
+    assert_eq!(
+        NewsItem::builder().text("本文").build().unwrap_err(),
+        NewsItemBuildError::MissingField("date")
+    );
+    assert_eq!(
+        NewsItem::builder()
+            .date("2020/03/25")
+            .text(" ")
+            .url("https://example.com/")
+            .build()
+            .unwrap_err(),
+        NewsItemBuildError::EmptyText
+    );
+    assert_eq!(
+        NewsItem::builder()
+            .date("2020/03/25")
+            .text("本文")
+            .url("http://example.com/")
+            .policy(UrlPolicy::kyoto())
+            .build()
+            .unwrap_err(),
+        NewsItemBuildError::InvalidUrl(UrlError::HttpsRequired("http://example.com/".to_string()))
+    );
+}
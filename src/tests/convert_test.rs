@@ -0,0 +1,187 @@
+use crate::{
+    convert::{convert, read, write, ConvertError, Format},
+    document::{Document, DocumentType},
+    tests::age_distribution_test::test_data_age_distribution,
+};
+use std::str::FromStr;
+
+const STATUS_JSON: &str = r#"{"attr":"patients","value":4096,"children":[{"attr":"hospitalizations","value":100,"children":[{"attr":"severely_patients","value":3}]},{"attr":"accommodations","value":32}],"last_update":"2020/03/25 21:40"}"#;
+const SUMMARY_JSON: &str = r#"{"data":[{"date":"2020-03-25T09:40:00+00:00","sum":10},{"date":"2020-03-26T09:40:00+00:00","sum":3}],"last_update":"2020/03/26 21:25"}"#;
+const NEWS_JSON: &str = r#"{"news_items":[{"date":"2020/03/25","text":"京都府, \"対策サイト\"","url":"https://kyoto.stopcovid19.jp/"}]}"#;
+const LAST_UPDATE_JSON: &str = r#"{"last_update":"2020/03/25 21:40"}"#;
+
+/// JSON形式から指定したデータ形式に変換し、再度JSON形式に戻した結果が元のデータと一致することを検証します。
+fn assert_round_trip(format: Format) {
+    for (document_type, json) in [
+        (DocumentType::Status, STATUS_JSON),
+        (DocumentType::Summary, SUMMARY_JSON),
+        (DocumentType::News, NEWS_JSON),
+        (DocumentType::LastUpdate, LAST_UPDATE_JSON),
+    ] {
+        let converted = convert(document_type, Format::Json, format, json.as_bytes()).unwrap();
+        let document = read(format, document_type, &converted).unwrap();
+        let original = Document::from_json(document_type, json).unwrap();
+        assert_eq!(
+            document.to_json_pretty().unwrap(),
+            original.to_json_pretty().unwrap()
+        );
+    }
+}
+
+/// Formatが名前から変換できることをテストします。
+#[test]
+fn format_from_str() {
+    for format in Format::ALL {
+        assert_eq!(Format::from_str(format.as_str()).unwrap(), *format);
+    }
+    assert_eq!(Format::from_str("yml").unwrap(), Format::Yaml);
+    assert!(Format::from_str("xml").is_err());
+}
+
+/// CSV形式との相互変換のテストを行います。
+#[test]
+fn convert_csv_round_trip() {
+    assert_round_trip(Format::Csv);
+}
+
+/// Status構造体のCSV形式の表現をテストします。
+#[test]
+fn convert_status_to_csv() {
+    let csv = convert(
+        DocumentType::Status,
+        Format::Json,
+        Format::Csv,
+        STATUS_JSON.as_bytes(),
+    )
+    .unwrap();
+    assert_eq!(
+        String::from_utf8(csv).unwrap(),
+        "depth,attr,value,has_children,last_update\n0,patients,4096,true,2020/03/25 21:40\n1,hospitalizations,100,true,\n2,severely_patients,3,false,\n1,accommodations,32,false,\n"
+    );
+}
+
+/// 空の子属性を持つStatus構造体が、CSV形式を経由しても子属性を失わないことをテストします。
+#[test]
+fn convert_status_csv_empty_children() {
+    let json = r#"{"attr":"patients","value":1,"children":[{"attr":"hospitalizations","value":1,"children":[]}]}"#;
+    let csv = convert(
+        DocumentType::Status,
+        Format::Json,
+        Format::Csv,
+        json.as_bytes(),
+    )
+    .unwrap();
+    assert_eq!(
+        read(Format::Csv, DocumentType::Status, &csv).unwrap(),
+        Document::from_json(DocumentType::Status, json).unwrap()
+    );
+
+    // 子属性を持たないStatusの下に、Statusを配置することはできない
+    let csv = "depth,attr,value,has_children,last_update\n0,patients,1,false,\n1,home,1,false,\n";
+    let error = read(Format::Csv, DocumentType::Status, csv.as_bytes()).unwrap_err();
+    assert!(error.to_string().starts_with("at line 3: "), "{}", error);
+}
+
+/// LastUpdate構造体のCSV形式で、2件目以降のレコードがエラーとなることをテストします。
+#[test]
+fn convert_last_update_csv_extra_records() {
+    let csv = "last_update\n2020/03/25 21:40\n2020/03/26 21:40\n";
+    let error = read(Format::Csv, DocumentType::LastUpdate, csv.as_bytes()).unwrap_err();
+    assert!(error.to_string().starts_with("at line 3: "), "{}", error);
+}
+
+/// 小計が存在しないSummary構造体が、最終更新日時とともにCSV形式で表現されることをテストします。
+#[test]
+fn convert_empty_summary_csv() {
+    let json = r#"{"data":[],"last_update":"2020/03/26 21:25"}"#;
+    let csv = convert(
+        DocumentType::Summary,
+        Format::Json,
+        Format::Csv,
+        json.as_bytes(),
+    )
+    .unwrap();
+    assert_eq!(
+        String::from_utf8(csv.clone()).unwrap(),
+        "date,sum,last_update\n,,2020/03/26 21:25\n"
+    );
+    let document = read(Format::Csv, DocumentType::Summary, &csv).unwrap();
+    assert_eq!(
+        document,
+        Document::from_json(DocumentType::Summary, json).unwrap()
+    );
+}
+
+/// Summary構造体のCSV形式で、最終更新日時が一致しないレコードがエラーとなることをテストします。
+#[test]
+fn convert_summary_csv_conflicting_last_update() {
+    let csv =
+        "date,sum,last_update\n2020/03/25,10,2020/03/26 21:25\n2020/03/26,3,2020/03/27 21:25\n";
+    let error = read(Format::Csv, DocumentType::Summary, csv.as_bytes()).unwrap_err();
+    assert!(error.to_string().starts_with("at line 3: "), "{}", error);
+    assert!(error.to_string().contains("last_update"), "{}", error);
+
+    // 最終更新日時のみのレコードは、小計と併用できない
+    let csv = "date,sum,last_update\n,,2020/03/26 21:25\n2020/03/26,3,2020/03/26 21:25\n";
+    assert!(read(Format::Csv, DocumentType::Summary, csv.as_bytes()).is_err());
+}
+
+/// 不正なCSV形式のデータについて、行番号が報告されることをテストします。
+#[test]
+fn convert_invalid_csv() {
+    let csv = "date,text,url\n2020/03/25,text,https://kyoto.stopcovid19.jp/\n2020/13/26,text,https://kyoto.stopcovid19.jp/\n";
+    let error = read(Format::Csv, DocumentType::News, csv.as_bytes()).unwrap_err();
+    assert!(error.to_string().starts_with("at line 3: "));
+
+    let csv = "depth,attr,value,has_children,last_update\n0,patients,1,true,\n2,home,1,false,\n";
+    assert!(read(Format::Csv, DocumentType::Status, csv.as_bytes()).is_err());
+}
+
+/// 有効になっていないデータ形式が、エラーとして扱われることをテストします。
+#[test]
+fn convert_unsupported_format() {
+    let document = Document::from_json(DocumentType::LastUpdate, LAST_UPDATE_JSON).unwrap();
+    for format in Format::ALL {
+        assert_eq!(write(&document, *format).is_ok(), format.is_supported());
+    }
+}
+
+/// CSV形式で表現しない種別が、エラーとして扱われることをテストします。
+#[test]
+fn convert_unsupported_document() {
+    let document = Document::AgeDistribution(test_data_age_distribution());
+    let error = write(&document, Format::Csv).unwrap_err();
+    assert!(matches!(
+        error,
+        ConvertError::UnsupportedDocument {
+            format: Format::Csv,
+            document_type: DocumentType::AgeDistribution,
+        }
+    ));
+    assert_eq!(
+        error.to_string(),
+        "format `csv` does not support `age_distribution`"
+    );
+    assert!(read(Format::Csv, DocumentType::AgeDistribution, b"date\n").is_err());
+
+    // JSON形式の相互変換には影響しない
+    let json = write(&document, Format::Json).unwrap();
+    assert_eq!(
+        read(Format::Json, DocumentType::AgeDistribution, &json).unwrap(),
+        document
+    );
+}
+
+/// YAML形式との相互変換のテストを行います。
+#[cfg(feature = "yaml")]
+#[test]
+fn convert_yaml_round_trip() {
+    assert_round_trip(Format::Yaml);
+}
+
+/// MessagePack形式との相互変換のテストを行います。
+#[cfg(feature = "msgpack")]
+#[test]
+fn convert_msgpack_round_trip() {
+    assert_round_trip(Format::MessagePack);
+}
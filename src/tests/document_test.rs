@@ -0,0 +1,87 @@
+use crate::document::{Document, DocumentType};
+use std::str::FromStr;
+
+/// DocumentTypeが名前から変換できることをテストします。
+#[test]
+fn document_type_from_str() {
+    for document_type in DocumentType::ALL {
+        assert_eq!(
+            DocumentType::from_str(document_type.as_str()).unwrap(),
+            *document_type
+        );
+    }
+    assert!(DocumentType::from_str("patients").is_err());
+}
+
+/// 正しいデータファイルが、指定した種別の構造体として読み込めることをテストします。
+#[test]
+fn document_from_json() {
+    let serialized =
+        r#"{"attr":"patients","value":4096,"children":[{"attr":"accommodations","value":32}]}"#;
+    let document = Document::from_json(DocumentType::Status, serialized).unwrap();
+    assert_eq!(document.document_type(), DocumentType::Status);
+}
+
+/// 不正なデータファイルについて、エラーの位置がJSONのパスで報告されることをテストします。
+#[test]
+fn document_error_path() {
+    let serialized = r#"{"data":[{"date":"2020-03-25T09:40:00.000Z","sum":10},{"date":"2020/02/30","sum":3}],"last_update":"2020/03/26 21:00"}"#;
+    let error = Document::from_json(DocumentType::Summary, serialized).unwrap_err();
+    assert_eq!(error.path(), "data[1].date");
+    assert_eq!(error.line(), 1);
+
+    let serialized = r#"{"news_items":[{"date":"2020/03/25","text":"テキスト"}]}"#;
+    let error = Document::from_json(DocumentType::News, serialized).unwrap_err();
+    assert_eq!(error.path(), "news_items[0]");
+}
+
+/// 正規化したデータファイルが、再度読み込めることをテストします。
+#[test]
+fn document_to_json_pretty() {
+    let serialized = r#"{"last_update":"2020/03/25 21:40"}"#;
+    let document = Document::from_json(DocumentType::LastUpdate, serialized).unwrap();
+    let formatted = document.to_json_pretty().unwrap();
+    assert_eq!(formatted, "{\n  \"last_update\": \"2020/03/25 21:40\"\n}");
+    assert!(Document::from_json(DocumentType::LastUpdate, &formatted).is_ok());
+}
+
+/// データファイルの内容が要約されることをテストします。
+#[test]
+fn document_inspect() {
+    let serialized =
+        r#"{"attr":"patients","value":4096,"children":[{"attr":"accommodations","value":32}]}"#;
+    let rows = Document::from_json(DocumentType::Status, serialized)
+        .unwrap()
+        .inspect();
+    assert_eq!(
+        rows,
+        vec![
+            ("type".to_string(), "status".to_string()),
+            ("patients".to_string(), "4096".to_string()),
+            ("  accommodations".to_string(), "32".to_string()),
+        ]
+    );
+}
+
+/// 年代別陽性者数の内容が要約されることをテストします。
+#[test]
+fn document_inspect_age_distribution() {
+    let serialized = r#"{"data":[{"age_group":"20代","count":3},{"age_group":"非公表","count":1}],"daily":[{"date":"2020/03/24","data":[{"age_group":"20代","count":3}]}],"last_update":"2020/03/25 21:40"}"#;
+    let rows = Document::from_json(DocumentType::AgeDistribution, serialized)
+        .unwrap()
+        .inspect();
+    assert_eq!(
+        rows,
+        vec![
+            ("type".to_string(), "age_distribution".to_string()),
+            (
+                "last_update".to_string(),
+                "2020/03/25 21:40 (令和2年3月25日 21時40分)".to_string()
+            ),
+            ("total".to_string(), "4".to_string()),
+            ("20代".to_string(), "3".to_string()),
+            ("非公表".to_string(), "1".to_string()),
+            ("days".to_string(), "1".to_string()),
+        ]
+    );
+}
@@ -0,0 +1,233 @@
+use crate::{
+    feed::{guid, FeedChannel, FeedError},
+    structs::news::{NewsItem, NewsItems},
+    tests::structs_test::{test_data_news_item, test_data_news_items},
+};
+use chrono::NaiveDate;
+
+/// テスト用のチャンネル情報を生成します。
+fn test_data_channel() -> FeedChannel {
+    FeedChannel {
+        title: "京都府 新型コロナウイルス感染症 対策サイト".to_string(),
+        link: "https://kyoto.stopcovid19.jp/".to_string(),
+        description: "最新のお知らせ".to_string(),
+        language: Some("ja".to_string()),
+        feed_url: Some("https://kyoto.stopcovid19.jp/feed.xml".to_string()),
+        domain: "kyoto.stopcovid19.jp".to_string(),
+    }
+}
+
+/// エスケープが必要な文字を含む、テスト用のお知らせを生成します。
+fn test_data_news_items_escaped() -> NewsItems {
+    NewsItems {
+        news_items: vec![
+            test_data_news_item(),
+            NewsItem {
+                date: NaiveDate::from_ymd_opt(2020, 4, 1).unwrap(),
+                text: "検査件数 & <陽性者数> の\"更新\"".to_string(),
+                url: "https://www.pref.kyoto.jp/kentai/news/index.html?a=1&b=2"
+                    .parse()
+                    .unwrap(),
+            },
+        ],
+    }
+}
+
+/// RSS形式で出力した内容を読み込み、元のお知らせと一致することをテストします。
+#[test]
+fn feed_rss_round_trip() {
+    let news_items = test_data_news_items_escaped();
+    let rss = news_items.to_rss(&test_data_channel());
+
+    let parsed = NewsItems::from_rss(&rss).unwrap();
+    assert_eq!(
+        serde_json::to_string(&parsed).unwrap(),
+        serde_json::to_string(&news_items).unwrap()
+    );
+}
+
+/// Atom形式で出力した内容を読み込み、元のお知らせと一致することをテストします。
+#[test]
+fn feed_atom_round_trip() {
+    let news_items = test_data_news_items_escaped();
+    let atom = news_items.to_atom(&test_data_channel());
+
+    let parsed = NewsItems::from_atom(&atom).unwrap();
+    assert_eq!(
+        serde_json::to_string(&parsed).unwrap(),
+        serde_json::to_string(&news_items).unwrap()
+    );
+}
+
+/// 出力される日付とGUIDが、日本標準時と安定した識別子であることをテストします。
+#[test]
+fn feed_rss_output() {
+    let rss = test_data_news_items().to_rss(&test_data_channel());
+
+    assert!(rss.contains("<pubDate>Wed, 25 Mar 2020 00:00:00 +0900</pubDate>"));
+    assert!(rss.contains(
+        "<guid isPermaLink=\"false\">tag:kyoto.stopcovid19.jp,2020-03-25:https://kyoto.stopcovid19.jp/</guid>"
+    ));
+    assert!(rss.contains("<lastBuildDate>Wed, 25 Mar 2020 00:00:00 +0900</lastBuildDate>"));
+    assert_eq!(
+        guid(&test_data_channel(), &test_data_news_item()),
+        "tag:kyoto.stopcovid19.jp,2020-03-25:https://kyoto.stopcovid19.jp/"
+    );
+    // 再生成しても同じ内容となる
+    assert_eq!(rss, test_data_news_items().to_rss(&test_data_channel()));
+
+    let atom = test_data_news_items().to_atom(&test_data_channel());
+    assert!(atom.contains("<published>2020-03-25T00:00:00+09:00</published>"));
+    assert!(atom.contains("<id>https://kyoto.stopcovid19.jp/feed.xml</id>"));
+
+    let rss = test_data_news_items_escaped().to_rss(&test_data_channel());
+    assert!(rss.contains("検査件数 &amp; &lt;陽性者数&gt; の&quot;更新&quot;"));
+}
+
+/// 外部で作成されたフィードを読み込めることをテストします。
+#[test]
+fn feed_parse_external() {
+    let rss = r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0">
+  <channel>
+    <title>京都府</title>
+    <item>
+      <title><![CDATA[新型コロナウイルス感染症の発生状況 & 検査件数]]></title>
+      <link> https://www.pref.kyoto.jp/kentai/news/20200401.html </link>
+      <description>本文</description>
+      <pubDate>Tue, 31 Mar 2020 16:30:00 GMT</pubDate>
+    </item>
+  </channel>
+</rss>"#;
+    let news_items = NewsItems::from_rss(rss).unwrap();
+    assert_eq!(news_items.news_items.len(), 1);
+    let item = &news_items.news_items[0];
+    // 日本標準時に変換すると、翌日の日付となる
+    assert_eq!(item.date, NaiveDate::from_ymd_opt(2020, 4, 1).unwrap());
+    assert_eq!(item.text, "新型コロナウイルス感染症の発生状況 & 検査件数");
+    assert_eq!(
+        item.url,
+        "https://www.pref.kyoto.jp/kentai/news/20200401.html"
+    );
+
+    let atom = r#"<feed xmlns="http://www.w3.org/2005/Atom">
+  <title>京都府</title>
+  <entry>
+    <title>お知らせ</title>
+    <link rel="enclosure" href="https://example.com/file.pdf"/>
+    <link href="https://www.pref.kyoto.jp/kentai/news/20200402.html"/>
+    <updated>2020-04-02T10:00:00+09:00</updated>
+  </entry>
+</feed>"#;
+    let news_items = NewsItems::from_atom(atom).unwrap();
+    let item = &news_items.news_items[0];
+    assert_eq!(item.date, NaiveDate::from_ymd_opt(2020, 4, 2).unwrap());
+    assert_eq!(
+        item.url,
+        "https://www.pref.kyoto.jp/kentai/news/20200402.html"
+    );
+}
+
+/// フラグメントを含むURLでも、識別子が重複しないことをテストします。
+#[test]
+fn feed_guid_with_fragment() {
+    let item = |date: &str, url: &str| NewsItem {
+        date: NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap(),
+        text: "お知らせ".to_string(),
+        url: url.parse().unwrap(),
+    };
+    let channel = test_data_channel();
+    let fragment = guid(
+        &channel,
+        &item("2020-03-25", "https://kyoto.stopcovid19.jp/#2020-03-26"),
+    );
+    assert_eq!(
+        fragment,
+        "tag:kyoto.stopcovid19.jp,2020-03-25:https://kyoto.stopcovid19.jp/%232020-03-26"
+    );
+    assert_eq!(fragment.matches('#').count(), 0);
+    assert_ne!(
+        fragment,
+        guid(
+            &channel,
+            &item("2020-03-26", "https://kyoto.stopcovid19.jp/#2020-03-25")
+        )
+    );
+    assert_ne!(
+        guid(
+            &channel,
+            &item("2020-03-25", "https://kyoto.stopcovid19.jp/a#b")
+        ),
+        guid(
+            &channel,
+            &item("2020-03-25", "https://kyoto.stopcovid19.jp/a%23b")
+        )
+    );
+}
+
+/// 識別子の発行者が、リンク先のホスト名ではなくチャンネル情報のドメイン名となることをテストします。
+#[test]
+fn feed_guid_authority() {
+    let item = NewsItem {
+        date: NaiveDate::from_ymd_opt(2020, 4, 1).unwrap(),
+        text: "お知らせ".to_string(),
+        url: "https://www.pref.kyoto.jp/kentai/news/20200401.html"
+            .parse()
+            .unwrap(),
+    };
+    assert_eq!(
+        guid(&test_data_channel(), &item),
+        "tag:kyoto.stopcovid19.jp,2020-04-01:https://www.pref.kyoto.jp/kentai/news/20200401.html"
+    );
+}
+
+/// 項目内の入れ子の要素が、項目の内容として扱われないことをテストします。
+#[test]
+fn feed_parse_nested_elements() {
+    let atom = r#"<feed xmlns="http://www.w3.org/2005/Atom">
+  <entry>
+    <title type="xhtml"><div xmlns="http://www.w3.org/1999/xhtml"><b>速報</b> 検査件数の更新</div></title>
+    <source>
+      <title>京都府</title>
+      <link href="https://www.pref.kyoto.jp/"/>
+      <updated>2020-04-01T10:00:00+09:00</updated>
+    </source>
+    <link href="https://www.pref.kyoto.jp/kentai/news/20200402.html"/>
+    <updated>2020-04-02T10:00:00+09:00</updated>
+  </entry>
+</feed>"#;
+    let news_items = NewsItems::from_atom(atom).unwrap();
+    let item = &news_items.news_items[0];
+    // 入れ子の要素の後のテキストも、タイトルに含まれる
+    assert_eq!(item.text, "速報 検査件数の更新");
+    // <source>内の要素は、項目のタイトル・リンク・日付を上書きしない
+    assert_eq!(
+        item.url,
+        "https://www.pref.kyoto.jp/kentai/news/20200402.html"
+    );
+    assert_eq!(item.date, NaiveDate::from_ymd_opt(2020, 4, 2).unwrap());
+}
+
+/// 不正なフィードを読み込んだ際に、エラーとなることをテストします。
+#[test]
+fn feed_parse_invalid() {
+    let rss = "<rss><channel><item><title>a</title><link>b</link></item></channel></rss>";
+    match NewsItems::from_rss(rss) {
+        Err(FeedError::MissingElement { index, element }) => {
+            assert_eq!(index, 0);
+            assert_eq!(element, "pubDate");
+        }
+        result => panic!("unexpected result: {:?}", result),
+    }
+
+    let rss = "<rss><channel><item><title>a</title><link>b</link><pubDate>yesterday</pubDate></item></channel></rss>";
+    assert!(matches!(
+        NewsItems::from_rss(rss),
+        Err(FeedError::InvalidDate { index: 0, .. })
+    ));
+
+    assert!(matches!(
+        NewsItems::from_rss("<rss><channel></item></rss>"),
+        Err(FeedError::Xml(_))
+    ));
+}
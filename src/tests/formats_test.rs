@@ -0,0 +1,259 @@
+use crate::{
+    structs::{
+        last_update::LastUpdate,
+        news::{NewsItem, NewsItems},
+    },
+    tests::structs_test::{test_data_news_item, test_data_summary_content},
+    utils::formats::{
+        codec::{DateCodec, InputFormat, OutputFormat, DATE, DATETIME, TIMESTAMP},
+        era::{
+            format_era_date, format_era_datetime, normalize_digits, parse_era_date,
+            parse_era_datetime, Digits, Era,
+        },
+    },
+};
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use serde::{
+    de::{DeserializeSeed, Deserializer},
+    Deserialize,
+};
+
+/// 日付を生成します。
+fn ymd(year: i32, month: u32, day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(year, month, day).unwrap()
+}
+
+/// 和暦の日付をパースできることをテストします。
+#[test]
+fn formats_parse_era_date() {
+    assert_eq!(parse_era_date("令和2年3月25日").unwrap(), ymd(2020, 3, 25));
+    assert_eq!(parse_era_date("令和元年5月1日").unwrap(), ymd(2019, 5, 1));
+    assert_eq!(parse_era_date("令和1年5月1日").unwrap(), ymd(2019, 5, 1));
+    assert_eq!(parse_era_date("平成31年4月30日").unwrap(), ymd(2019, 4, 30));
+    assert_eq!(parse_era_date("平成元年1月8日").unwrap(), ymd(1989, 1, 8));
+    assert_eq!(
+        parse_era_date("令和２年３月２５日").unwrap(),
+        ymd(2020, 3, 25)
+    );
+    assert_eq!(
+        parse_era_date(" 令和2年03月05日 ").unwrap(),
+        ymd(2020, 3, 5)
+    );
+
+    for value in [
+        "令和元年4月30日",
+        "平成31年5月1日",
+        "平成元年1月7日",
+        "令和0年5月1日",
+        "令和2年2月30日",
+        "令和2年3月25",
+        "令和2年3月25日です",
+        "昭和64年1月7日",
+        "2020/03/25",
+    ] {
+        assert!(parse_era_date(value).is_err(), "{}", value);
+    }
+    assert_eq!(
+        parse_era_date("平成32年1月1日").unwrap_err(),
+        "invalid era date `平成32年1月1日`: 2020-01-01 is outside of 平成"
+    );
+}
+
+/// 和暦の日付と時刻をパースできることをテストします。
+#[test]
+fn formats_parse_era_datetime() {
+    let expected = ymd(2020, 3, 25).and_hms_opt(21, 40, 0).unwrap();
+    assert_eq!(
+        parse_era_datetime("令和2年3月25日 21時40分").unwrap(),
+        expected
+    );
+    assert_eq!(
+        parse_era_datetime("令和2年3月25日21時40分").unwrap(),
+        expected
+    );
+    assert_eq!(
+        parse_era_datetime("令和2年3月25日 21:40").unwrap(),
+        expected
+    );
+    assert_eq!(
+        parse_era_datetime("令和２年３月２５日　２１：４０").unwrap(),
+        expected
+    );
+    assert!(parse_era_datetime("令和2年3月25日").is_err());
+    assert!(parse_era_datetime("令和2年3月25日 25時00分").is_err());
+}
+
+/// 日付を和暦で出力できることをテストします。
+#[test]
+fn formats_format_era_date() {
+    assert_eq!(
+        format_era_date(ymd(2020, 3, 25), Digits::HalfWidth).unwrap(),
+        "令和2年3月25日"
+    );
+    assert_eq!(
+        format_era_date(ymd(2019, 5, 1), Digits::HalfWidth).unwrap(),
+        "令和元年5月1日"
+    );
+    assert_eq!(
+        format_era_date(ymd(2019, 4, 30), Digits::FullWidth).unwrap(),
+        "平成３１年４月３０日"
+    );
+    assert!(format_era_date(ymd(1989, 1, 7), Digits::HalfWidth).is_none());
+    assert_eq!(
+        format_era_datetime(
+            ymd(2020, 3, 25).and_hms_opt(9, 5, 0).unwrap(),
+            Digits::HalfWidth
+        )
+        .unwrap(),
+        "令和2年3月25日 9時05分"
+    );
+    assert_eq!(Era::of(ymd(2019, 5, 1)), Some(Era::Reiwa));
+    assert_eq!(Era::Heisei.end(), Some(ymd(2019, 4, 30)));
+    assert_eq!(Era::Reiwa.end(), None);
+
+    // 出力した文字列を、再度パースできること
+    let date = ymd(2021, 12, 31);
+    for digits in [Digits::HalfWidth, Digits::FullWidth] {
+        assert_eq!(
+            parse_era_date(&format_era_date(date, digits).unwrap()).unwrap(),
+            date
+        );
+    }
+    assert_eq!(normalize_digits("１２：３４"), "12:34");
+}
+
+/// 構造体が和暦の日付を読み込み、和暦で出力できることをテストします。
+#[test]
+fn formats_era_in_structs() {
+    let news_items = serde_json::from_str::<NewsItems>(
+        r#"{"news_items":[{"date":"令和2年3月25日","text":"お知らせ","url":"https://kyoto.stopcovid19.jp/"}]}"#,
+    )
+    .unwrap();
+    let item: &NewsItem = &news_items.news_items[0];
+    assert_eq!(item.date, ymd(2020, 3, 25));
+    assert_eq!(
+        serde_json::to_string(&news_items).unwrap(),
+        r#"{"news_items":[{"date":"2020/03/25","text":"お知らせ","url":"https://kyoto.stopcovid19.jp/"}]}"#
+    );
+    assert_eq!(
+        test_data_news_item().era_date(Digits::HalfWidth).unwrap(),
+        "令和2年3月25日"
+    );
+
+    let last_update =
+        serde_json::from_str::<LastUpdate<Local>>(r#"{"last_update":"令和2年3月25日 21時40分"}"#)
+            .unwrap();
+    assert_eq!(
+        serde_json::to_string(&last_update).unwrap(),
+        r#"{"last_update":"2020/03/25 21:40"}"#
+    );
+    assert_eq!(
+        last_update.era_datetime(Digits::FullWidth).unwrap(),
+        "令和２年３月２５日 ２１時４０分"
+    );
+    assert!(test_data_summary_content()
+        .era_date(Digits::HalfWidth)
+        .is_some());
+}
+
+/// コーデックが複数の書式を受け付け、正規化された書式で出力することをテストします。
+#[test]
+fn formats_codec_inputs() {
+    for value in [
+        "2020/03/25",
+        "2020/3/25",
+        "2020-03-25",
+        "令和2年3月25日",
+        "2020-03-24T15:00:00Z",
+        "2020-03-25T00:00:00+09:00",
+    ] {
+        let date = DATE.parse::<NaiveDate>(value).unwrap();
+        assert_eq!(date, ymd(2020, 3, 25), "{}", value);
+        assert_eq!(DATE.format(&date), "2020/03/25");
+    }
+
+    let datetime = DATETIME
+        .parse::<DateTime<Local>>("2020-03-25 21:40")
+        .unwrap();
+    assert_eq!(DATETIME.format(&datetime), "2020/03/25 21:40");
+    assert!(DATETIME.parse::<DateTime<Local>>("2020/03/25").is_err());
+
+    // 時差を含まない値は、日本標準時として扱う
+    let timestamp = TIMESTAMP.parse::<DateTime<Utc>>("2020/03/25").unwrap();
+    assert_eq!(TIMESTAMP.format(&timestamp), "2020-03-24T15:00:00+00:00");
+    assert_eq!(
+        TIMESTAMP
+            .parse::<DateTime<Utc>>("2020-03-25T09:40:00.000Z")
+            .unwrap(),
+        TIMESTAMP
+            .parse::<DateTime<Utc>>("2020/03/25 18:40")
+            .unwrap()
+    );
+}
+
+/// 月・日・年の順の日付を受け付けるコーデックです。(テスト用)
+const MONTH_FIRST: DateCodec = DateCodec {
+    inputs: &[InputFormat::Chrono("%m/%d/%Y")],
+    output: OutputFormat::Chrono("%m/%d/%Y"),
+};
+
+/// `MONTH_FIRST`コーデックで、日付をデシリアライズします。
+fn month_first<'de, D: Deserializer<'de>>(deserializer: D) -> Result<NaiveDate, D::Error> {
+    MONTH_FIRST.deserialize(deserializer)
+}
+
+/// `DATE`コーデックで、日付をデシリアライズします。
+fn date<'de, D: Deserializer<'de>>(deserializer: D) -> Result<NaiveDate, D::Error> {
+    DATE.deserialize(deserializer)
+}
+
+/// フィールドごとに異なるコーデックで読み込む構造体です。(テスト用)
+#[derive(Debug, Deserialize)]
+struct Report {
+    #[serde(deserialize_with = "month_first")]
+    reported: NaiveDate,
+    #[serde(deserialize_with = "date")]
+    published: NaiveDate,
+}
+
+/// 呼び出し側が指定したコーデックで、フィールドを読み込めることをテストします。
+#[test]
+fn formats_codec_per_field() {
+    let report =
+        serde_json::from_str::<Report>(r#"{"reported":"03/24/2020","published":"2020-03-25"}"#)
+            .unwrap();
+    assert_eq!(report.reported, ymd(2020, 3, 24));
+    assert_eq!(report.published, ymd(2020, 3, 25));
+    // 既定の書式は、MONTH_FIRSTコーデックでは受け付けない
+    let error =
+        serde_json::from_str::<Report>(r#"{"reported":"2020/03/24","published":"2020/03/25"}"#)
+            .unwrap_err();
+    assert!(
+        error.to_string().contains("expected `%m/%d/%Y`"),
+        "{}",
+        error
+    );
+
+    let mut deserializer = serde_json::Deserializer::from_str(r#""12/31/2020""#);
+    let date: NaiveDate = MONTH_FIRST.seed().deserialize(&mut deserializer).unwrap();
+    assert_eq!(MONTH_FIRST.format(&date), "12/31/2020");
+}
+
+/// コーデックがパースに失敗した際、受け付ける書式をエラーとして報告することをテストします。
+#[test]
+fn formats_codec_errors() {
+    assert_eq!(
+        DATE.parse::<NaiveDate>("2020.03.25").unwrap_err(),
+        "invalid date `2020.03.25` (expected `%Y/%m/%d`, `%Y-%m-%d`, Japanese era, RFC3339)"
+    );
+    assert!(DATE.parse::<NaiveDate>("2020/02/30").is_err());
+    // 0001年から9999年までの範囲外の値は受け付けない
+    assert!(DATE.parse::<NaiveDate>("0000/12/31").is_err());
+    assert!(DATE.parse::<NaiveDate>("+10000/01/01").is_err());
+    assert!(DATE.parse::<NaiveDate>("0001/01/01").is_ok());
+    // 和暦として解釈できる場合は、和暦のエラーを報告する
+    assert_ne!(
+        DATE.parse::<NaiveDate>("令和0年5月1日").unwrap_err(),
+        DATE.parse::<NaiveDate>("2020.03.25").unwrap_err()
+    );
+}
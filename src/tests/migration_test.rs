@@ -0,0 +1,161 @@
+use crate::{
+    convert::{self, Format},
+    document::{Document, DocumentType},
+    migration::{upgrade, MigrationError, CURRENT_VERSION, MIGRATIONS},
+};
+use serde_json::json;
+
+/// 版ごとのデータファイルの例です。(種別、第1版、第2版、第3版)
+const FIXTURES: &[(DocumentType, &str, &str, &str)] = &[
+    (
+        DocumentType::Summary,
+        include_str!("fixtures/v1/summary.json"),
+        include_str!("fixtures/v2/summary.json"),
+        include_str!("fixtures/v3/summary.json"),
+    ),
+    (
+        DocumentType::Status,
+        include_str!("fixtures/v1/status.json"),
+        include_str!("fixtures/v2/status.json"),
+        include_str!("fixtures/v3/status.json"),
+    ),
+    (
+        DocumentType::News,
+        include_str!("fixtures/v1/news.json"),
+        include_str!("fixtures/v2/news.json"),
+        include_str!("fixtures/v3/news.json"),
+    ),
+    (
+        DocumentType::LastUpdate,
+        include_str!("fixtures/v1/last_update.json"),
+        include_str!("fixtures/v2/last_update.json"),
+        include_str!("fixtures/v3/last_update.json"),
+    ),
+];
+
+/// 変換処理が、第1版から現在の版まで1段階ずつ定義されていることをテストします。
+#[test]
+fn migration_steps() {
+    for (index, migration) in MIGRATIONS.iter().enumerate() {
+        assert_eq!(migration.from, index as u32 + 1);
+    }
+    assert_eq!(MIGRATIONS.len() as u32 + 1, CURRENT_VERSION);
+}
+
+/// 各版のデータファイルが、現在の版の構造体として読み込めることをテストします。
+#[test]
+fn migration_fixtures() {
+    for (document_type, v1, v2, v3) in FIXTURES {
+        let current = Document::from_versioned_json(*document_type, v3).unwrap();
+        let upgraded = Document::from_versioned_json(*document_type, v2).unwrap();
+        assert_eq!(upgraded, current, "{}", document_type);
+
+        // 第1版のStatusは子属性を持たないため、子属性を除いた内容と比較する
+        let mut current = current;
+        if let Document::Status(status) = &mut current {
+            status.children = None;
+        }
+        let upgraded = Document::from_versioned_json(*document_type, v1).unwrap();
+        assert_eq!(upgraded, current, "{}", document_type);
+    }
+}
+
+/// 第1版のStatusが、書き換えられずに第2版のStatusとなることをテストします。
+#[test]
+fn migration_status_without_children() {
+    let (_, v1, _, _) = FIXTURES[1];
+    let value = serde_json::from_str::<serde_json::Value>(v1).unwrap();
+    assert!(value.get("children").is_none());
+
+    let upgraded = upgrade(DocumentType::Status, value.clone()).unwrap();
+    assert_eq!(upgraded, value);
+    match Document::from_versioned_json(DocumentType::Status, v1).unwrap() {
+        Document::Status(status) => assert_eq!(status.children, None),
+        document => panic!("unexpected document: {:?}", document),
+    }
+}
+
+/// JSON形式の読み込みが、版数を含むデータファイルを現在の版に変換することをテストします。
+#[test]
+fn migration_convert_read() {
+    for (document_type, v1, v2, v3) in FIXTURES {
+        let current = Document::from_versioned_json(*document_type, v3).unwrap();
+        for json in [v2, v3] {
+            let read = convert::read(Format::Json, *document_type, json.as_bytes()).unwrap();
+            assert_eq!(read, current, "{}", document_type);
+        }
+        assert!(convert::read(Format::Json, *document_type, v1.as_bytes()).is_ok());
+    }
+
+    // 版数を含まないデータファイルは、元の入力における位置でエラーを報告する
+    let error = Document::from_versioned_json(
+        DocumentType::Summary,
+        "{\n  \"data\": [{\"date\": \"2020/03/25\"}],\n  \"last_update\": \"2020/03/27 21:40\"\n}",
+    )
+    .unwrap_err();
+    match error {
+        MigrationError::Document(error) => assert_eq!((error.path(), error.line()), ("data[0]", 2)),
+        error => panic!("unexpected error: {}", error),
+    }
+}
+
+/// 小計の日付が、RFC3339形式から日本標準時の日付に変換されることをテストします。
+#[test]
+fn migration_summary_dates() {
+    let (_, v1, _, _) = FIXTURES[0];
+    let upgraded = upgrade(DocumentType::Summary, serde_json::from_str(v1).unwrap()).unwrap();
+    assert_eq!(upgraded["data"][0]["date"], "2020/03/25");
+    assert_eq!(upgraded["data"][2]["date"], "2020/03/27");
+    assert!(upgraded.get("version").is_none());
+}
+
+/// 現在の版で出力したデータファイルが、版数を含み、再度読み込めることをテストします。
+#[test]
+fn migration_versioned_json() {
+    for (document_type, _, _, v3) in FIXTURES {
+        let document = Document::from_versioned_json(*document_type, v3).unwrap();
+        let json = document.to_versioned_json_pretty().unwrap();
+        assert_eq!(json.trim_end(), v3.trim_end(), "{}", document_type);
+        assert_eq!(
+            Document::from_versioned_json(*document_type, &json).unwrap(),
+            document
+        );
+    }
+}
+
+/// 不正な版数や内容について、エラーとなることをテストします。
+#[test]
+fn migration_errors() {
+    let upgrade_json = |value| upgrade(DocumentType::Summary, value).unwrap_err();
+
+    assert!(matches!(
+        upgrade_json(json!([])),
+        MigrationError::NotAnObject
+    ));
+    assert!(matches!(
+        upgrade_json(json!({"version": "3"})),
+        MigrationError::InvalidVersion(_)
+    ));
+    assert!(matches!(
+        upgrade_json(json!({"version": 0})),
+        MigrationError::UnsupportedVersion(0)
+    ));
+    assert!(matches!(
+        upgrade_json(json!({"version": CURRENT_VERSION + 1})),
+        MigrationError::UnsupportedVersion(_)
+    ));
+
+    let error = upgrade_json(json!({"version": 2, "data": [{"date": "2020-02-30T00:00:00Z"}]}));
+    assert!(matches!(error, MigrationError::Step { from: 2, .. }));
+    assert!(error.to_string().contains("data[0].date"));
+
+    let error = Document::from_versioned_json(
+        DocumentType::Summary,
+        r#"{"version":3,"data":[{"date":"2020/03/25"}],"last_update":"2020/03/27 21:40"}"#,
+    )
+    .unwrap_err();
+    match error {
+        MigrationError::Document(error) => assert_eq!(error.path(), "data[0]"),
+        error => panic!("unexpected error: {}", error),
+    }
+}
@@ -0,0 +1,362 @@
+use crate::structs::news::{
+    curation::{DuplicateUrl, StrictLoadError},
+    link::{NewsUrl, UrlError, UrlPolicy},
+    localized::{Fallback, Locale, LocalizedNewsItem, LocalizedNewsItems},
+    NewsItem, NewsItems,
+};
+use crate::tests::structs_test::{test_data_localized_news_item, test_data_news_item};
+use chrono::NaiveDate;
+use std::num::NonZeroUsize;
+
+/// テスト用のお知らせを生成します。
+fn news_item(day: u32, text: &str, url: &str) -> NewsItem {
+    NewsItem {
+        date: NaiveDate::from_ymd_opt(2020, 4, day).unwrap(),
+        text: text.to_string(),
+        url: url.parse().unwrap(),
+    }
+}
+
+/// 重複と順序の乱れを含む、テスト用のお知らせの一覧を生成します。
+fn test_data_unsorted_news_items() -> NewsItems {
+    NewsItems {
+        news_items: vec![
+            news_item(1, "第1報", "https://example.com/1"),
+            news_item(3, "第3報", "https://example.com/3"),
+            news_item(2, "第2報", "https://example.com/2"),
+            news_item(4, "第1報(訂正)", "https://example.com/1"),
+            news_item(3, "第3報(重複)", "https://example.com/3"),
+        ],
+    }
+}
+
+/// お知らせの本文を、順番に取り出します。
+fn texts(news_items: &[NewsItem]) -> Vec<&str> {
+    news_items.iter().map(|item| item.text.as_str()).collect()
+}
+
+/// お知らせを日付の新しい順に並び替えられることをテストします。
+#[test]
+fn news_sort_newest_first() {
+    let mut news_items = test_data_unsorted_news_items();
+    news_items.sort_newest_first();
+    assert_eq!(
+        texts(&news_items.news_items),
+        ["第1報(訂正)", "第3報", "第3報(重複)", "第2報", "第1報"]
+    );
+}
+
+/// 同じURLのお知らせについて、最も新しいもののみが残ることをテストします。
+#[test]
+fn news_dedup_by_url() {
+    let mut news_items = test_data_unsorted_news_items();
+    news_items.dedup_by_url();
+    assert_eq!(
+        texts(&news_items.news_items),
+        ["第3報", "第2報", "第1報(訂正)"]
+    );
+
+    let mut news_items = test_data_unsorted_news_items();
+    news_items.curate();
+    assert_eq!(
+        texts(&news_items.news_items),
+        ["第1報(訂正)", "第3報", "第2報"]
+    );
+}
+
+/// 日付の範囲と語句で、お知らせを絞り込めることをテストします。
+#[test]
+fn news_filter() {
+    let news_items = test_data_unsorted_news_items();
+    let from = NaiveDate::from_ymd_opt(2020, 4, 2).unwrap();
+    let to = NaiveDate::from_ymd_opt(2020, 4, 3).unwrap();
+
+    assert_eq!(
+        texts(&news_items.filter_by_date(from..=to).news_items),
+        ["第3報", "第2報", "第3報(重複)"]
+    );
+    assert_eq!(
+        texts(&news_items.filter_by_date(from..to).news_items),
+        ["第2報"]
+    );
+    assert_eq!(news_items.filter_by_date(to..).news_items.len(), 3);
+    assert_eq!(
+        texts(&news_items.filter_by_keyword("訂正").news_items),
+        ["第1報(訂正)"]
+    );
+
+    let news_items = NewsItems {
+        news_items: vec![news_item(1, "PCR検査の実施件数", "https://example.com/pcr")],
+    };
+    assert_eq!(news_items.filter_by_keyword("pcr").news_items.len(), 1);
+}
+
+/// お知らせをページごとに取り出せることをテストします。
+#[test]
+fn news_paginate() {
+    let per_page = |n| NonZeroUsize::new(n).unwrap();
+    let news_items = test_data_unsorted_news_items();
+    assert_eq!(news_items.page_count(per_page(2)), 3);
+    assert_eq!(news_items.page_count(per_page(5)), 1);
+    assert_eq!(texts(news_items.page(0, per_page(2))), ["第1報", "第3報"]);
+    assert_eq!(texts(news_items.page(2, per_page(2))), ["第3報(重複)"]);
+    assert!(news_items.page(3, per_page(2)).is_empty());
+
+    let empty = NewsItems { news_items: vec![] };
+    assert_eq!(empty.page_count(per_page(10)), 0);
+    assert!(empty.page(0, per_page(10)).is_empty());
+}
+
+/// 厳格な読み込みで、重複したURLが報告されることをテストします。
+#[test]
+fn news_from_json_strict() {
+    let news_items = test_data_unsorted_news_items();
+    assert_eq!(
+        news_items.duplicates(),
+        [
+            DuplicateUrl {
+                url: "https://example.com/1".to_string(),
+                indices: vec![0, 3],
+            },
+            DuplicateUrl {
+                url: "https://example.com/3".to_string(),
+                indices: vec![1, 4],
+            },
+        ]
+    );
+
+    let json = serde_json::to_string(&news_items).unwrap();
+    match NewsItems::from_json_strict(&json) {
+        Err(error @ StrictLoadError::Duplicates(_)) => assert_eq!(
+            error.to_string(),
+            "duplicate url `https://example.com/1` at news_items[0], news_items[3]; \
+             duplicate url `https://example.com/3` at news_items[1], news_items[4]"
+        ),
+        result => panic!("unexpected result: {:?}", result),
+    }
+
+    let mut news_items = news_items;
+    news_items.curate();
+    let json = serde_json::to_string(&news_items).unwrap();
+    assert_eq!(
+        NewsItems::from_json_strict(&json).unwrap().news_items.len(),
+        3
+    );
+    assert!(matches!(
+        NewsItems::from_json_strict("{}"),
+        Err(StrictLoadError::Json(_))
+    ));
+}
+
+/// URLが既定のポリシーで検証されることをテストします。
+#[test]
+fn news_url_default_policy() {
+    let url = "https://www.pref.kyoto.jp/kentai/news/index.html"
+        .parse::<NewsUrl>()
+        .unwrap();
+    assert_eq!(url, "https://www.pref.kyoto.jp/kentai/news/index.html");
+    assert!("http://example.com/".parse::<NewsUrl>().is_ok());
+
+    assert!(matches!(
+        "#news".parse::<NewsUrl>(),
+        Err(UrlError::FragmentOnly(_))
+    ));
+    assert!(matches!(
+        "/kentai/news/index.html".parse::<NewsUrl>(),
+        Err(UrlError::Relative(_))
+    ));
+    assert!(matches!(
+        "ftp://example.com/file".parse::<NewsUrl>(),
+        Err(UrlError::UnsupportedScheme { .. })
+    ));
+    assert!(matches!(
+        "https://exa mple.com/".parse::<NewsUrl>(),
+        Err(UrlError::Invalid { .. })
+    ));
+
+    // デシリアライズの際に、URLの位置とともにエラーを報告する
+    let json = r#"{"date":"2020/04/01","text":"お知らせ","url":"news/20200401.html"}"#;
+    let error = serde_json::from_str::<NewsItem>(json).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "url `news/20200401.html` is relative; an absolute URL starting with `https://` is required at line 1 column 69"
+    );
+}
+
+/// 検証したURLが書き換えられず、入力された文字列のまま出力されることをテストします。
+#[test]
+fn news_url_preserves_input() {
+    let json = r#"{"news_items":[{"date":"2020/04/01","text":"お知らせ","url":"https://www.pref.kyoto.jp/お知らせ/新型コロナ.html"},{"date":"2020/04/02","text":"対策サイト","url":"https://kyoto.stopcovid19.jp"}]}"#;
+    let news_items = UrlPolicy::kyoto().news_items_from_json(json).unwrap();
+    assert_eq!(serde_json::to_string(&news_items).unwrap(), json);
+
+    // パースされたURLは、正規化された形式で参照できる
+    let url = &news_items.news_items[0].url;
+    assert_eq!(
+        url.as_str(),
+        "https://www.pref.kyoto.jp/お知らせ/新型コロナ.html"
+    );
+    assert!(url.as_url().as_str().contains("%E3%81%8A"));
+    assert_eq!(
+        news_items.news_items[1].url.as_url().as_str(),
+        "https://kyoto.stopcovid19.jp/"
+    );
+}
+
+/// 設定したポリシーで、URLを検証できることをテストします。
+#[test]
+fn news_url_kyoto_policy() {
+    let policy = UrlPolicy::kyoto();
+    assert!(policy
+        .parse("https://www.pref.kyoto.jp/kentai/news/index.html")
+        .is_ok());
+    assert!(policy
+        .parse("https://www.city.kyoto.lg.jp/hokenfukushi/page/0000268303.html")
+        .is_ok());
+    assert!(matches!(
+        policy.parse("http://www.pref.kyoto.jp/"),
+        Err(UrlError::HttpsRequired(_))
+    ));
+    match policy.parse("https://notpref.kyoto.jp/") {
+        Err(error @ UrlError::DomainNotAllowed { .. }) => assert_eq!(
+            error.to_string(),
+            "url `https://notpref.kyoto.jp/` has host `notpref.kyoto.jp`, which is not in the allowed domains (pref.kyoto.jp, city.kyoto.lg.jp, kyoto.stopcovid19.jp)"
+        ),
+        result => panic!("unexpected result: {:?}", result),
+    }
+
+    let news_items = NewsItems {
+        news_items: vec![
+            news_item(1, "京都府", "https://www.pref.kyoto.jp/"),
+            news_item(2, "外部サイト", "https://example.com/"),
+        ],
+    };
+    assert!(matches!(
+        policy.validate_all(&news_items),
+        Err((1, UrlError::DomainNotAllowed { .. }))
+    ));
+
+    // 読み込みの際に、ポリシーを満たさないURLを報告する
+    let json = serde_json::to_string(&news_items).unwrap();
+    assert!(UrlPolicy::default().news_items_from_json(&json).is_ok());
+    let error = policy.news_items_from_json(&json).unwrap_err();
+    assert!(error
+        .to_string()
+        .starts_with("url `https://example.com/` has host"));
+}
+
+/// 言語ごとの本文を持つお知らせを、読み書きできることをテストします。
+#[test]
+fn news_localized_round_trip() {
+    let item = test_data_localized_news_item();
+    let serialized = serde_json::to_string(&item).unwrap();
+    assert_eq!(
+        serialized,
+        r#"{"date":"2020/03/25","text":{"ja":"京都府 新型コロナウイルス感染症 対策サイト","en":"Kyoto Prefecture COVID-19 Information Site","ko":"교토부 신종 코로나바이러스 감염증 대책 사이트"},"url":"https://kyoto.stopcovid19.jp/"}"#
+    );
+    let deserialized = serde_json::from_str::<LocalizedNewsItems>(&format!(
+        r#"{{"news_items":[{}]}}"#,
+        serialized
+    ))
+    .unwrap();
+    assert_eq!(
+        serde_json::to_string(&deserialized.news_items[0]).unwrap(),
+        serialized
+    );
+
+    // 文字列のみの本文は、日本語の本文として扱う
+    let flat = serde_json::to_string(&test_data_news_item()).unwrap();
+    let deserialized =
+        serde_json::from_str::<LocalizedNewsItems>(&format!(r#"{{"news_items":[{}]}}"#, flat))
+            .unwrap();
+    assert_eq!(
+        deserialized.news_items[0].text.ja,
+        test_data_news_item().text
+    );
+    assert!(deserialized.news_items[0].text.en.is_none());
+}
+
+/// 言語ごとの本文が不正な場合に、エラーとなることをテストします。
+#[test]
+fn news_localized_invalid() {
+    for text in [
+        r#"{"en":"English only"}"#,
+        r#"{"ja":"日本語","fr":"Français"}"#,
+        r#"{"ja":"日本語","ja":"日本語"}"#,
+    ] {
+        let json = format!(
+            r#"{{"date":"2020/03/25","text":{},"url":"https://kyoto.stopcovid19.jp/"}}"#,
+            text
+        );
+        assert!(serde_json::from_str::<LocalizedNewsItem>(&json).is_err());
+    }
+    assert_eq!("en".parse::<Locale>().unwrap(), Locale::En);
+    assert!("fr".parse::<Locale>().is_err());
+}
+
+/// 単一の言語を選択した場合に、NewsItemと同じ形式で出力されることをテストします。
+#[test]
+fn news_localized_select() {
+    let news_items = LocalizedNewsItems {
+        news_items: vec![
+            test_data_localized_news_item(),
+            test_data_news_item().into(),
+        ],
+    };
+
+    let ja = news_items.select(Locale::Ja, Fallback::Skip);
+    assert_eq!(
+        serde_json::to_string(&ja).unwrap(),
+        serde_json::to_string(&NewsItems {
+            news_items: vec![test_data_news_item(), test_data_news_item()],
+        })
+        .unwrap()
+    );
+
+    let en = news_items.select(Locale::En, Fallback::Skip);
+    assert_eq!(en.news_items.len(), 1);
+    assert_eq!(
+        serde_json::to_string(&en).unwrap(),
+        r#"{"news_items":[{"date":"2020/03/25","text":"Kyoto Prefecture COVID-19 Information Site","url":"https://kyoto.stopcovid19.jp/"}]}"#
+    );
+
+    let zh = news_items.localize(Locale::Zh, Fallback::Japanese);
+    let text = test_data_news_item().text;
+    assert_eq!(texts(&zh.news_items), [text.as_str(), text.as_str()]);
+    assert!(news_items
+        .localize(Locale::Zh, Fallback::Skip)
+        .news_items
+        .is_empty());
+    assert_eq!(
+        serde_json::to_string(&news_items.localize(Locale::En, Fallback::Japanese)).unwrap(),
+        serde_json::to_string(&news_items.select(Locale::En, Fallback::Japanese)).unwrap()
+    );
+}
+
+/// 版数を含むお知らせの一覧を、URLを検証しながら読み込めることをテストします。
+#[test]
+fn news_versioned() {
+    let v1 = include_str!("fixtures/v1/news.json");
+    let v2 = include_str!("fixtures/v2/news.json");
+    let current = UrlPolicy::kyoto().news_items_from_json(v1).unwrap();
+    assert_eq!(
+        UrlPolicy::kyoto().news_items_from_json(v2).unwrap(),
+        current
+    );
+    assert_eq!(NewsItems::from_json_strict(v2).unwrap(), current);
+
+    let error = UrlPolicy::kyoto()
+        .news_items_from_json(r#"{"version":99,"news_items":[]}"#)
+        .unwrap_err();
+    assert!(
+        error.to_string().contains("unsupported version 99"),
+        "{}",
+        error
+    );
+    match NewsItems::from_json_strict(r#"{"version":99,"news_items":[]}"#) {
+        Err(StrictLoadError::Json(error)) => {
+            assert!(error.to_string().contains("unsupported version 99"))
+        }
+        result => panic!("unexpected result: {:?}", result),
+    }
+}
@@ -0,0 +1,199 @@
+use crate::{
+    population::{age_band::AgeBand, PopulationDataset, PopulationError, Populations, PREFECTURE},
+    status,
+    structs::status::Attributes,
+    summary,
+};
+
+/// テスト用の人口のデータセットです。(実際の人口ではありません)
+const TEST_CSV: &str = "region,age_band,population
+京都府,,2000000
+京都府,10歳未満,200000
+京都府,90歳以上,50000
+京都市,,1000000
+";
+
+/// テスト用の人口のデータセットを読み込みます。
+fn test_data_dataset(year: u16) -> PopulationDataset {
+    PopulationDataset::from_csv(year, "テスト", TEST_CSV.as_bytes()).unwrap()
+}
+
+/// 年代の表記と年齢の区分をテストします。
+#[test]
+fn population_age_band() {
+    assert_eq!(AgeBand::of(0), AgeBand::Under10);
+    assert_eq!(AgeBand::of(19), AgeBand::Teens);
+    assert_eq!(AgeBand::of(89), AgeBand::Eighties);
+    assert_eq!(AgeBand::of(90), AgeBand::Over90);
+    assert_eq!(AgeBand::of(105), AgeBand::Over90);
+    for age_band in AgeBand::ALL {
+        assert_eq!(age_band.as_str().parse::<AgeBand>().unwrap(), age_band);
+    }
+    assert!("100代".parse::<AgeBand>().is_err());
+    assert_eq!(
+        serde_json::to_string(&AgeBand::Over90).unwrap(),
+        "\"90歳以上\""
+    );
+    assert_eq!(
+        serde_json::from_str::<AgeBand>("\"10代\"").unwrap(),
+        AgeBand::Teens
+    );
+}
+
+/// CSV形式のデータセットを読み込み、人口を参照できることをテストします。
+#[test]
+fn population_dataset_from_csv() {
+    let dataset = test_data_dataset(2020);
+    assert_eq!(dataset.population(PREFECTURE, None).unwrap(), 2_000_000);
+    assert_eq!(
+        dataset
+            .population(PREFECTURE, Some(AgeBand::Under10))
+            .unwrap(),
+        200_000
+    );
+    assert!(matches!(
+        dataset.population("京都市", Some(AgeBand::Teens)),
+        Err(PopulationError::MissingAgeBand { .. })
+    ));
+    assert!(matches!(
+        dataset.population("大阪府", None),
+        Err(PopulationError::UnknownRegion(_))
+    ));
+    assert_eq!(dataset.per_100k("京都市", None, 25.0).unwrap(), 2.5);
+
+    for invalid in [
+        "region,age_band,population\n京都府,,1\n京都府,,2\n",
+        "region,age_band,population\n京都府,,1\n京都府,100代,2\n",
+        "region,age_band,population\n京都府,10代,1\n",
+    ] {
+        assert!(matches!(
+            PopulationDataset::from_csv(2020, "テスト", invalid.as_bytes()),
+            Err(PopulationError::InvalidRecord { .. })
+        ));
+    }
+    let zero = PopulationDataset::from_csv(
+        2020,
+        "テスト",
+        "region,age_band,population\n京都府,,0\n".as_bytes(),
+    )
+    .unwrap();
+    assert!(matches!(
+        zero.per_100k(PREFECTURE, None, 1.0),
+        Err(PopulationError::ZeroPopulation { .. })
+    ));
+}
+
+/// 調査年を指定して、データセットを選択できることをテストします。
+#[test]
+fn population_select_year() {
+    let mut populations = Populations::new();
+    assert!(matches!(
+        populations.select(None),
+        Err(PopulationError::UnknownYear(None))
+    ));
+    populations.insert(test_data_dataset(2015));
+    populations.insert(test_data_dataset(2020));
+    assert_eq!(populations.years().collect::<Vec<u16>>(), vec![2015, 2020]);
+    assert_eq!(populations.select(None).unwrap().year, 2020);
+    assert_eq!(populations.select(Some(2015)).unwrap().year, 2015);
+    assert!(matches!(
+        populations.select(Some(2010)),
+        Err(PopulationError::UnknownYear(Some(2010)))
+    ));
+    // 同梱するデータセットは、全て正しく読み込める
+    for year in Populations::bundled().years() {
+        assert!(Populations::bundled().select(Some(year)).is_ok());
+    }
+}
+
+/// 同梱するデータセットが、国勢調査の公表値と一致することをテストします。
+#[test]
+fn population_bundled() {
+    let populations = Populations::bundled();
+    assert_eq!(populations.years().collect::<Vec<u16>>(), vec![2015, 2020]);
+    let dataset = populations.select(None).unwrap();
+    assert_eq!(dataset.year, 2020);
+    assert_eq!(dataset.population(PREFECTURE, None).unwrap(), 2_578_087);
+    assert_eq!(dataset.population("京都市", None).unwrap(), 1_463_723);
+    let dataset = populations.select(Some(2015)).unwrap();
+    assert_eq!(dataset.population(PREFECTURE, None).unwrap(), 2_610_353);
+    assert_eq!(dataset.population("京都市", None).unwrap(), 1_475_183);
+}
+
+/// 同梱するデータセットが、京都府と京都市の総人口のみを含むことをテストします。
+///
+/// その他の市町村や年代ごとの人口を同梱した場合は、このテストを公表値の照合に置き換えてください。
+#[test]
+fn population_bundled_scope() {
+    let populations = Populations::bundled();
+    for year in [2015, 2020] {
+        let dataset = populations.select(Some(year)).unwrap();
+        assert!(matches!(
+            dataset.population("宇治市", None),
+            Err(PopulationError::UnknownRegion(region)) if region == "宇治市"
+        ));
+        for age_band in AgeBand::ALL {
+            for region in [PREFECTURE, "京都市"] {
+                assert!(matches!(
+                    dataset.population(region, Some(age_band)),
+                    Err(PopulationError::MissingAgeBand { .. })
+                ));
+            }
+        }
+    }
+
+    // 同梱していない人口は、データセットを置き換えて追加できる
+    let mut populations = Populations::bundled();
+    populations.insert(test_data_dataset(2020));
+    assert_eq!(
+        populations
+            .select(Some(2020))
+            .unwrap()
+            .population(PREFECTURE, Some(AgeBand::Under10))
+            .unwrap(),
+        200_000
+    );
+}
+
+/// 小計の一覧とCOVID-19に関する情報の値を、人口10万人あたりに換算できることをテストします。
+#[test]
+fn population_per_100k() {
+    let dataset = test_data_dataset(2020);
+    let summary = summary!(
+        last_update: "2020-03-25T21:40:00+09:00";
+        "2020/03/24" => 10,
+        "2020/03/25" => 40
+    )
+    .unwrap();
+    let series = summary.per_100k(&dataset, PREFECTURE, None).unwrap();
+    assert_eq!(
+        series.data.iter().map(|v| v.rate).collect::<Vec<f64>>(),
+        vec![0.5, 2.0]
+    );
+    let series = summary
+        .per_100k(&dataset, PREFECTURE, Some(AgeBand::Over90))
+        .unwrap();
+    assert_eq!(series.data[1].rate, 80.0);
+    let json = serde_json::to_value(&series).unwrap();
+    assert_eq!(json["data"][0]["date"], "2020/03/24");
+    assert_eq!(json["age_band"], "90歳以上");
+    assert_eq!(json["year"], 2020);
+    assert!(summary.per_100k(&dataset, "大阪府", None).is_err());
+
+    let status = status!(Patients => 300 {
+        Hospitalizations => 20
+    })
+    .unwrap();
+    assert_eq!(
+        status
+            .per_100k(&Attributes::Hospitalizations, &dataset, "京都市")
+            .unwrap(),
+        Some(2.0)
+    );
+    assert_eq!(
+        status
+            .per_100k(&Attributes::Dead, &dataset, "京都市")
+            .unwrap(),
+        None
+    );
+}
@@ -0,0 +1,159 @@
+use crate::{
+    structs::summary::{
+        revision::{Revision, SummaryRevisions},
+        Summary, SummaryContent,
+    },
+    summary,
+    tests::structs_test::dummy_localdate,
+};
+use chrono::NaiveDate;
+
+/// 日付を生成します。
+fn date(day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2020, 3, day).unwrap()
+}
+
+/// 過去の日付の小計の訂正が、以前と新しい値とともに検出されることをテストします。
+#[test]
+fn revision_changed_dates() {
+    let previous = summary!(
+        last_update: "2020/03/27 21:40";
+        "2020/03/24" => 4,
+        "2020/03/25" => 10,
+        "2020/03/26" => 5,
+        "2020/03/27" => 3
+    )
+    .unwrap();
+    // 3月25日の重複計上を訂正し、3月28日の小計を追加した版
+    let current = summary!(
+        last_update: "2020/03/28 21:40";
+        "2020/03/24" => 4,
+        "2020/03/25" => 8,
+        "2020/03/26" => 6,
+        "2020/03/27" => 3,
+        "2020/03/28" => 7
+    )
+    .unwrap();
+
+    let revisions = current.revisions_since(&previous);
+    assert_eq!(
+        revisions.revisions,
+        vec![
+            Revision {
+                date: date(25),
+                old: Some(10),
+                new: Some(8),
+            },
+            Revision {
+                date: date(26),
+                old: Some(5),
+                new: Some(6),
+            },
+        ]
+    );
+    assert_eq!(revisions.net_effect(), -1);
+    assert_eq!(revisions.previous_total, 22);
+    assert_eq!(revisions.revised_total, 21);
+    assert_eq!(
+        revisions.to_string(),
+        "2020/03/27 21:40 -> 2020/03/28 21:40: 2 revision(s), total 22 -> 21 (-1)\n  2020/03/25: 10 -> 8 (-2)\n  2020/03/26: 5 -> 6 (+1)\n"
+    );
+}
+
+/// 追加・削除された過去の日付が、訂正として検出されることをテストします。
+#[test]
+fn revision_added_and_removed_dates() {
+    let previous = summary!(
+        last_update: "2020/03/27 21:40";
+        "2020/03/24" => 4,
+        "2020/03/26" => 5,
+        "2020/03/27" => 3
+    )
+    .unwrap();
+    let current = summary!(
+        last_update: "2020/03/28 21:40";
+        "2020/03/25" => 2,
+        "2020/03/26" => 5,
+        "2020/03/27" => 3
+    )
+    .unwrap();
+
+    let revisions = current.revisions_since(&previous);
+    assert_eq!(
+        revisions.revisions,
+        vec![
+            Revision {
+                date: date(24),
+                old: Some(4),
+                new: None,
+            },
+            Revision {
+                date: date(25),
+                old: None,
+                new: Some(2),
+            },
+        ]
+    );
+    assert_eq!(revisions.net_effect(), -2);
+    assert_eq!(
+        revisions.revisions[0].to_string(),
+        "2020/03/24: 4 -> - (-4)"
+    );
+}
+
+/// 訂正が存在しない場合と、以前の版が空の場合をテストします。
+#[test]
+fn revision_none() {
+    let previous = summary!(last_update: "2020/03/27 21:40"; "2020/03/27" => 3).unwrap();
+    let current = summary!(
+        last_update: "2020/03/28 21:40";
+        "2020/03/27" => 3,
+        "2020/03/28" => 7
+    )
+    .unwrap();
+    let revisions = current.revisions_since(&previous);
+    assert!(revisions.is_empty());
+    assert_eq!(revisions.net_effect(), 0);
+
+    let empty = summary!(last_update: "2020/03/26 21:40";).unwrap();
+    assert_eq!(
+        current.revisions_since(&empty),
+        SummaryRevisions {
+            previous_last_update: empty.last_update,
+            current_last_update: current.last_update,
+            revisions: Vec::new(),
+            previous_total: 0,
+            revised_total: 0,
+        }
+    );
+}
+
+/// 同じ日付の小計の合計が`u32`の範囲を超える場合も、訂正を検出できることをテストします。
+#[test]
+fn revision_large_sums() {
+    let summary = |sums: &[u32]| Summary {
+        data: sums
+            .iter()
+            .map(|sum| SummaryContent {
+                date: date(25),
+                sum: *sum,
+            })
+            .collect(),
+        last_update: dummy_localdate(),
+    };
+    let previous = summary(&[u32::MAX, 5]);
+    let current = summary(&[u32::MAX, 6]);
+
+    let revisions = current.revisions_since(&previous);
+    let total = u64::from(u32::MAX);
+    assert_eq!(
+        revisions.revisions,
+        vec![Revision {
+            date: date(25),
+            old: Some(total + 5),
+            new: Some(total + 6),
+        }]
+    );
+    assert_eq!(revisions.net_effect(), 1);
+    assert_eq!(revisions.previous_total, total + 5);
+}
@@ -0,0 +1,104 @@
+use crate::{
+    convert::{read, write, Format},
+    document::Document,
+    structs::{
+        age_distribution::AgeDistribution,
+        last_update::LastUpdate,
+        news::{localized::LocalizedNewsItems, NewsItems},
+        status::Status,
+        summary::{DateRepresentation, Summary},
+        vaccination::Vaccination,
+    },
+};
+use chrono::Local;
+use proptest::prelude::*;
+use serde::{de::DeserializeOwned, Serialize};
+use std::fmt::Debug;
+
+/// JSON形式でシリアライズした後、デシリアライズした結果が元の値と一致するか検証します。
+fn assert_json_round_trip<T>(value: &T) -> Result<(), TestCaseError>
+where
+    T: Serialize + DeserializeOwned + PartialEq + Debug,
+{
+    let serialized = serde_json::to_string(value).unwrap();
+    let deserialized = serde_json::from_str::<T>(&serialized)
+        .map_err(|error| TestCaseError::fail(format!("{}: {}", error, serialized)))?;
+    prop_assert_eq!(&deserialized, value);
+    Ok(())
+}
+
+/// 有効な全てのデータ形式で書き出した後、読み込んだ結果が元の構造体と一致するか検証します。
+fn assert_convert_round_trip(document: &Document, formats: &[Format]) -> Result<(), TestCaseError> {
+    for format in formats.iter().filter(|format| format.is_supported()) {
+        let written = write(document, *format).unwrap();
+        let read = read(*format, document.document_type(), &written)
+            .map_err(|error| TestCaseError::fail(format!("{}: {}", format, error)))?;
+        prop_assert_eq!(&read, document, "{}", format);
+    }
+    Ok(())
+}
+
+proptest! {
+    /// 無作為なStatusの階層が、シリアライズ・デシリアライズの前後で一致することをテストします。
+    #[test]
+    fn roundtrip_status(status in any::<Status>()) {
+        assert_json_round_trip(&status)?;
+        assert_convert_round_trip(&Document::Status(status), Format::ALL)?;
+    }
+
+    /// 無作為なSummaryが、シリアライズ・デシリアライズの前後で一致することをテストします。
+    #[test]
+    fn roundtrip_summary(summary in any::<Summary>()) {
+        assert_json_round_trip(&summary)?;
+        assert_convert_round_trip(&Document::Summary(summary.clone()), Format::ALL)?;
+
+        // 以前のRFC3339形式で書き出した場合も、同じ日付として読み込めること
+        let legacy =
+            serde_json::to_string(&summary.with_dates(DateRepresentation::Legacy)).unwrap();
+        prop_assert_eq!(serde_json::from_str::<Summary>(&legacy).unwrap(), summary);
+    }
+
+    /// 無作為なお知らせの一覧が、シリアライズ・デシリアライズの前後で一致することをテストします。
+    #[test]
+    fn roundtrip_news_items(news_items in any::<NewsItems>()) {
+        assert_json_round_trip(&news_items)?;
+        assert_convert_round_trip(&Document::News(news_items), Format::ALL)?;
+    }
+
+    /// 無作為な複数の言語のお知らせが、シリアライズ・デシリアライズの前後で一致することをテストします。
+    #[test]
+    fn roundtrip_localized_news_items(news_items in any::<LocalizedNewsItems>()) {
+        assert_json_round_trip(&news_items)?;
+    }
+
+    /// 無作為な最終更新日時が、シリアライズ・デシリアライズの前後で一致することをテストします。
+    #[test]
+    fn roundtrip_last_update(last_update in any::<LastUpdate<Local>>()) {
+        assert_json_round_trip(&last_update)?;
+        assert_convert_round_trip(&Document::LastUpdate(last_update), Format::ALL)?;
+    }
+
+    /// 無作為な年代別陽性者数が、シリアライズ・デシリアライズの前後で一致することをテストします。
+    ///
+    /// CSV形式は年代別陽性者数を表現しないため、CSV形式を除いた各形式を検証します。
+    #[test]
+    fn roundtrip_age_distribution(distribution in any::<AgeDistribution>()) {
+        assert_json_round_trip(&distribution)?;
+        assert_convert_round_trip(
+            &Document::AgeDistribution(distribution),
+            &[Format::Json, Format::Yaml, Format::MessagePack],
+        )?;
+    }
+
+    /// 無作為なワクチン接種の実績が、シリアライズ・デシリアライズの前後で一致することをテストします。
+    ///
+    /// CSV形式はワクチン接種の実績を表現しないため、CSV形式を除いた各形式を検証します。
+    #[test]
+    fn roundtrip_vaccination(vaccination in any::<Vaccination>()) {
+        assert_json_round_trip(&vaccination)?;
+        assert_convert_round_trip(
+            &Document::Vaccination(vaccination),
+            &[Format::Json, Format::Yaml, Format::MessagePack],
+        )?;
+    }
+}
@@ -0,0 +1,190 @@
+use crate::{
+    document::DocumentType,
+    store::{Store, StoreError},
+    structs::{last_update::LastUpdate, status::Status},
+    summary,
+    tests::structs_test::{
+        test_data_news_items, test_data_status, test_data_status_with_children, test_data_summary,
+    },
+    utils::formats::jst,
+};
+use chrono::{DateTime, Local, TimeZone};
+use proptest::prelude::*;
+
+/// 日本標準時の日時を、DateTime<Local>型で返却します。
+fn jst_datetime(day: u32, hour: u32) -> DateTime<Local> {
+    jst()
+        .with_ymd_and_hms(2020, 3, day, hour, 0, 0)
+        .unwrap()
+        .with_timezone(&Local)
+}
+
+/// 保存したスナップショットが、最終更新日時を指定して読み込めることをテストします。
+#[test]
+fn store_round_trip() {
+    let mut store = Store::open_in_memory().unwrap();
+    let summary = test_data_summary();
+    let status = test_data_status_with_children();
+    let news_items = test_data_news_items();
+    let last_update = LastUpdate {
+        datetime: summary.last_update,
+    };
+
+    store.save_summary(&summary).unwrap();
+    store.save_status(&status).unwrap();
+    store.save_news_items(&news_items, &last_update).unwrap();
+
+    assert_eq!(
+        store.load_summary(summary.last_update).unwrap(),
+        Some(summary)
+    );
+    assert_eq!(
+        store.load_status(status.last_update.unwrap()).unwrap(),
+        Some(status)
+    );
+    assert_eq!(
+        store.load_news_items(last_update.datetime).unwrap(),
+        Some(news_items)
+    );
+    assert_eq!(
+        store.snapshots(DocumentType::LastUpdate).unwrap(),
+        vec![last_update.datetime]
+    );
+    assert_eq!(store.load_summary("2020/03/26 21:40").unwrap(), None);
+}
+
+/// 同じ最終更新日時のスナップショットが、置き換えられることをテストします。
+#[test]
+fn store_replace_snapshot() {
+    let mut store = Store::open_in_memory().unwrap();
+    let mut summary = test_data_summary();
+    store.save_summary(&summary).unwrap();
+    summary.data[0].sum = 20;
+    store.save_summary(&summary).unwrap();
+
+    assert_eq!(store.snapshots(DocumentType::Summary).unwrap().len(), 1);
+    assert_eq!(
+        store.load_summary(summary.last_update).unwrap(),
+        Some(summary)
+    );
+}
+
+/// 指定された日付の時点で最新であったスナップショットが、読み込まれることをテストします。
+#[test]
+fn store_as_of() {
+    let mut store = Store::open_in_memory().unwrap();
+    let statuses = [(25, 21, 100), (26, 21, 120), (27, 9, 150)].map(|(day, hour, value)| Status {
+        value,
+        last_update: Some(jst_datetime(day, hour)),
+        ..test_data_status()
+    });
+    for status in &statuses {
+        store.save_status(status).unwrap();
+    }
+
+    assert_eq!(store.status_as_of("2020/03/24").unwrap(), None);
+    assert_eq!(
+        store.status_as_of("2020/03/25").unwrap().unwrap().value,
+        100
+    );
+    assert_eq!(
+        store.status_as_of("2020-03-26").unwrap().unwrap().value,
+        120
+    );
+    assert_eq!(
+        store.status_as_of("令和2年4月1日").unwrap().unwrap().value,
+        150
+    );
+    assert_eq!(
+        store.snapshots(DocumentType::Status).unwrap(),
+        statuses.map(|status| status.last_update.unwrap())
+    );
+
+    let older = test_data_news_items();
+    let mut newer = older.clone();
+    newer.news_items[0].text = "更新されたお知らせ".to_string();
+    for (news_items, day) in [(&older, 25), (&newer, 27)] {
+        let last_update = LastUpdate {
+            datetime: jst_datetime(day, 0),
+        };
+        store.save_news_items(news_items, &last_update).unwrap();
+    }
+    assert_eq!(store.news_items_as_of("2020/03/26").unwrap(), Some(older));
+    assert_eq!(store.news_items_as_of("2020/03/27").unwrap(), Some(newer));
+}
+
+/// 最新の小計の一覧から、指定された期間の小計が読み込まれることをテストします。
+#[test]
+fn store_summary_between() {
+    let mut store = Store::open_in_memory().unwrap();
+    let older =
+        summary!(last_update: "2020-03-27T21:40:00+09:00"; "2020/03/25" => 2, "2020/03/26" => 5)
+            .unwrap();
+    let newer = summary!(
+        last_update: "2020-03-28T21:40:00+09:00";
+        "2020/03/25" => 2,
+        "2020/03/26" => 6,
+        "2020/03/27" => 3,
+        "2020/03/28" => 4
+    )
+    .unwrap();
+    store.save_summary(&newer).unwrap();
+    store.save_summary(&older).unwrap();
+
+    let between = store
+        .summary_between("2020/03/26", "2020/03/27")
+        .unwrap()
+        .unwrap();
+    assert_eq!(between.last_update, newer.last_update);
+    assert_eq!(between.data, newer.data[1..3]);
+    assert!(store
+        .summary_between("2020/03/27", "2020/03/26")
+        .unwrap()
+        .unwrap()
+        .data
+        .is_empty());
+
+    assert_eq!(store.summary_as_of("2020/03/27").unwrap(), Some(older));
+}
+
+/// 保存・読み込みできない値について、エラーとなることをテストします。
+#[test]
+fn store_errors() {
+    let mut store = Store::open_in_memory().unwrap();
+    assert!(matches!(
+        store.save_status(&test_data_status()),
+        Err(StoreError::MissingLastUpdate)
+    ));
+    assert!(matches!(
+        store.status_as_of("2020/02/30"),
+        Err(StoreError::InvalidDate(_))
+    ));
+}
+
+/// 保存したデータベースを、再度開いて読み込めることをテストします。
+#[test]
+fn store_open_file() {
+    let path =
+        std::env::temp_dir().join(format!("covid19-kyoto-store-{}.sqlite", std::process::id()));
+    let summary = test_data_summary();
+    Store::open(&path).unwrap().save_summary(&summary).unwrap();
+    let loaded = Store::open(&path)
+        .unwrap()
+        .load_summary(summary.last_update);
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(loaded.unwrap(), Some(summary));
+}
+
+proptest! {
+    /// 無作為なStatusの階層が、保存・読み込みの前後で一致することをテストします。
+    #[test]
+    fn store_status_round_trip(status in any::<Status>(), last_update in any::<LastUpdate<Local>>()) {
+        let status = Status {
+            last_update: Some(last_update.datetime),
+            ..status
+        };
+        let mut store = Store::open_in_memory().unwrap();
+        store.save_status(&status).unwrap();
+        prop_assert_eq!(store.load_status(last_update.datetime).unwrap(), Some(status));
+    }
+}
@@ -0,0 +1,188 @@
+use crate::{
+    document::{Document, DocumentType},
+    stream::{
+        patients::PatientReader,
+        summary::{SummaryReader, SummaryWriter},
+        StreamError,
+    },
+    structs::{age_distribution::AgeDistribution, summary::Summary},
+    tests::{
+        age_distribution_test::test_data_patient_records,
+        structs_test::{dummy_localdate, test_data_summary},
+    },
+};
+use std::convert::Infallible;
+
+const SUMMARY_JSON: &str = r#"{"data":[{"date":"2020-03-25T09:40:00+00:00","sum":10},{"date":"2020-03-26T09:40:00+00:00","sum":3}],"last_update":"2020/03/26 21:25"}"#;
+
+/// Summaryを小計ごとに読み込めることをテストします。
+#[test]
+fn stream_summary_reader() {
+    let mut data = Vec::new();
+    let last_update = SummaryReader::new(SUMMARY_JSON.as_bytes())
+        .for_each(|content| {
+            data.push(content);
+            Ok::<_, Infallible>(())
+        })
+        .unwrap();
+
+    let summary = serde_json::from_str::<Summary>(SUMMARY_JSON).unwrap();
+    assert_eq!(last_update, summary.last_update);
+    assert_eq!(data.len(), 2);
+    assert_eq!(
+        serde_json::to_string(&data).unwrap(),
+        serde_json::to_string(&summary.data).unwrap()
+    );
+}
+
+/// 不正なSummaryを読み込んだ際に、エラーとなることをテストします。
+#[test]
+fn stream_summary_reader_invalid() {
+    let serialized = r#"{"data":[{"date":"2020-03-25T09:40:00+00:00","sum":10}]}"#;
+    assert!(SummaryReader::new(serialized.as_bytes())
+        .for_each(|_| Ok::<_, Infallible>(()))
+        .is_err());
+
+    let serialized = r#"{"data":[{"date":"2020-03-25T09:40:00+00:00","sum":-1}],"last_update":"2020/03/26 21:25"}"#;
+    assert!(SummaryReader::new(serialized.as_bytes())
+        .for_each(|_| Ok::<_, Infallible>(()))
+        .is_err());
+}
+
+/// 関数が返却したエラーにより、以降の小計を読み込まずに中断することをテストします。
+#[test]
+fn stream_summary_reader_callback_error() {
+    let mut count = 0;
+    let error = SummaryReader::new(SUMMARY_JSON.as_bytes())
+        .for_each(|content| {
+            count += 1;
+            match content.sum {
+                10 => Err("stop"),
+                _ => Ok(()),
+            }
+        })
+        .unwrap_err();
+    assert!(matches!(error, StreamError::Callback("stop")));
+    assert_eq!(count, 1);
+
+    // 読み込みのエラーは、JSON形式のエラーとして返却する
+    let serialized = r#"{"data":[],"last_update":"2020/03/26 21:25"} {}"#;
+    let error = SummaryReader::new(serialized.as_bytes())
+        .for_each(|_| Ok::<_, &str>(()))
+        .unwrap_err();
+    assert!(matches!(error, StreamError::Json(_)));
+}
+
+/// 陽性者の個票の配列を個票ごとに読み込み、一括で読み込んだ場合と同じ結果を集計できることをテストします。
+#[test]
+fn stream_patient_reader() {
+    let records = test_data_patient_records();
+    let serialized = serde_json::to_string(&records).unwrap();
+
+    let mut read = Vec::new();
+    PatientReader::new(serialized.as_bytes())
+        .for_each(|record| {
+            read.push(record);
+            Ok::<_, Infallible>(())
+        })
+        .unwrap();
+    assert_eq!(read, records);
+    assert_eq!(
+        AgeDistribution::from_records(&read, dummy_localdate(), true),
+        AgeDistribution::from_records(&records, dummy_localdate(), true)
+    );
+
+    // 関数がエラーを返却した場合は、以降の個票を読み込まない
+    let mut count = 0;
+    let error = PatientReader::new(serialized.as_bytes())
+        .for_each(|_| {
+            count += 1;
+            if count == 2 {
+                Err("stop")
+            } else {
+                Ok(())
+            }
+        })
+        .unwrap_err();
+    assert!(matches!(error, StreamError::Callback("stop")));
+    assert_eq!(count, 2);
+
+    // 不正な個票はJSON形式のエラーとなる
+    let serialized = r#"[{"date":"2020/03/25","age_group":"不明"}]"#;
+    let error = PatientReader::new(serialized.as_bytes())
+        .for_each(|_| Ok::<_, Infallible>(()))
+        .unwrap_err();
+    assert!(matches!(error, StreamError::Json(_)));
+}
+
+/// Summaryを小計ごとに書き出した結果が、一括でシリアライズした結果と一致することをテストします。
+#[test]
+fn stream_summary_writer() {
+    let summary = test_data_summary();
+    let mut writer = SummaryWriter::new(Vec::new()).unwrap();
+    for content in &summary.data {
+        writer.write(content).unwrap();
+        writer.write(content).unwrap();
+    }
+    let written = writer.finish(&dummy_localdate()).unwrap();
+
+    let expected = Summary {
+        data: [summary.data.clone(), summary.data.clone()].concat(),
+        last_update: summary.last_update,
+    };
+    assert_eq!(
+        String::from_utf8(written).unwrap(),
+        serde_json::to_string(&expected).unwrap()
+    );
+
+    // 小計が存在しない場合
+    let written = SummaryWriter::new(Vec::new())
+        .unwrap()
+        .finish(&dummy_localdate())
+        .unwrap();
+    assert!(serde_json::from_slice::<Summary>(&written).is_ok());
+}
+
+/// 版数を含むSummaryを、小計ごとに現在の版に変換して読み込めることをテストします。
+#[test]
+fn stream_summary_reader_versioned() {
+    let v2 = include_str!("fixtures/v2/summary.json");
+    let Document::Summary(current) = Document::from_versioned_json(
+        DocumentType::Summary,
+        include_str!("fixtures/v3/summary.json"),
+    )
+    .unwrap() else {
+        unreachable!()
+    };
+
+    let mut data = Vec::new();
+    let last_update = SummaryReader::new(v2.as_bytes())
+        .for_each(|content| {
+            data.push(content);
+            Ok::<_, Infallible>(())
+        })
+        .unwrap();
+    assert_eq!(last_update, current.last_update);
+    assert_eq!(data, current.data);
+
+    // 版数がdataより後に含まれる場合も読み込める
+    let serialized = r#"{"data":[{"date":"2020-03-24T15:00:00+00:00","sum":2}],"last_update":"2020/03/27 21:40","version":2}"#;
+    let mut data = Vec::new();
+    SummaryReader::new(serialized.as_bytes())
+        .for_each(|content| {
+            data.push(content);
+            Ok::<_, Infallible>(())
+        })
+        .unwrap();
+    assert_eq!(data, current.data[..1]);
+
+    let serialized = r#"{"version":99,"data":[],"last_update":"2020/03/27 21:40"}"#;
+    let error = SummaryReader::new(serialized.as_bytes())
+        .for_each(|_| Ok::<_, Infallible>(()))
+        .unwrap_err();
+    assert!(
+        error.to_string().contains("unsupported version 99"),
+        "{}",
+        error
+    );
+}
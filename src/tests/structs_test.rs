@@ -1,14 +1,19 @@
 use crate::{
     structs::{
         last_update::LastUpdate,
-        news::{NewsItem, NewsItems},
+        news::{
+            borrowed::{NewsItemRef, NewsItemsRef},
+            localized::{LocalizedNewsItem, LocalizedText},
+            NewsItem, NewsItems,
+        },
         status::{Attributes as StatusAttributes, Status},
         summary::Summary,
-        summary::SummaryContent,
+        summary::{DateRepresentation, SummaryContent},
     },
     utils::formats::DATETIME_FORMAT,
 };
-use chrono::{DateTime, Local, NaiveDate, TimeZone, Utc};
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime};
+use std::borrow::Cow;
 
 /// LastUpdate構造体のシリアライズのテストを行います。
 #[test]
@@ -20,8 +25,8 @@ fn serialize_last_update() {
 #[test]
 fn deserialize_last_update() {
     let serialized = r#"{"last_update":"2020/03/25 21:40"}"#;
-    let deserialized = serde_json::from_str::<LastUpdate<Local>>(&serialized);
-    assert!(deserialized.is_ok());
+    let deserialized = serde_json::from_str::<LastUpdate<Local>>(serialized);
+    assert_eq!(deserialized.unwrap(), test_data_last_update());
 }
 
 #[test]
@@ -35,8 +40,8 @@ fn serialize_news_item() {
 /// NewsItem構造体のデシリアライズのテストを行います。
 fn deserialize_news_item() {
     let serialized = r#"{"date":"2020/03/25","text":"京都府 新型コロナウイルス感染症 対策サイト","url":"https://kyoto.stopcovid19.jp/"}"#;
-    let deserialized = serde_json::from_str::<NewsItem>(&serialized);
-    assert!(deserialized.is_ok());
+    let deserialized = serde_json::from_str::<NewsItem>(serialized);
+    assert_eq!(deserialized.unwrap(), test_data_news_item());
 }
 
 #[test]
@@ -53,8 +58,27 @@ fn serialize_news_items() {
 /// NewsItems構造体のデシリアライズのテストを行います。
 fn deserialize_news_items() {
     let serialized = r#"{"news_items":[{"date":"2020/03/25","text":"京都府 新型コロナウイルス感染症 対策サイト","url":"https://kyoto.stopcovid19.jp/"}]}"#;
-    let deserialized = serde_json::from_str::<NewsItems>(&serialized);
-    assert!(deserialized.is_ok());
+    let deserialized = serde_json::from_str::<NewsItems>(serialized);
+    assert_eq!(deserialized.unwrap(), test_data_news_items());
+}
+
+#[test]
+/// NewsItemsRef構造体のデシリアライズで、文字列が入力から借用されることをテストします。
+fn deserialize_news_items_ref() {
+    let serialized = r#"{"news_items":[{"date":"2020/03/25","text":"京都府 新型コロナウイルス感染症 対策サイト","url":"https:\/\/kyoto.stopcovid19.jp\/"}]}"#;
+    let deserialized = serde_json::from_str::<NewsItemsRef>(serialized).unwrap();
+    let item = &deserialized.news_items[0];
+    assert!(matches!(item.text, Cow::Borrowed(_)));
+    // エスケープを含む文字列は複製される
+    assert!(matches!(item.url, Cow::Owned(_)));
+    assert_eq!(item.url, "https://kyoto.stopcovid19.jp/");
+
+    // NewsItemと同じ形式でシリアライズされること
+    assert_eq!(
+        serde_json::to_string(&NewsItemRef::from(&test_data_news_item())).unwrap(),
+        serde_json::to_string(&test_data_news_item()).unwrap()
+    );
+    assert_eq!(deserialized.into_owned().unwrap(), test_data_news_items());
 }
 
 /// Status構造体のシリアライズのテストを行います。
@@ -69,8 +93,39 @@ fn serialize_status() {
 fn deserialize_status() {
     let serialized =
         r#"{"attr":"patients","value":4096,"children":[{"attr":"accommodations","value":32}]}"#;
-    let deserialized = serde_json::from_str::<Status>(&serialized);
-    assert!(deserialized.is_ok());
+    let deserialized = serde_json::from_str::<Status>(serialized);
+    assert_eq!(
+        deserialized.unwrap(),
+        Status {
+            last_update: None,
+            ..test_data_status_with_children()
+        }
+    );
+}
+
+/// Attributesの属性名が、シリアライズ結果と一致していることをテストします。
+#[test]
+fn attributes_as_str() {
+    for attr in StatusAttributes::ALL {
+        assert_eq!(
+            serde_json::to_string(attr).unwrap(),
+            format!("\"{}\"", attr.as_str())
+        );
+    }
+}
+
+/// Attributesの属性名から、同じ属性にデシリアライズできることをテストします。
+#[test]
+fn deserialize_attributes() {
+    for attr in StatusAttributes::ALL {
+        let serialized = format!("\"{}\"", attr.as_str());
+        assert_eq!(
+            &serde_json::from_str::<StatusAttributes>(&serialized).unwrap(),
+            attr
+        );
+    }
+    let error = serde_json::from_str::<StatusAttributes>("\"unknown\"").unwrap_err();
+    assert!(error.to_string().contains("unknown attribute `unknown`"));
 }
 
 /// Summary構造体のシリアライズのテストを行います。
@@ -82,9 +137,9 @@ fn serialize_summary() {
 /// Summary構造体のデシリアライズのテストを行います。
 #[test]
 fn deserialize_summary() {
-    let serialized = r#"{"data":[{"date":"2020-03-25T09:40:00.000Z", "sum": 10}], "last_update":"2020/03/25 21:25"}"#;
-    let deserialized = serde_json::from_str::<Summary>(&serialized);
-    assert!(deserialized.is_ok());
+    let serialized = r#"{"data":[{"date":"2020-03-25T09:40:00.000Z", "sum": 10}], "last_update":"2020/03/25 21:40"}"#;
+    let deserialized = serde_json::from_str::<Summary>(serialized);
+    assert_eq!(deserialized.unwrap(), test_data_summary());
 }
 
 /// SummaryContent構造体のシリアライズのテストを行います。
@@ -97,74 +152,204 @@ fn serialize_summary_content() {
 #[test]
 fn deserialize_summary_content() {
     let serialized = r#"{"date":"2020-03-25T09:40:00.000Z", "sum": 10}"#;
-    let deserialized = serde_json::from_str::<SummaryContent>(&serialized);
-    assert!(deserialized.is_ok());
+    let deserialized = serde_json::from_str::<SummaryContent>(serialized);
+    assert_eq!(deserialized.unwrap(), test_data_summary_content());
+}
+
+/// SummaryContent構造体の日付が、日本標準時の日付として読み書きされることをテストします。
+#[test]
+fn summary_content_calendar_day() {
+    let content = test_data_summary_content();
+    assert_eq!(
+        serde_json::to_string(&content).unwrap(),
+        r#"{"date":"2020/03/25","sum":10}"#
+    );
+    assert_eq!(
+        serde_json::to_string(&content.with_dates(DateRepresentation::Legacy)).unwrap(),
+        r#"{"date":"2020-03-24T15:00:00+00:00","sum":10}"#
+    );
+
+    // 以前の形式も、日本標準時の日付として読み込めること
+    for serialized in [
+        r#"{"date":"2020/03/25","sum":10}"#,
+        r#"{"date":"2020-03-24T15:00:00+00:00","sum":10}"#,
+        r#"{"date":"2020-03-25T09:25:00.000Z","sum":10}"#,
+    ] {
+        let deserialized = serde_json::from_str::<SummaryContent>(serialized).unwrap();
+        assert_eq!(deserialized.date, content.date, "{}", serialized);
+    }
+    assert_eq!(
+        content.timestamp().to_rfc3339(),
+        "2020-03-24T15:00:00+00:00"
+    );
+
+    let summary = test_data_summary();
+    let legacy = serde_json::to_string(&summary.with_dates(DateRepresentation::Legacy)).unwrap();
+    assert!(legacy.starts_with(r#"{"data":[{"date":"2020-03-24T15:00:00+00:00","sum":10}],"#));
+    let deserialized = serde_json::from_str::<Summary>(&legacy).unwrap();
+    assert_eq!(deserialized.data[0].date, content.date);
 }
 
 /// LastUpdate構造体のテスト用のデータを生成します。
-fn test_data_last_update() -> LastUpdate<Local> {
-    return LastUpdate {
+pub(crate) fn test_data_last_update() -> LastUpdate<Local> {
+    LastUpdate {
         datetime: dummy_localdate(),
-    };
+    }
 }
 
 /// NewsItem構造体のテスト用のデータを生成します。
-fn test_data_news_item() -> NewsItem {
-    return NewsItem {
-        date: NaiveDate::from_ymd(2020, 3, 25),
+pub(crate) fn test_data_news_item() -> NewsItem {
+    NewsItem {
+        date: NaiveDate::from_ymd_opt(2020, 3, 25).unwrap(),
         text: "京都府 新型コロナウイルス感染症 対策サイト".to_string(),
-        url: "https://kyoto.stopcovid19.jp/".to_string(),
-    };
+        url: "https://kyoto.stopcovid19.jp/".parse().unwrap(),
+    }
 }
 
 /// NewsItems構造体のテスト用のデータを生成します。
-fn test_data_news_items() -> NewsItems {
-    return NewsItems {
+pub(crate) fn test_data_news_items() -> NewsItems {
+    NewsItems {
         news_items: vec![test_data_news_item()],
-    };
+    }
+}
+
+/// LocalizedNewsItem構造体のテスト用のデータを生成します。
+pub(crate) fn test_data_localized_news_item() -> LocalizedNewsItem {
+    LocalizedNewsItem {
+        date: NaiveDate::from_ymd_opt(2020, 3, 25).unwrap(),
+        text: LocalizedText {
+            ja: "京都府 新型コロナウイルス感染症 対策サイト".to_string(),
+            en: Some("Kyoto Prefecture COVID-19 Information Site".to_string()),
+            zh: None,
+            ko: Some("교토부 신종 코로나바이러스 감염증 대책 사이트".to_string()),
+        },
+        url: "https://kyoto.stopcovid19.jp/".parse().unwrap(),
+    }
 }
 
 /// Status構造体のテスト用データを生成します。
-fn test_data_status() -> Status {
-    return Status {
+pub(crate) fn test_data_status() -> Status {
+    Status {
         attr: StatusAttributes::Accommodations,
         value: 32,
         children: None,
         last_update: None,
-    };
+    }
 }
 
 /// Status構造体のテスト用のデータを生成します。(子属性を含む)
-fn test_data_status_with_children() -> Status {
-    return Status {
+pub(crate) fn test_data_status_with_children() -> Status {
+    Status {
         attr: StatusAttributes::Patients,
         value: 4096,
         children: Some(vec![test_data_status()]),
         last_update: Some(dummy_localdate()),
-    };
+    }
 }
 
 /// SummaryContent構造体のテスト用のデータを生成します。
-fn test_data_summary_content() -> SummaryContent {
-    return SummaryContent {
-        date: "2020-03-25T09:25:00.000Z".parse::<DateTime<Utc>>().unwrap(),
+pub(crate) fn test_data_summary_content() -> SummaryContent {
+    SummaryContent {
+        date: NaiveDate::from_ymd_opt(2020, 3, 25).unwrap(),
         sum: 10,
-    };
+    }
 }
 
 /// Summary構造体のテスト用のデータを生成します。
-fn test_data_summary() -> Summary {
-    return Summary {
+pub(crate) fn test_data_summary() -> Summary {
+    Summary {
         data: vec![test_data_summary_content()],
         last_update: dummy_localdate(),
-    };
+    }
 }
 
 /// 2020年3月25日 21時40分をDateTime<Local>型で表現し、これをダミーのデータとして扱います。
 ///
 /// このダミーデータで扱う日付・時刻は、対策サイトが産声を上げた瞬間を指しています。
-fn dummy_localdate() -> DateTime<Local> {
-    return Local
-        .datetime_from_str("2020/03/25 21:40", DATETIME_FORMAT)
-        .unwrap();
+pub(crate) fn dummy_localdate() -> DateTime<Local> {
+    NaiveDateTime::parse_from_str("2020/03/25 21:40", DATETIME_FORMAT)
+        .unwrap()
+        .and_local_timezone(Local)
+        .unwrap()
+}
+
+/// 各構造体のフィールド名の定数が、シリアライズ結果のフィールド名と一致することをテストします。
+#[test]
+fn fields_match_serialized_keys() {
+    assert_fields(
+        &test_data_last_update(),
+        crate::structs::last_update::FIELDS,
+    );
+    assert_fields(
+        &test_data_news_item(),
+        crate::structs::news::NEWS_ITEM_FIELDS,
+    );
+    assert_fields(
+        &test_data_status_with_children(),
+        crate::structs::status::STATUS_FIELDS,
+    );
+    assert_fields(
+        &test_data_summary(),
+        crate::structs::summary::SUMMARY_FIELDS,
+    );
+    assert_fields(
+        &test_data_summary_content(),
+        crate::structs::summary::SUMMARYCONTENT_FIELDS,
+    );
+}
+
+/// フィールドが存在しない場合に、エラーに正しいフィールド名が示されることをテストします。
+#[test]
+fn deserialize_missing_field_names() {
+    let error = serde_json::from_str::<LastUpdate<Local>>("{}").unwrap_err();
+    assert!(
+        error.to_string().contains("missing field `last_update`"),
+        "{}",
+        error
+    );
+
+    let error = serde_json::from_str::<Summary>(r#"{"data":[]}"#).unwrap_err();
+    assert!(
+        error.to_string().contains("missing field `last_update`"),
+        "{}",
+        error
+    );
+
+    let error = serde_json::from_str::<SummaryContent>(r#"{"sum":1}"#).unwrap_err();
+    assert!(
+        error.to_string().contains("missing field `date`"),
+        "{}",
+        error
+    );
+}
+
+/// Summaryのlast_updateが重複した場合に、エラーにlast_updateが示されることをテストします。
+#[test]
+fn deserialize_summary_duplicate_last_update() {
+    let serialized =
+        r#"{"data":[],"last_update":"2020/03/25 21:40","last_update":"2020/03/25 21:40"}"#;
+    let error = serde_json::from_str::<Summary>(serialized).unwrap_err();
+    assert!(
+        error.to_string().contains("duplicate field `last_update`"),
+        "{}",
+        error
+    );
+}
+
+/// シリアライズ結果のフィールド名が、定数のフィールド名と一致することを確認します。
+fn assert_fields<T: serde::Serialize>(value: &T, fields: &[&str]) {
+    let value = serde_json::to_value(value).unwrap();
+    let mut keys = value
+        .as_object()
+        .unwrap()
+        .keys()
+        .cloned()
+        .collect::<Vec<String>>();
+    let mut fields = fields
+        .iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<String>>();
+    keys.sort();
+    fields.sort();
+    assert_eq!(keys, fields);
 }
@@ -0,0 +1,172 @@
+use crate::{
+    codegen::typescript::{declarations, StringFormat, TypeScriptDefinition},
+    structs::{
+        age_distribution::{
+            AgeDistribution, AgeDistributionContent, AgeGroup, DailyAgeDistribution,
+        },
+        last_update::LastUpdate,
+        news::{
+            localized::{LocalizedNewsItem, LocalizedNewsItems, LocalizedText},
+            NewsItem, NewsItems,
+        },
+        status::{Attributes, Status},
+        summary::{Summary, SummaryContent},
+        vaccination::{CoverageGroup, Vaccination, VaccinationContent, VaccinationCoverage},
+    },
+    tests::age_distribution_test::test_data_age_distribution,
+    tests::structs_test::{
+        test_data_last_update, test_data_localized_news_item, test_data_news_item,
+        test_data_news_items, test_data_status_with_children, test_data_summary,
+        test_data_summary_content,
+    },
+    tests::vaccination_test::test_data_vaccination,
+};
+use chrono::{Local, NaiveDate, NaiveDateTime};
+use serde::Serialize;
+use serde_json::Value;
+
+/// 型定義のプロパティ名・型・書式が、シリアライズ結果と一致しているか検証します。
+///
+/// 省略可能なプロパティは、テスト用のデータに含まれている場合のみ検証します。
+fn assert_properties<T: TypeScriptDefinition + Serialize>(value: &T) {
+    let serialized = serde_json::to_value(value).unwrap();
+    let object = serialized.as_object().unwrap();
+
+    let properties = T::properties();
+    // シリアライズ結果の全てのフィールドが、型定義に含まれ、宣言された型と書式を満たすこと
+    for (key, value) in object {
+        let property = properties
+            .iter()
+            .find(|property| property.name == key)
+            .unwrap_or_else(|| panic!("`{}` is not declared in `{}`", key, T::NAME));
+        assert_type(value, property.ty, property.format.as_ref(), key);
+    }
+    // 省略できないプロパティが、シリアライズ結果に含まれていること
+    for property in properties.iter().filter(|property| !property.optional) {
+        assert!(object.contains_key(property.name));
+    }
+}
+
+/// シリアライズ結果の値が、TypeScriptの型と文字列の書式を満たしているか検証します。
+///
+/// 配列は各要素を検証し、型定義の名前は文字列リテラル型かインターフェースかによって検証します。
+fn assert_type(value: &Value, ty: &str, format: Option<&StringFormat>, key: &str) {
+    if let Some(element) = ty.strip_suffix("[]") {
+        let values = value
+            .as_array()
+            .unwrap_or_else(|| panic!("`{}` is not an array", key));
+        for value in values {
+            assert_type(value, element, format, key);
+        }
+        return;
+    }
+    match ty {
+        "number" => assert!(value.is_number(), "`{}` is not a number", key),
+        "string" => {
+            let text = value
+                .as_str()
+                .unwrap_or_else(|| panic!("`{}` is not a string", key));
+            // 書式が宣言されている場合は、その書式で解釈できること
+            let valid = match format {
+                None => true,
+                Some(StringFormat::Chrono(format)) => {
+                    NaiveDate::parse_from_str(text, format).is_ok()
+                        || NaiveDateTime::parse_from_str(text, format).is_ok()
+                }
+                Some(StringFormat::Url) => url::Url::parse(text).is_ok(),
+            };
+            assert!(valid, "`{}` does not match {:?}: {}", key, format, text);
+        }
+        _ => {
+            let output = declarations();
+            if output.contains(&format!("export type {} =", ty)) {
+                // 文字列リテラル型の場合は、宣言されたリテラルのいずれかであること
+                let literal = serde_json::to_string(value).unwrap();
+                let declaration = output
+                    .split("export ")
+                    .find(|declaration| declaration.starts_with(&format!("type {} =", ty)))
+                    .unwrap();
+                assert!(
+                    declaration.contains(&format!("| {}", literal)),
+                    "`{}` is not a member of `{}`: {}",
+                    key,
+                    ty,
+                    literal
+                );
+            } else {
+                assert!(
+                    output.contains(&format!("export interface {} {{", ty)),
+                    "`{}` is not declared",
+                    ty
+                );
+                assert!(value.is_object(), "`{}` is not an object", key);
+            }
+        }
+    }
+}
+
+/// 各構造体の型定義が、シリアライズ結果と一致していることをテストします。
+#[test]
+fn typescript_properties_match_serialized_fields() {
+    assert_properties(&test_data_status_with_children());
+    assert_properties(&test_data_summary());
+    assert_properties(&test_data_summary_content());
+    assert_properties(&test_data_news_item());
+    assert_properties(&test_data_news_items());
+    assert_properties(&test_data_localized_news_item());
+    assert_properties(&test_data_localized_news_item().text);
+    assert_properties(&test_data_last_update());
+    let distribution = test_data_age_distribution();
+    assert_properties(&distribution);
+    assert_properties(&distribution.data[0]);
+    assert_properties(&distribution.daily.unwrap()[0]);
+    let vaccination = test_data_vaccination();
+    assert_properties(&vaccination);
+    assert_properties(&vaccination.data[0]);
+    assert_properties(&vaccination.coverage[0]);
+}
+
+/// Attributesの文字列リテラル型に、全ての属性が含まれていることをテストします。
+#[test]
+fn typescript_attributes_union() {
+    let declaration = Attributes::declaration();
+    for attr in Attributes::ALL {
+        let literal = serde_json::to_string(attr).unwrap();
+        assert!(declaration.contains(&format!("| {}", literal)));
+    }
+}
+
+/// 全ての型定義が出力されることをテストします。
+#[test]
+fn typescript_declarations() {
+    let output = declarations();
+    for name in [
+        Attributes::NAME,
+        Status::NAME,
+        SummaryContent::NAME,
+        Summary::NAME,
+        NewsItem::NAME,
+        NewsItems::NAME,
+        LocalizedText::NAME,
+        LocalizedNewsItem::NAME,
+        LocalizedNewsItems::NAME,
+        LastUpdate::<Local>::NAME,
+        AgeGroup::NAME,
+        AgeDistributionContent::NAME,
+        DailyAgeDistribution::NAME,
+        AgeDistribution::NAME,
+        CoverageGroup::NAME,
+        VaccinationContent::NAME,
+        VaccinationCoverage::NAME,
+        Vaccination::NAME,
+    ] {
+        assert!(
+            output.contains(&format!("export type {} =", name))
+                || output.contains(&format!("export interface {} {{", name))
+        );
+    }
+    assert!(output.contains("  children?: Status[];"));
+    assert!(output.contains("  daily?: DailyAgeDistribution[];"));
+    assert!(output.contains("  | \"非公表\""));
+    assert!(output.contains("  | \"全体\""));
+}
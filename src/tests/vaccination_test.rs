@@ -0,0 +1,184 @@
+use crate::{
+    population::{age_band::AgeBand, PopulationDataset, PREFECTURE},
+    structs::vaccination::{
+        CoverageGroup, Dose, PerDose, Vaccination, VaccinationContent, VaccinationCoverage,
+    },
+    tests::structs_test::dummy_localdate,
+};
+use chrono::NaiveDate;
+
+/// Vaccination構造体のテスト用のデータを生成します。
+pub(crate) fn test_data_vaccination() -> Vaccination {
+    Vaccination {
+        data: vec![
+            VaccinationContent {
+                date: NaiveDate::from_ymd_opt(2021, 5, 1).unwrap(),
+                doses: PerDose {
+                    first: 300,
+                    second: 100,
+                    third: 0,
+                    booster: 0,
+                },
+            },
+            VaccinationContent {
+                date: NaiveDate::from_ymd_opt(2021, 5, 2).unwrap(),
+                doses: PerDose {
+                    first: 200,
+                    second: 150,
+                    third: 20,
+                    booster: 5,
+                },
+            },
+        ],
+        coverage: vec![
+            VaccinationCoverage {
+                age_group: CoverageGroup::All,
+                rates: PerDose {
+                    first: 50.0,
+                    second: 25.0,
+                    third: 2.0,
+                    booster: 0.5,
+                },
+            },
+            VaccinationCoverage::from_counts(
+                CoverageGroup::Band(AgeBand::Over90),
+                PerDose {
+                    first: 90,
+                    second: 45,
+                    third: 9,
+                    booster: 0,
+                },
+                100,
+            )
+            .unwrap(),
+        ],
+        last_update: dummy_localdate(),
+    }
+}
+
+/// 回次ごとの接種回数の累計と、接種率をテストします。
+#[test]
+fn vaccination_cumulative() {
+    let vaccination = test_data_vaccination();
+    let cumulative = vaccination.cumulative();
+    assert_eq!(
+        Dose::ALL.map(|dose| cumulative.get(dose)),
+        [500, 250, 20, 5]
+    );
+    assert_eq!(vaccination.data[1].total(), 375);
+
+    let over90 = vaccination
+        .coverage_of(CoverageGroup::Band(AgeBand::Over90))
+        .unwrap();
+    assert_eq!(over90.rates.first, 90.0);
+    assert_eq!(over90.rates.third, 9.0);
+    assert_eq!(
+        vaccination.coverage_of(CoverageGroup::Band(AgeBand::Teens)),
+        None
+    );
+    assert_eq!(
+        VaccinationCoverage::from_counts(CoverageGroup::All, PerDose::default(), 0),
+        None
+    );
+
+    // テスト用の人口です。(実際の人口ではありません)
+    let dataset = PopulationDataset::from_csv(
+        2020,
+        "テスト",
+        "region,age_band,population\n京都府,,1000\n".as_bytes(),
+    )
+    .unwrap();
+    let overall = vaccination.overall_coverage(&dataset, PREFECTURE).unwrap();
+    assert_eq!(overall.age_group, CoverageGroup::All);
+    assert_eq!(overall.rates.first, 50.0);
+    assert_eq!(overall.rates.booster, 0.5);
+    assert!(vaccination.overall_coverage(&dataset, "京都市").is_err());
+}
+
+/// ワクチン接種の実績のシリアライズ・デシリアライズをテストします。
+#[test]
+fn vaccination_serde() {
+    let vaccination = test_data_vaccination();
+    let json = serde_json::to_value(&vaccination).unwrap();
+    assert_eq!(json["data"][0]["date"], "2021/05/01");
+    assert_eq!(json["data"][1]["booster"], 5);
+    assert_eq!(json["coverage"][0]["age_group"], "全体");
+    assert_eq!(json["coverage"][1]["age_group"], "90歳以上");
+    assert_eq!(json["last_update"], "2020/03/25 21:40");
+    assert_eq!(
+        serde_json::from_value::<Vaccination>(json).unwrap(),
+        vaccination
+    );
+
+    // 3回目以降の接種と接種率を持たない、以前のデータファイルも読み込める
+    let vaccination = serde_json::from_str::<Vaccination>(
+        r#"{"data":[{"date":"2021-05-01","first":3,"second":1}],"last_update":"2021/05/01 21:40"}"#,
+    )
+    .unwrap();
+    assert_eq!(vaccination.data[0].doses.third, 0);
+    assert!(vaccination.coverage.is_empty());
+
+    for (invalid, message) in [
+        (
+            r#"{"data":[{"date":"2021/05/01","first":3,"first":1}],"last_update":"2021/05/01 21:40"}"#,
+            "duplicate field `first`",
+        ),
+        (
+            r#"{"data":[{"first":3}],"last_update":"2021/05/01 21:40"}"#,
+            "missing field `date`",
+        ),
+        (
+            r#"{"data":[],"coverage":[{"age_group":"高齢者"}],"last_update":"2021/05/01 21:40"}"#,
+            "高齢者",
+        ),
+        (
+            r#"{"data":[{"date":"2021/05/01","fourth":3}],"last_update":"2021/05/01 21:40"}"#,
+            "unknown field `fourth`",
+        ),
+        (
+            r#"{"data":[],"coverage":[{"age_group":"全体","booster":1.0,"booster":2.0}],"last_update":"2021/05/01 21:40"}"#,
+            "duplicate field `booster`",
+        ),
+        (
+            r#"{"data":[],"coverage":[{"age_group":"全体","first":1.0},{"age_group":"全体","first":2.0}],"last_update":"2021/05/01 21:40"}"#,
+            "duplicate coverage group `全体`",
+        ),
+        (
+            r#"{"data":[{"date":"2021/05/01","second":1}],"last_update":"2021/05/01 21:40"}"#,
+            "missing field `first`",
+        ),
+        (
+            r#"{"data":[{"date":"2021/05/01","first":3,"third":1}],"last_update":"2021/05/01 21:40"}"#,
+            "missing field `second`",
+        ),
+        (
+            r#"{"data":[],"coverage":[{"age_group":"全体","first":100.5}],"last_update":"2021/05/01 21:40"}"#,
+            "coverage rate `100.5` of `first` must be between 0 and 100",
+        ),
+        (
+            r#"{"data":[],"coverage":[{"age_group":"全体","second":-0.5}],"last_update":"2021/05/01 21:40"}"#,
+            "coverage rate `-0.5` of `second` must be between 0 and 100",
+        ),
+    ] {
+        let error = serde_json::from_str::<Vaccination>(invalid).unwrap_err();
+        assert!(error.to_string().contains(message), "{}", error);
+    }
+}
+
+/// JSON形式では表現できない、有限でない接種率がエラーとなることをテストします。
+#[cfg(feature = "yaml")]
+#[test]
+fn vaccination_non_finite_rate() {
+    for rate in [".nan", ".inf", "-.inf"] {
+        let yaml = format!(
+            "data: []\ncoverage:\n- age_group: 全体\n  first: {}\nlast_update: 2021/05/01 21:40\n",
+            rate
+        );
+        let error = serde_yaml::from_str::<Vaccination>(&yaml).unwrap_err();
+        assert!(
+            error.to_string().contains("must be between 0 and 100"),
+            "{}",
+            error
+        );
+    }
+}
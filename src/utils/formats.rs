@@ -1,2 +1,80 @@
-pub const DATE_FORMAT: &'static str = "%Y/%m/%d";
-pub const DATETIME_FORMAT: &'static str = "%Y/%m/%d %R";
+use chrono::{DateTime, FixedOffset, NaiveDate};
+use serde::de::{DeserializeSeed, Deserializer, Error as DeserializationError, Visitor};
+use std::{
+    fmt::{Display, Error, Formatter},
+    marker::PhantomData,
+};
+
+pub mod codec;
+pub mod era;
+
+/// 日付を文字列で表現する際の書式です。
+pub const DATE_FORMAT: &str = "%Y/%m/%d";
+/// 日付と時刻を文字列で表現する際の書式です。
+pub const DATETIME_FORMAT: &str = "%Y/%m/%d %R";
+
+/// 日本標準時(JST)の、協定世界時からの時差(秒)です。
+pub const JST_OFFSET_SECONDS: i32 = 9 * 60 * 60;
+
+/// 日本標準時(JST)のタイムゾーンを返却します。
+pub fn jst() -> FixedOffset {
+    FixedOffset::east_opt(JST_OFFSET_SECONDS).unwrap()
+}
+
+/// 日付を、日本標準時の0時0分の日時として返却します。
+pub fn jst_midnight(date: NaiveDate) -> DateTime<FixedOffset> {
+    date.and_hms_opt(0, 0, 0)
+        .unwrap()
+        .and_local_timezone(jst())
+        .unwrap()
+}
+
+/// 文字列を読み込み、指定された関数でパースするDeserializeSeedです。
+///
+/// `MapAccess::next_value_seed`に渡すことで、パースに失敗した位置をフィールド単位で報告できます。
+pub(crate) struct ParseSeed<T, E, F>(F, PhantomData<(T, E)>);
+
+impl<T, E, F> ParseSeed<T, E, F>
+where
+    E: Display,
+    F: FnOnce(&str) -> Result<T, E>,
+{
+    /// パースに使用する関数を指定して生成します。
+    pub(crate) fn new(parse: F) -> Self {
+        ParseSeed(parse, PhantomData)
+    }
+}
+
+impl<'de, T, E, F> DeserializeSeed<'de> for ParseSeed<T, E, F>
+where
+    E: Display,
+    F: FnOnce(&str) -> Result<T, E>,
+{
+    type Value = T;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(self)
+    }
+}
+
+impl<'de, T, E, F> Visitor<'de> for ParseSeed<T, E, F>
+where
+    E: Display,
+    F: FnOnce(&str) -> Result<T, E>,
+{
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        write!(formatter, "a string")
+    }
+
+    fn visit_str<D>(self, value: &str) -> Result<Self::Value, D>
+    where
+        D: DeserializationError,
+    {
+        (self.0)(value).map_err(DeserializationError::custom)
+    }
+}
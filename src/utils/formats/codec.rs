@@ -0,0 +1,307 @@
+//! 日付・時刻の文字列表現を読み書きする、再利用可能なコーデックを提供します。
+//!
+//! 入力は優先順位付きの複数の書式を順に試し、出力は常に単一の正規化された書式で行います。
+
+use chrono::{DateTime, Datelike, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use serde::de::{DeserializeSeed, Deserializer};
+use std::ops::RangeInclusive;
+
+use super::{
+    era::{is_era, parse_era_date, parse_era_datetime},
+    jst, ParseSeed, DATETIME_FORMAT, DATE_FORMAT,
+};
+
+/// 入力として受け付ける書式です。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum InputFormat {
+    /// chronoクレートの書式指定子で表現される書式です。日付のみの書式も指定できます。
+    Chrono(&'static str),
+    /// RFC3339形式の日付・時刻です。
+    Rfc3339,
+    /// 和暦(`令和2年3月25日`・`令和2年3月25日 21時40分`)です。
+    Era,
+}
+
+impl InputFormat {
+    /// 書式を、エラーメッセージ向けの形式で返却します。
+    fn describe(&self) -> String {
+        match self {
+            InputFormat::Chrono(format) => format!("`{}`", format),
+            InputFormat::Rfc3339 => "RFC3339".to_string(),
+            InputFormat::Era => "Japanese era".to_string(),
+        }
+    }
+}
+
+/// 出力する書式です。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum OutputFormat {
+    /// chronoクレートの書式指定子で表現される書式です。
+    Chrono(&'static str),
+    /// RFC3339形式の日付・時刻です。
+    Rfc3339,
+}
+
+/// 入力の書式から読み込まれた値です。
+///
+/// 書式によって、時刻やタイムゾーンの有無が異なります。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Parsed {
+    /// 日付のみを含む値です。
+    Date(NaiveDate),
+    /// タイムゾーンを含まない日付と時刻です。
+    Naive(NaiveDateTime),
+    /// 協定世界時からの時差を含む日付と時刻です。
+    Fixed(DateTime<FixedOffset>),
+}
+
+/// コーデックで読み書きできる、日付・時刻の型です。
+pub trait CodecValue: Sized {
+    /// 型の説明です。エラーメッセージに使用します。
+    const KIND: &'static str;
+
+    /// 読み込まれた値を変換します。変換できない場合は`None`を返却します。
+    fn from_parsed(parsed: Parsed) -> Option<Self>;
+
+    /// 値を、指定された書式で出力します。
+    fn format(&self, format: OutputFormat) -> String;
+}
+
+/// 日付は、日本標準時の日付として扱います。
+///
+/// RFC3339形式では、日本標準時の0時0分を協定世界時で出力します。(例: `2020-03-24T15:00:00+00:00`)
+impl CodecValue for NaiveDate {
+    const KIND: &'static str = "date";
+
+    fn from_parsed(parsed: Parsed) -> Option<Self> {
+        Some(match parsed {
+            Parsed::Date(date) => date,
+            Parsed::Naive(datetime) => datetime.date(),
+            Parsed::Fixed(datetime) => datetime.with_timezone(&jst()).date_naive(),
+        })
+    }
+
+    fn format(&self, format: OutputFormat) -> String {
+        match format {
+            OutputFormat::Chrono(format) => self.format(format).to_string(),
+            OutputFormat::Rfc3339 => self
+                .and_time(NaiveTime::MIN)
+                .and_local_timezone(jst())
+                .unwrap()
+                .with_timezone(&Utc)
+                .to_rfc3339(),
+        }
+    }
+}
+
+/// 時刻を含まない値は受け付けません。
+impl CodecValue for DateTime<Local> {
+    const KIND: &'static str = "datetime";
+
+    fn from_parsed(parsed: Parsed) -> Option<Self> {
+        match parsed {
+            Parsed::Date(_) => None,
+            // 夏時間の終了により2回現れる時刻は、先の時刻として扱う
+            // (出力した文字列を再度読み込めるようにするため)
+            Parsed::Naive(datetime) => datetime.and_local_timezone(Local).earliest(),
+            Parsed::Fixed(datetime) => Some(datetime.with_timezone(&Local)),
+        }
+    }
+
+    fn format(&self, format: OutputFormat) -> String {
+        match format {
+            OutputFormat::Chrono(format) => self.format(format).to_string(),
+            OutputFormat::Rfc3339 => self.to_rfc3339(),
+        }
+    }
+}
+
+/// タイムゾーンを含まない値は、日本標準時として扱います。
+impl CodecValue for DateTime<Utc> {
+    const KIND: &'static str = "datetime";
+
+    fn from_parsed(parsed: Parsed) -> Option<Self> {
+        let datetime = match parsed {
+            Parsed::Date(date) => date.and_time(NaiveTime::MIN),
+            Parsed::Naive(datetime) => datetime,
+            Parsed::Fixed(datetime) => return Some(datetime.with_timezone(&Utc)),
+        };
+        datetime
+            .and_local_timezone(jst())
+            .single()
+            .map(|datetime| datetime.with_timezone(&Utc))
+    }
+
+    fn format(&self, format: OutputFormat) -> String {
+        match format {
+            OutputFormat::Chrono(format) => self.format(format).to_string(),
+            OutputFormat::Rfc3339 => self.to_rfc3339(),
+        }
+    }
+}
+
+/// 日付・時刻の文字列表現を読み書きするコーデックです。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DateCodec {
+    /// 入力として受け付ける書式です。先頭の書式ほど優先して使用します。
+    pub inputs: &'static [InputFormat],
+    /// 出力する書式です。
+    pub output: OutputFormat,
+}
+
+/// 日付のコーデックです。(`NewsItem`の`date`等)
+///
+/// `2020/03/25`の形式で出力し、`2020-03-25`・`2020/3/25`・和暦・RFC3339形式も受け付けます。
+pub const DATE: DateCodec = DateCodec {
+    inputs: &[
+        InputFormat::Chrono(DATE_FORMAT),
+        InputFormat::Chrono("%Y-%m-%d"),
+        InputFormat::Era,
+        InputFormat::Rfc3339,
+    ],
+    output: OutputFormat::Chrono(DATE_FORMAT),
+};
+
+/// 日付と時刻のコーデックです。(`last_update`等)
+///
+/// `2020/03/25 21:40`の形式で出力し、`2020-03-25 21:40`・和暦・RFC3339形式も受け付けます。
+pub const DATETIME: DateCodec = DateCodec {
+    inputs: &[
+        InputFormat::Chrono(DATETIME_FORMAT),
+        InputFormat::Chrono("%Y-%m-%d %R"),
+        InputFormat::Era,
+        InputFormat::Rfc3339,
+    ],
+    output: OutputFormat::Chrono(DATETIME_FORMAT),
+};
+
+/// RFC3339形式の日付と時刻のコーデックです。(以前の形式の`SummaryContent`の`date`)
+///
+/// RFC3339形式で出力し、日付のみの値(`2020/03/25`等)は日本標準時の0時0分として受け付けます。
+pub const TIMESTAMP: DateCodec = DateCodec {
+    inputs: &[
+        InputFormat::Rfc3339,
+        InputFormat::Chrono(DATETIME_FORMAT),
+        InputFormat::Chrono(DATE_FORMAT),
+        InputFormat::Chrono("%Y-%m-%d"),
+        InputFormat::Era,
+    ],
+    output: OutputFormat::Rfc3339,
+};
+
+impl DateCodec {
+    /// 文字列を、入力の書式の優先順位に従ってパースします。
+    pub fn parse<T: CodecValue>(&self, value: &str) -> Result<T, String> {
+        let trimmed = value.trim();
+        for input in self.inputs {
+            if let Some(value) = parse_input(input, trimmed)
+                .filter(in_range)
+                .and_then(T::from_parsed)
+            {
+                return Ok(value);
+            }
+        }
+
+        // 和暦として解釈できる場合は、より詳細なエラーを返却する
+        if self.inputs.contains(&InputFormat::Era) && is_era(trimmed) {
+            let error = match T::KIND {
+                "date" => parse_era_date(trimmed).err(),
+                _ => parse_era_datetime(trimmed).err(),
+            };
+            if let Some(error) = error {
+                return Err(error);
+            }
+        }
+        Err(format!(
+            "invalid {} `{}` (expected {})",
+            T::KIND,
+            value,
+            self.inputs
+                .iter()
+                .map(InputFormat::describe)
+                .collect::<Vec<String>>()
+                .join(", ")
+        ))
+    }
+
+    /// 値を、出力の書式で文字列に変換します。
+    pub fn format<T: CodecValue>(&self, value: &T) -> String {
+        value.format(self.output)
+    }
+
+    /// 文字列を読み込み、このコーデックでパースするDeserializeSeedを返却します。
+    ///
+    /// `MapAccess::next_value_seed`に渡すことで、フィールドごとに異なるコーデックで読み込めます。
+    pub fn seed<'de, T: CodecValue>(self) -> impl DeserializeSeed<'de, Value = T> {
+        ParseSeed::new(move |value: &str| self.parse(value))
+    }
+
+    /// 文字列をデシリアライズし、このコーデックでパースします。
+    ///
+    /// `#[serde(deserialize_with = "...")]`に指定した関数から呼び出すことで、フィールドごとに入力の書式を選択できます。
+    pub fn deserialize<'de, T, D>(&self, deserializer: D) -> Result<T, D::Error>
+    where
+        T: CodecValue,
+        D: Deserializer<'de>,
+    {
+        self.seed().deserialize(deserializer)
+    }
+}
+
+/// 日付・時刻の値、または日付・時刻として解釈できる文字列です。
+///
+/// ビルダー等で、値と文字列のいずれも受け付けるために使用します。
+pub trait DateInput<T> {
+    /// 日付・時刻の値に変換します。文字列は、型に対応するコーデックでパースします。
+    fn into_date(self) -> Result<T, String>;
+}
+
+impl<T: CodecValue> DateInput<T> for T {
+    fn into_date(self) -> Result<T, String> {
+        Ok(self)
+    }
+}
+
+/// 文字列は、`DATE`コーデックでパースします。
+impl DateInput<NaiveDate> for &str {
+    fn into_date(self) -> Result<NaiveDate, String> {
+        DATE.parse(self)
+    }
+}
+
+/// 文字列は、`DATETIME`コーデックでパースします。
+impl DateInput<DateTime<Local>> for &str {
+    fn into_date(self) -> Result<DateTime<Local>, String> {
+        DATETIME.parse(self)
+    }
+}
+
+/// 受け付ける年の範囲です。
+///
+/// 範囲外の値は、タイムゾーンの変換で表現できなくなる場合や、出力の書式で桁数が変わる場合があるため受け付けません。
+const YEARS: RangeInclusive<i32> = 1..=9999;
+
+/// 読み込まれた値が、受け付ける年の範囲内であるか判定します。
+fn in_range(parsed: &Parsed) -> bool {
+    let year = match parsed {
+        Parsed::Date(date) => date.year(),
+        Parsed::Naive(datetime) => datetime.year(),
+        Parsed::Fixed(datetime) => datetime.year(),
+    };
+    YEARS.contains(&year)
+}
+
+/// 文字列を、指定された入力の書式でパースします。
+fn parse_input(input: &InputFormat, value: &str) -> Option<Parsed> {
+    match input {
+        InputFormat::Chrono(format) => NaiveDateTime::parse_from_str(value, format)
+            .map(Parsed::Naive)
+            .or_else(|_| NaiveDate::parse_from_str(value, format).map(Parsed::Date))
+            .ok(),
+        InputFormat::Rfc3339 => DateTime::parse_from_rfc3339(value).ok().map(Parsed::Fixed),
+        InputFormat::Era => parse_era_datetime(value)
+            .map(Parsed::Naive)
+            .or_else(|_| parse_era_date(value).map(Parsed::Date))
+            .ok(),
+    }
+}
@@ -0,0 +1,260 @@
+//! 和暦(令和・平成)で表現された日付の読み書きを行います。
+//!
+//! `令和2年3月25日`のような表記に加え、元年(`令和元年`)と全角数字(`令和２年３月２５日`)に対応します。
+
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+use std::{
+    borrow::Cow,
+    fmt::{Display, Error, Formatter},
+};
+
+/// 元号です。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Era {
+    /// 平成(1989年1月8日〜2019年4月30日)です。
+    Heisei,
+    /// 令和(2019年5月1日〜)です。
+    Reiwa,
+}
+
+impl Era {
+    /// 全ての元号の一覧です。古い順に並んでいます。
+    pub const ALL: &'static [Era] = &[Era::Heisei, Era::Reiwa];
+
+    /// 元号の名前を返却します。
+    pub fn name(&self) -> &'static str {
+        match self {
+            Era::Heisei => "平成",
+            Era::Reiwa => "令和",
+        }
+    }
+
+    /// 元号の初日を返却します。
+    pub fn start(&self) -> NaiveDate {
+        match self {
+            Era::Heisei => NaiveDate::from_ymd_opt(1989, 1, 8).unwrap(),
+            Era::Reiwa => NaiveDate::from_ymd_opt(2019, 5, 1).unwrap(),
+        }
+    }
+
+    /// 元号の最終日を返却します。現在の元号の場合は`None`を返却します。
+    pub fn end(&self) -> Option<NaiveDate> {
+        Era::ALL
+            .iter()
+            .find(|era| *era > self)
+            .and_then(|next| next.start().pred_opt())
+    }
+
+    /// 指定された日付の元号を返却します。平成より前の日付の場合は`None`を返却します。
+    pub fn of(date: NaiveDate) -> Option<Era> {
+        Era::ALL
+            .iter()
+            .rev()
+            .find(|era| era.start() <= date)
+            .copied()
+    }
+
+    /// 指定された日付の、元号における年(元年は1)を返却します。
+    fn year_of(&self, date: NaiveDate) -> i32 {
+        date.year() - self.start().year() + 1
+    }
+}
+
+impl Display for Era {
+    fn fmt(&self, formatter: &mut Formatter) -> Result<(), Error> {
+        write!(formatter, "{}", self.name())
+    }
+}
+
+/// 和暦で出力する際の数字の表記です。
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Digits {
+    /// 半角数字(`令和2年3月25日`)です。
+    HalfWidth,
+    /// 全角数字(`令和２年３月２５日`)です。
+    FullWidth,
+}
+
+/// 全角数字と全角空白を、半角に変換します。
+pub fn normalize_digits(value: &str) -> Cow<'_, str> {
+    if !value.chars().any(is_full_width) {
+        return Cow::Borrowed(value);
+    }
+    Cow::Owned(
+        value
+            .chars()
+            .map(|c| match c {
+                '０'..='９' => char::from(b'0' + (c as u32 - '０' as u32) as u8),
+                '：' => ':',
+                '\u{3000}' => ' ',
+                c => c,
+            })
+            .collect(),
+    )
+}
+
+/// 半角への変換の対象となる文字であるか判定します。
+fn is_full_width(c: char) -> bool {
+    matches!(c, '０'..='９' | '：' | '\u{3000}')
+}
+
+/// 元号から始まる、和暦で表現された文字列であるか判定します。
+pub fn is_era(value: &str) -> bool {
+    let value = value.trim_start_matches([' ', '\u{3000}']);
+    Era::ALL.iter().any(|era| value.starts_with(era.name()))
+}
+
+/// 和暦の日付を、日付としてパースします。(例: `令和2年3月25日`、`令和元年5月1日`)
+///
+/// 全角数字にも対応します。元号の期間外の日付(`平成32年`等)はエラーとします。
+pub fn parse_era_date(value: &str) -> Result<NaiveDate, String> {
+    let normalized = normalize_digits(value);
+    let (date, rest) = parse_era_date_prefix(value, normalized.trim())?;
+    if !rest.is_empty() {
+        return Err(format!(
+            "invalid era date `{}`: unexpected trailing `{}`",
+            value, rest
+        ));
+    }
+    Ok(date)
+}
+
+/// 和暦の日付と時刻を、日付と時刻としてパースします。(例: `令和2年3月25日 21時40分`、`令和2年3月25日 21:40`)
+///
+/// 全角数字にも対応します。
+pub fn parse_era_datetime(value: &str) -> Result<NaiveDateTime, String> {
+    let normalized = normalize_digits(value);
+    let (date, rest) = parse_era_date_prefix(value, normalized.trim())?;
+    let rest = rest.trim_start();
+
+    let invalid = || {
+        format!(
+            "invalid era datetime `{}` (expected e.g. `令和2年3月25日 21時40分`)",
+            value
+        )
+    };
+    let (hour, rest) = take_number(rest).ok_or_else(invalid)?;
+    let rest = rest
+        .strip_prefix('時')
+        .or_else(|| rest.strip_prefix(':'))
+        .ok_or_else(invalid)?;
+    let (minute, rest) = take_number(rest).ok_or_else(invalid)?;
+    let rest = rest.strip_prefix('分').unwrap_or(rest);
+    if !rest.is_empty() {
+        return Err(invalid());
+    }
+
+    let time = NaiveTime::from_hms_opt(hour, minute, 0)
+        .ok_or_else(|| format!("invalid era datetime `{}`: time out of range", value))?;
+    Ok(date.and_time(time))
+}
+
+/// 文字列の先頭にある和暦の日付をパースし、残りの文字列とともに返却します。
+fn parse_era_date_prefix<'a>(value: &str, input: &'a str) -> Result<(NaiveDate, &'a str), String> {
+    let invalid = || {
+        format!(
+            "invalid era date `{}` (expected e.g. `令和2年3月25日`)",
+            value
+        )
+    };
+
+    // 元号を読み込む
+    let (era, rest) = Era::ALL
+        .iter()
+        .find_map(|era| input.strip_prefix(era.name()).map(|rest| (*era, rest)))
+        .ok_or_else(|| {
+            format!(
+                "invalid era date `{}`: unknown era (expected one of: {})",
+                value,
+                Era::ALL
+                    .iter()
+                    .map(Era::name)
+                    .collect::<Vec<&str>>()
+                    .join(", ")
+            )
+        })?;
+
+    // 年を読み込む(元年は1年として扱う)
+    let (year, rest) = match rest.strip_prefix('元') {
+        Some(rest) => (1, rest),
+        None => take_number(rest).ok_or_else(invalid)?,
+    };
+    let rest = rest.strip_prefix('年').ok_or_else(invalid)?;
+    // 月と日を読み込む
+    let (month, rest) = take_number(rest).ok_or_else(invalid)?;
+    let rest = rest.strip_prefix('月').ok_or_else(invalid)?;
+    let (day, rest) = take_number(rest).ok_or_else(invalid)?;
+    let rest = rest.strip_prefix('日').ok_or_else(invalid)?;
+
+    if year == 0 {
+        return Err(format!(
+            "invalid era date `{}`: year must be 1 or later",
+            value
+        ));
+    }
+    let date = i32::try_from(year)
+        .ok()
+        .and_then(|year| era.start().year().checked_add(year - 1))
+        .and_then(|year| NaiveDate::from_ymd_opt(year, month, day))
+        .ok_or_else(|| format!("invalid era date `{}`: date out of range", value))?;
+
+    // 元号の期間内の日付であるか判定する
+    if date < era.start() || era.end().is_some_and(|end| end < date) {
+        return Err(format!(
+            "invalid era date `{}`: {} is outside of {}",
+            value, date, era
+        ));
+    }
+    Ok((date, rest))
+}
+
+/// 文字列の先頭にある数字を読み込み、残りの文字列とともに返却します。
+fn take_number(value: &str) -> Option<(u32, &str)> {
+    let len = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    if len == 0 {
+        return None;
+    }
+    Some((value[..len].parse().ok()?, &value[len..]))
+}
+
+/// 日付を、和暦の文字列として出力します。(例: `令和2年3月25日`、`令和元年5月1日`)
+///
+/// 平成より前の日付の場合は`None`を返却します。
+pub fn format_era_date(date: NaiveDate, digits: Digits) -> Option<String> {
+    let era = Era::of(date)?;
+    let year = match era.year_of(date) {
+        1 => "元".to_string(),
+        year => year.to_string(),
+    };
+    Some(to_digits(
+        format!("{}{}年{}月{}日", era, year, date.month(), date.day()),
+        digits,
+    ))
+}
+
+/// 日付と時刻を、和暦の文字列として出力します。(例: `令和2年3月25日 21時40分`)
+///
+/// 平成より前の日付の場合は`None`を返却します。
+pub fn format_era_datetime(datetime: NaiveDateTime, digits: Digits) -> Option<String> {
+    let date = format_era_date(datetime.date(), digits)?;
+    Some(to_digits(
+        format!("{} {}時{:02}分", date, datetime.hour(), datetime.minute()),
+        digits,
+    ))
+}
+
+/// 数字を、指定された表記に変換します。
+fn to_digits(value: String, digits: Digits) -> String {
+    match digits {
+        Digits::HalfWidth => value,
+        Digits::FullWidth => value
+            .chars()
+            .map(|c| match c {
+                '0'..='9' => char::from_u32('０' as u32 + (c as u32 - '0' as u32)).unwrap(),
+                c => c,
+            })
+            .collect(),
+    }
+}
@@ -0,0 +1,174 @@
+//! `covid19-kyoto`コマンドのテストです。
+
+use std::{
+    io::Write,
+    process::{Command, Output, Stdio},
+};
+
+/// 版ごとのデータファイルの例を格納したディレクトリです。
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/tests/fixtures");
+
+/// 指定された引数で`covid19-kyoto`コマンドを実行します。
+fn run(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_covid19-kyoto"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+/// 指定された引数と標準入力で`covid19-kyoto`コマンドを実行します。
+fn run_with_stdin(args: &[&str], input: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_covid19-kyoto"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+/// 版数を含むデータファイルを、各サブコマンドで読み込めることをテストします。
+#[test]
+fn cli_versioned_document() {
+    let file = format!("{}/v3/summary.json", FIXTURES);
+
+    let output = run(&["validate", &file, "--type", "summary"]);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(String::from_utf8_lossy(&output.stdout).ends_with(": ok (summary)\n"));
+
+    let output = run(&["inspect", &file, "--type", "summary"]);
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("2020/03/25 - 2020/03/27"));
+
+    let output = run(&["convert", &file, "--type", "summary", "--to", "csv"]);
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stdout),
+        "date,sum,last_update\n2020/03/25,2,2020/03/27 21:40\n2020/03/26,5,2020/03/27 21:40\n2020/03/27,3,2020/03/27 21:40\n"
+    );
+
+    let output = run(&["growth", &file, "--window", "3"]);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    // 対応していない版数は、不正なデータとして扱う
+    let output = run_with_stdin(
+        &["validate", "-", "--type", "summary"],
+        r#"{"version":99,"data":[],"last_update":"2020/03/27 21:40"}"#,
+    );
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("unsupported version 99"));
+}
+
+/// 年代別陽性者数を、データファイルの種別として指定できることをテストします。
+#[test]
+fn cli_age_distribution() {
+    let input = r#"{"data":[{"age_group":"20代","count":3}],"last_update":"2020/03/25 21:40"}"#;
+    let output = run_with_stdin(&["validate", "-", "--type", "age_distribution"], input);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(String::from_utf8_lossy(&output.stdout).ends_with(": ok (age_distribution)\n"));
+
+    // 区分が重複したデータファイルは、不正なデータとして扱う
+    let input = r#"{"data":[{"age_group":"20代","count":3},{"age_group":"20代","count":1}],"last_update":"2020/03/25 21:40"}"#;
+    let output = run_with_stdin(&["validate", "-", "--type", "age_distribution"], input);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("duplicate age group `20代`"));
+}
+
+/// ワクチン接種の実績を、データファイルの種別として指定できることをテストします。
+#[test]
+fn cli_vaccination() {
+    let input = r#"{"data":[{"date":"2021/05/01","first":3,"second":1}],"coverage":[{"age_group":"全体","first":50.0}],"last_update":"2021/05/01 21:40"}"#;
+    let output = run_with_stdin(&["inspect", "-", "--type", "vaccination"], input);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(String::from_utf8_lossy(&output.stdout).contains("50% / 0% / 0% / 0%"));
+
+    // 区分が重複したデータファイルは、不正なデータとして扱う
+    let input = r#"{"data":[],"coverage":[{"age_group":"全体"},{"age_group":"全体"}],"last_update":"2021/05/01 21:40"}"#;
+    let output = run_with_stdin(&["validate", "-", "--type", "vaccination"], input);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("duplicate coverage group `全体`"));
+}
+
+/// `--type`のヘルプに、全ての種別が列挙されることをテストします。
+#[test]
+fn cli_document_types() {
+    let output = run(&["validate", "--help"]);
+    assert!(output.status.success());
+    let help = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        help.contains("summary, status, news, last_update, age_distribution, vaccination"),
+        "{}",
+        help
+    );
+
+    let output = run(&["validate", "-", "--type", "patients"]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+/// 標準入力から読み込む場合に、`--write`が誤りとして扱われることをテストします。
+#[test]
+fn cli_write_stdin() {
+    let dir = std::env::temp_dir().join(format!("covid19-kyoto-cli-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    for subcommand in ["fmt", "migrate"] {
+        let output = Command::new(env!("CARGO_BIN_EXE_covid19-kyoto"))
+            .args([subcommand, "-", "--type", "last_update", "--write"])
+            .current_dir(&dir)
+            .stdin(Stdio::null())
+            .output()
+            .unwrap();
+        assert_eq!(output.status.code(), Some(2), "{}", subcommand);
+        assert!(String::from_utf8_lossy(&output.stderr).contains("`--write` cannot be used"));
+        assert!(!dir.join("-").exists());
+    }
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+/// 版数を含むお知らせの一覧を、`localize`で読み込めることをテストします。
+#[test]
+fn cli_localize_versioned() {
+    let file = format!("{}/v2/news.json", FIXTURES);
+    let output = run(&["localize", &file, "--locale", "en"]);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("https://kyoto.stopcovid19.jp/"),
+        "{}",
+        stdout
+    );
+    assert!(!stdout.contains("version"), "{}", stdout);
+
+    let output = run_with_stdin(
+        &["localize", "-", "--locale", "en"],
+        r#"{"version":99,"news_items":[]}"#,
+    );
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("unsupported version 99"));
+}